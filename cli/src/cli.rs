@@ -145,6 +145,177 @@ pub enum TopicCommands {
         /// Activate or deactivate the topic
         #[arg(long)]
         active: Option<bool>,
+
+        /// [ADMIN] Lower the global tokens-to-mint per submission for this topic
+        #[arg(long, conflicts_with = "clear_tokens_to_mint")]
+        tokens_to_mint: Option<u64>,
+
+        /// Remove the topic's tokens-to-mint override (use the global value)
+        #[arg(long)]
+        clear_tokens_to_mint: bool,
+
+        /// [ADMIN] Rep minted per tempRep converted on correct votes, in basis points (max 10000 = 1:1)
+        #[arg(long, conflicts_with = "clear_rep_mint_ratio")]
+        rep_mint_ratio_bps: Option<u16>,
+
+        /// Remove the topic's Rep mint ratio override (back to 1:1)
+        #[arg(long)]
+        clear_rep_mint_ratio: bool,
+    },
+
+    /// Grant topic-scoped roles to a wallet (topic creator or protocol authority only)
+    GrantRole {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Public key of the wallet receiving the roles
+        #[arg(index = 2)]
+        holder: String,
+
+        /// May update topic settings and voting phases
+        #[arg(long)]
+        moderator: bool,
+
+        /// May set voting phases
+        #[arg(long)]
+        phase_manager: bool,
+
+        /// May submit AI votes for the topic (protocol authority only)
+        #[arg(long)]
+        oracle: bool,
+    },
+
+    /// Revoke all of a wallet's topic-scoped roles
+    RevokeRole {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Public key of the role holder
+        #[arg(index = 2)]
+        holder: String,
+    },
+
+    /// Set a topic's per-epoch tempAlign emission budget (only the admin can raise or remove it)
+    SetEmissionBudget {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Maximum tempAlign minted per epoch (omit to remove the cap)
+        #[arg(long)]
+        budget: Option<u64>,
+
+        /// Optional new epoch length (seconds)
+        #[arg(long)]
+        epoch_duration: Option<u64>,
+    },
+
+    /// Set how much AI voting power counts when finalizing submissions in a topic
+    SetAiWeight {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// AI weight in basis points (0 = ignore AI verdicts, 10000 = same as human power)
+        #[arg(index = 2)]
+        ai_weight_bps: u16,
+
+        /// Also scale each AI verdict's power by the model's reported confidence
+        #[arg(long)]
+        confidence_scaling: bool,
+    },
+
+    /// [ADMIN] Assign the oracle that answers a topic's AI requests
+    SetOracle {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Oracle public key (omit to fall back to the global oracle)
+        #[arg(index = 2)]
+        oracle: Option<String>,
+    },
+
+    /// [ADMIN] Create a topic's own oracle committee (AI votes then need its attestations)
+    InitOracleCommittee {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Public key of a committee member (repeat for each member)
+        #[arg(long = "member", required = true)]
+        members: Vec<String>,
+
+        /// Number of matching attestations needed to complete an AI request
+        #[arg(long)]
+        threshold: u8,
+    },
+
+    /// [ADMIN] Replace a topic committee's members and threshold, or switch it off
+    UpdateOracleCommittee {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Public key of a committee member (repeat for each member)
+        #[arg(long = "member", required = true)]
+        members: Vec<String>,
+
+        /// Number of matching attestations needed to complete an AI request
+        #[arg(long)]
+        threshold: u8,
+
+        /// Stop using the committee for this topic
+        #[arg(long)]
+        inactive: bool,
+    },
+
+    /// Set a topic's per-user submission rate limits (omitted limits are removed)
+    SetRateLimits {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Maximum submissions per user per window
+        #[arg(long)]
+        max_per_window: Option<u64>,
+
+        /// Optional new window length (seconds)
+        #[arg(long)]
+        window_duration: Option<u64>,
+
+        /// Maximum unfinalized submissions per user
+        #[arg(long)]
+        max_pending: Option<u64>,
+    },
+
+    /// Set who may participate in a topic (omitted rules keep their current values)
+    SetEligibility {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Minimum permanent Rep balance (0 = no requirement)
+        #[arg(long)]
+        min_rep: Option<u64>,
+
+        /// Minimum number of accepted submissions across all topics
+        #[arg(long)]
+        min_accepted_submissions: Option<u64>,
+
+        /// Index of the parent topic in which Rep must have been earned
+        #[arg(long, conflicts_with = "clear_parent_topic")]
+        parent_topic: Option<u64>,
+
+        /// Remove the parent-topic requirement
+        #[arg(long)]
+        clear_parent_topic: bool,
+
+        /// Minimum Rep earned in the parent topic
+        #[arg(long)]
+        min_parent_rep: Option<u64>,
     },
 }
 
@@ -217,6 +388,13 @@ pub enum SubmissionCommands {
         #[arg(long)]
         amount: u64,
     },
+
+    /// Expire an AI validation request the oracle never answered (refunds the stake)
+    ExpireAiRequest {
+        /// AiValidationRequest PDA (Pubkey as String)
+        #[arg(index = 1)]
+        ai_request_pda: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -243,6 +421,10 @@ pub enum VoteCommands {
         #[arg(long = "perm-rep", value_name = "AMOUNT", default_value_t = 0)]
         perm_rep_amount: u64,
 
+        /// Most delegated tempRep to draw across your delegations (defaults to all unlocked)
+        #[arg(long = "delegated-rep", value_name = "AMOUNT")]
+        delegated_temp_rep: Option<u64>,
+
         /// Secret nonce for commitment. If not provided, one will be generated.
         #[arg(long)]
         nonce: Option<String>,
@@ -278,6 +460,21 @@ pub enum VoteCommands {
         topic_index: u64,
     },
 
+    /// Release the tempRep locked in a vote that was not revealed in time
+    SettleUnrevealed {
+        /// Submission PDA (Pubkey as String)
+        #[arg(index = 1)]
+        submission_pda: String,
+
+        /// Topic index
+        #[arg(index = 2, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Validator public key (defaults to the CLI payer if not provided)
+        #[arg(index = 3)]
+        validator: Option<String>,
+    },
+
     /// [ADMIN] Set arbitrary timestamps for voting phases
     SetPhases {
         /// Submission PDA (Pubkey as String)
@@ -319,6 +516,39 @@ pub enum TokenCommands {
         amount: u64,
     },
 
+    /// Delegate topic-specific tempRep to a validator (creates or tops up the delegation)
+    Delegate {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Validator public key
+        #[arg(index = 2)]
+        validator: String,
+
+        /// Amount of tempRep to delegate
+        #[arg(index = 3)]
+        amount: u64,
+    },
+
+    /// Withdraw unlocked tempRep from your delegation in a topic
+    Undelegate {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Amount of tempRep to withdraw
+        #[arg(index = 2)]
+        amount: u64,
+    },
+
+    /// Claim starter tempAlign for a topic from the faucet, once per user (devnet builds only)
+    Faucet {
+        /// Topic index
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+    },
+
     /// [ADMIN] Mint tokens to a user
     Mint {
         /// Token type (temp-align, align, temp-rep, rep)
@@ -332,6 +562,14 @@ pub enum TokenCommands {
         /// Amount to mint
         #[arg(index = 3)]
         amount: u64,
+
+        /// Topic index whose balance is credited (required for temp-align and temp-rep)
+        #[arg(long)]
+        topic: Option<u64>,
+
+        /// Justification recorded on-chain with the mint
+        #[arg(long)]
+        reason: String,
     },
 }
 
@@ -394,6 +632,17 @@ pub enum QueryCommands {
         #[arg(index = 2)]
         user: Option<String>,
     },
+
+    /// Get a user's delegation for a specific topic
+    Delegation {
+        /// Topic index (creation index)
+        #[arg(index = 1, value_name = "TOPIC_INDEX")]
+        topic_index: u64,
+
+        /// Delegator public key (defaults to the CLI payer if not provided)
+        #[arg(index = 2)]
+        delegator: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -444,6 +693,25 @@ pub enum InitCommands {
         #[arg(long, required = true)]
         oracle_pubkey: String,
     },
+
+    /// [ADMIN] Set a token mint's name, symbol and URI
+    Metadata {
+        /// Token type (temp-align, align, temp-rep, rep)
+        #[arg(index = 1)]
+        token_type: String,
+
+        /// Token name (max 32 characters)
+        #[arg(long)]
+        name: String,
+
+        /// Token symbol (max 10 characters)
+        #[arg(long)]
+        symbol: String,
+
+        /// URI of the token's off-chain JSON metadata (max 200 characters)
+        #[arg(long, default_value = "")]
+        uri: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -455,6 +723,85 @@ pub enum ConfigCommands {
         tokens: u64,
     },
 
+    /// [ADMIN] Update the Rep emitted into each link's validator reward pool
+    UpdateValidatorRewardEmission {
+        /// New amount of Rep added to each finalized link's reward pool
+        #[arg(index = 1)]
+        amount: u64,
+    },
+
+    /// [ADMIN] Update the commission validators keep from delegators' vote rewards
+    UpdateDelegationCommission {
+        /// New commission in basis points (0-10000)
+        #[arg(index = 1)]
+        bps: u16,
+    },
+
+    /// [ADMIN] Update the lamports paid to whoever finalizes a link or vote
+    UpdateFinalizationReward {
+        /// New reward in lamports per finalization (0 disables it)
+        #[arg(index = 1)]
+        lamports: u64,
+
+        /// Revealed voting power a link needs before finalizing it pays the reward
+        #[arg(long)]
+        min_voting_power: u64,
+    },
+
+    /// [ADMIN] Update the lifetime cap on admin minting for one token
+    UpdateAdminMintCap {
+        /// Token type (temp-align, align, temp-rep, rep)
+        #[arg(index = 1)]
+        token_type: String,
+
+        /// New lifetime cap (cannot be below the amount already minted)
+        #[arg(index = 2)]
+        cap: u64,
+    },
+
+    /// [ADMIN] Create the fee vault that pays finalization rewards
+    InitFeeVault,
+
+    /// Deposit lamports into the fee vault (anyone can fund it)
+    FundFeeVault {
+        /// Amount to deposit, in lamports
+        #[arg(index = 1)]
+        lamports: u64,
+    },
+
+    /// [ADMIN] Create or update the starter tempAlign faucet (devnet builds only)
+    Faucet {
+        /// tempAlign minted per claim (0 disables the faucet)
+        #[arg(index = 1)]
+        amount: u64,
+
+        /// Seconds per claim epoch; 0 allows one claim per user ever
+        #[arg(long, default_value_t = 0)]
+        claim_interval: u64,
+    },
+
+    /// [ADMIN] Create the oracle committee and switch AI votes to threshold attestations
+    InitOracleCommittee {
+        /// Public key of a committee member (repeat for each member)
+        #[arg(long = "member", required = true)]
+        members: Vec<String>,
+
+        /// Number of matching attestations needed to complete an AI request
+        #[arg(long)]
+        threshold: u8,
+    },
+
+    /// [ADMIN] Replace the oracle committee's members and threshold
+    UpdateOracleCommittee {
+        /// Public key of a committee member (repeat for each member)
+        #[arg(long = "member", required = true)]
+        members: Vec<String>,
+
+        /// Number of matching attestations needed to complete an AI request
+        #[arg(long)]
+        threshold: u8,
+    },
+
     /// [ADMIN] Set and save the Solana cluster configuration
     SetCluster {
         /// Cluster name or URL (localnet, devnet, testnet, mainnet-beta, or custom URL)
@@ -1,4 +1,4 @@
-use anchor_client::solana_sdk::signature::Keypair;
+use anchor_client::solana_sdk::{signature::Keypair, system_program};
 use anchor_client::Program;
 use anyhow::{anyhow, Result};
 use dirs::home_dir;
@@ -10,7 +10,11 @@ use std::rc::Rc;
 use alignment_protocol::{accounts as AccountsAll, instruction as InstructionAll};
 
 use crate::commands::admin::init::is_state_initialized;
-use crate::commands::common::pda::get_state_pda;
+use crate::commands::admin::token::parse_token_type;
+use crate::commands::common::committee::parse_committee_members;
+use crate::commands::common::pda::{
+    get_faucet_config_pda, get_fee_vault_pda, get_oracle_committee_pda, get_state_pda,
+};
 
 /// Update the number of tokens to mint per submission (admin only)
 pub fn cmd_admin_update_tokens_to_mint(program: &Program<Rc<Keypair>>, tokens: u64) -> Result<()> {
@@ -42,6 +46,339 @@ pub fn cmd_admin_update_tokens_to_mint(program: &Program<Rc<Keypair>>, tokens: u
     Ok(())
 }
 
+/// Update the Rep emitted into each link's validator reward pool (admin only)
+pub fn cmd_admin_update_validator_reward_emission(
+    program: &Program<Rc<Keypair>>,
+    amount: u64,
+) -> Result<()> {
+    // Check if state is initialized
+    if !is_state_initialized(program) {
+        return Err(anyhow!(
+            "Protocol state not initialized. Run 'init state' first."
+        ));
+    }
+
+    let (state_pda, _) = get_state_pda(program);
+
+    println!("Updating validator reward emission to {}", amount);
+
+    let accounts = AccountsAll::UpdateValidatorRewardEmission {
+        authority: program.payer(),
+        state: state_pda,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateValidatorRewardEmission {
+            new_validator_reward_emission: amount,
+        })
+        .send()?;
+
+    println!(
+        "Validator reward emission updated successfully (txSig: {})",
+        tx_sig
+    );
+    Ok(())
+}
+
+/// Update the commission validators keep from delegators' vote rewards (admin only)
+pub fn cmd_admin_update_delegation_commission(
+    program: &Program<Rc<Keypair>>,
+    bps: u16,
+) -> Result<()> {
+    // Check if state is initialized
+    if !is_state_initialized(program) {
+        return Err(anyhow!(
+            "Protocol state not initialized. Run 'init state' first."
+        ));
+    }
+
+    let (state_pda, _) = get_state_pda(program);
+
+    println!("Updating delegation commission to {} bps", bps);
+
+    let accounts = AccountsAll::UpdateDelegationCommission {
+        authority: program.payer(),
+        state: state_pda,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateDelegationCommission {
+            new_delegation_commission_bps: bps,
+        })
+        .send()?;
+
+    println!(
+        "Delegation commission updated successfully (txSig: {})",
+        tx_sig
+    );
+    Ok(())
+}
+
+/// Update the lamports paid to whoever finalizes a link or vote, and the revealed voting power
+/// a link needs before it pays them (admin only)
+pub fn cmd_admin_update_finalization_reward(
+    program: &Program<Rc<Keypair>>,
+    lamports: u64,
+    min_voting_power: u64,
+) -> Result<()> {
+    // Check if state is initialized
+    if !is_state_initialized(program) {
+        return Err(anyhow!(
+            "Protocol state not initialized. Run 'init state' first."
+        ));
+    }
+
+    let (state_pda, _) = get_state_pda(program);
+
+    println!(
+        "Updating finalization reward to {} lamports (min voting power {})",
+        lamports, min_voting_power
+    );
+
+    let accounts = AccountsAll::UpdateFinalizationReward {
+        authority: program.payer(),
+        state: state_pda,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateFinalizationReward {
+            new_finalization_reward: lamports,
+            new_min_voting_power: min_voting_power,
+        })
+        .send()?;
+
+    println!(
+        "Finalization reward updated successfully (txSig: {})",
+        tx_sig
+    );
+    Ok(())
+}
+
+/// Update the lifetime cap on `admin_mint` for one token (admin only)
+pub fn cmd_admin_update_admin_mint_cap(
+    program: &Program<Rc<Keypair>>,
+    token_type: &str,
+    cap: u64,
+) -> Result<()> {
+    // Check if state is initialized
+    if !is_state_initialized(program) {
+        return Err(anyhow!(
+            "Protocol state not initialized. Run 'init state' first."
+        ));
+    }
+
+    let token_type = parse_token_type(token_type)?;
+    let (state_pda, _) = get_state_pda(program);
+
+    println!("Updating {:?} admin mint cap to {}", token_type, cap);
+
+    let accounts = AccountsAll::UpdateAdminMintCap {
+        authority: program.payer(),
+        state: state_pda,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateAdminMintCap {
+            token_type,
+            new_cap: cap,
+        })
+        .send()?;
+
+    println!("Admin mint cap updated successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Create the fee vault that pays finalization rewards (admin only)
+pub fn cmd_admin_init_fee_vault(program: &Program<Rc<Keypair>>) -> Result<()> {
+    // Check if state is initialized
+    if !is_state_initialized(program) {
+        return Err(anyhow!(
+            "Protocol state not initialized. Run 'init state' first."
+        ));
+    }
+
+    let (state_pda, _) = get_state_pda(program);
+    let (fee_vault_pda, _) = get_fee_vault_pda(program);
+
+    println!("Creating fee vault {}", fee_vault_pda);
+
+    let accounts = AccountsAll::InitializeFeeVault {
+        state: state_pda,
+        fee_vault: fee_vault_pda,
+        authority: program.payer(),
+        system_program: system_program::ID,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::InitializeFeeVault {})
+        .send()?;
+
+    println!("Fee vault created successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Create the starter tempAlign faucet, or update it if it exists (admin only)
+pub fn cmd_admin_configure_faucet(
+    program: &Program<Rc<Keypair>>,
+    amount: u64,
+    claim_interval: u64,
+) -> Result<()> {
+    // Check if state is initialized
+    if !is_state_initialized(program) {
+        return Err(anyhow!(
+            "Protocol state not initialized. Run 'init state' first."
+        ));
+    }
+
+    let (state_pda, _) = get_state_pda(program);
+    let (faucet_config_pda, _) = get_faucet_config_pda(program);
+
+    println!(
+        "Setting faucet {} to {} tempAlign per claim, claim interval {} seconds",
+        faucet_config_pda, amount, claim_interval
+    );
+
+    let request = program.request();
+    let request = if program.rpc().get_account(&faucet_config_pda).is_ok() {
+        request
+            .accounts(AccountsAll::UpdateFaucet {
+                state: state_pda,
+                faucet_config: faucet_config_pda,
+                authority: program.payer(),
+            })
+            .args(InstructionAll::UpdateFaucet {
+                amount,
+                claim_interval,
+            })
+    } else {
+        request
+            .accounts(AccountsAll::InitializeFaucet {
+                state: state_pda,
+                faucet_config: faucet_config_pda,
+                authority: program.payer(),
+                system_program: system_program::ID,
+            })
+            .args(InstructionAll::InitializeFaucet {
+                amount,
+                claim_interval,
+            })
+    };
+    let tx_sig = request.send()?;
+
+    println!("Faucet configured successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Deposit lamports into the fee vault (anyone)
+pub fn cmd_fund_fee_vault(program: &Program<Rc<Keypair>>, lamports: u64) -> Result<()> {
+    let (fee_vault_pda, _) = get_fee_vault_pda(program);
+
+    println!(
+        "Depositing {} lamports into fee vault {}",
+        lamports, fee_vault_pda
+    );
+
+    let accounts = AccountsAll::FundFeeVault {
+        fee_vault: fee_vault_pda,
+        funder: program.payer(),
+        system_program: system_program::ID,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::FundFeeVault { amount: lamports })
+        .send()?;
+
+    println!("Fee vault funded successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Create the oracle committee (admin only)
+pub fn cmd_admin_init_oracle_committee(
+    program: &Program<Rc<Keypair>>,
+    members: Vec<String>,
+    threshold: u8,
+) -> Result<()> {
+    // Check if state is initialized
+    if !is_state_initialized(program) {
+        return Err(anyhow!(
+            "Protocol state not initialized. Run 'init state' first."
+        ));
+    }
+
+    let members = parse_committee_members(&members)?;
+    let (state_pda, _) = get_state_pda(program);
+    let (oracle_committee_pda, _) = get_oracle_committee_pda(program);
+
+    println!(
+        "Creating oracle committee with {} members, threshold {}",
+        members.len(),
+        threshold
+    );
+
+    let accounts = AccountsAll::InitializeOracleCommittee {
+        state: state_pda,
+        oracle_committee: oracle_committee_pda,
+        authority: program.payer(),
+        system_program: system_program::ID,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::InitializeOracleCommittee { members, threshold })
+        .send()?;
+
+    println!(
+        "Oracle committee created at {} (txSig: {})",
+        oracle_committee_pda, tx_sig
+    );
+    Ok(())
+}
+
+/// Replace the oracle committee's members and threshold (admin only)
+pub fn cmd_admin_update_oracle_committee(
+    program: &Program<Rc<Keypair>>,
+    members: Vec<String>,
+    threshold: u8,
+) -> Result<()> {
+    let members = parse_committee_members(&members)?;
+    let (state_pda, _) = get_state_pda(program);
+    let (oracle_committee_pda, _) = get_oracle_committee_pda(program);
+
+    println!(
+        "Updating oracle committee to {} members, threshold {}",
+        members.len(),
+        threshold
+    );
+
+    let accounts = AccountsAll::UpdateOracleCommittee {
+        state: state_pda,
+        oracle_committee: oracle_committee_pda,
+        authority: program.payer(),
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateOracleCommittee { members, threshold })
+        .send()?;
+
+    println!("Oracle committee updated successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
 /// Get the path to the CLI config file
 fn get_config_file_path() -> Result<PathBuf> {
     let mut path = home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
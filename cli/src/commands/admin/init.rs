@@ -2,7 +2,7 @@ use anchor_client::solana_sdk::signature::Keypair;
 use anchor_client::solana_sdk::system_program;
 use anchor_client::Program;
 use anchor_lang::prelude::Pubkey;
-use anchor_spl::token::ID as TokenProgramID;
+use anchor_spl::token_2022::ID as TokenProgramID;
 use anyhow::{anyhow, Result};
 use std::rc::Rc;
 use std::str::FromStr;
@@ -321,3 +321,55 @@ pub fn cmd_init_all(program: &Program<Rc<Keypair>>, oracle_pubkey_str: String) -
     println!("All protocol accounts initialized successfully!");
     Ok(())
 }
+
+/// Set a token mint's name, symbol and URI (admin only)
+pub fn cmd_init_metadata(
+    program: &Program<Rc<Keypair>>,
+    token_type: &str,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    if !are_mints_initialized(program) {
+        return Err(anyhow!(
+            "Token mints not initialized. Run 'init all' first."
+        ));
+    }
+
+    let (state_pda, _) = get_state_pda(program);
+    let state = program.account::<StateAccount>(state_pda)?;
+    let mint = match token_type.to_lowercase().as_str() {
+        "temp-align" => state.temp_align_mint,
+        "align" => state.align_mint,
+        "temp-rep" => state.temp_rep_mint,
+        "rep" => state.rep_mint,
+        _ => {
+            return Err(anyhow!(
+                "Invalid token type: {}. Must be one of: temp-align, align, temp-rep, rep",
+                token_type
+            ))
+        }
+    };
+
+    println!("Setting metadata on {} mint {}...", token_type, mint);
+    println!("  Name: {}", name);
+    println!("  Symbol: {}", symbol);
+    println!("  URI: {}", uri);
+
+    let accounts = AccountsAll::SetMintMetadata {
+        state: state_pda,
+        mint,
+        authority: program.payer(),
+        token_program: TokenProgramID,
+        system_program: system_program::ID,
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::SetMintMetadata { name, symbol, uri })
+        .send()?;
+
+    println!("Mint metadata set (txSig: {})", tx_sig);
+    Ok(())
+}
@@ -1,17 +1,122 @@
-use anchor_client::solana_sdk::signature::Keypair;
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Keypair};
 use anchor_client::Program;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use std::rc::Rc;
+use std::str::FromStr;
+
+use alignment_protocol::{
+    accounts as AccountsAll, instruction as InstructionAll, State as StateAccount, TokenType,
+};
+
+use crate::commands::common::eligibility::init_topic_balance_ix_if_missing;
+use crate::commands::common::pda::{
+    get_state_pda, get_token_ata, get_topic_pda, get_user_temp_token_account_pda,
+    get_user_topic_balance_pda,
+};
+
+/// Parse a CLI token type name into the program's `TokenType`
+pub fn parse_token_type(token_type: &str) -> Result<TokenType> {
+    match token_type.to_lowercase().as_str() {
+        "temp-align" => Ok(TokenType::TempAlign),
+        "align" => Ok(TokenType::Align),
+        "temp-rep" => Ok(TokenType::TempRep),
+        "rep" => Ok(TokenType::Rep),
+        _ => Err(anyhow!(
+            "Invalid token type: {}. Must be one of: temp-align, align, temp-rep, rep",
+            token_type
+        )),
+    }
+}
 
 /// Mint tokens to a user (admin only)
+///
+/// Temporary tokens go to the user's protocol-owned temp account and are credited to their
+/// balance in `topic_index`; permanent tokens go to the user's ATA.
 pub fn cmd_admin_mint_tokens(
-    _program: &Program<Rc<Keypair>>,
-    _token_type: &str,
-    _to: &str,
-    _amount: u64,
+    program: &Program<Rc<Keypair>>,
+    token_type: &str,
+    to: &str,
+    amount: u64,
+    topic_index: Option<u64>,
+    reason: String,
 ) -> Result<()> {
-    // Future implementation for token minting function
-    // println!("Admin: Minting {} {} tokens to {}", amount, token_type, to);
-    println!("This command is not implemented yet.");
+    let token_type = parse_token_type(token_type)?;
+    let recipient =
+        Pubkey::from_str(to).map_err(|e| anyhow!("Invalid recipient pubkey format: {}", e))?;
+
+    let (state_pda, _) = get_state_pda(program);
+    let state: StateAccount = program.account(state_pda)?;
+    let (mint, temp_account_seed) = match token_type {
+        TokenType::TempAlign => (state.temp_align_mint, Some("user_temp_align")),
+        TokenType::Align => (state.align_mint, None),
+        TokenType::TempRep => (state.temp_rep_mint, Some("user_temp_rep")),
+        TokenType::Rep => (state.rep_mint, None),
+    };
+
+    let index = token_type as usize;
+    let remaining = state.admin_mint_caps[index].saturating_sub(state.admin_minted[index]);
+    if amount > remaining {
+        return Err(anyhow!(
+            "Minting {} would exceed the {:?} admin mint cap ({} of {} already minted). Raise it with 'config update-admin-mint-cap'.",
+            amount,
+            token_type,
+            state.admin_minted[index],
+            state.admin_mint_caps[index]
+        ));
+    }
+
+    let mut init_balance_ix = None;
+    let (recipient_token_account, user_topic_balance) = match temp_account_seed {
+        Some(seed) => {
+            let topic_index = topic_index.ok_or_else(|| {
+                anyhow!("Minting temporary tokens requires --topic to credit the user's balance")
+            })?;
+            let (topic_pda, _) = get_topic_pda(program, topic_index);
+            let (user_topic_balance_pda, _) =
+                get_user_topic_balance_pda(program, &recipient, &topic_pda);
+            init_balance_ix = init_topic_balance_ix_if_missing(program, &topic_pda, &recipient)?;
+            let (temp_account, _) = get_user_temp_token_account_pda(program, &recipient, seed);
+            (temp_account, Some(user_topic_balance_pda))
+        }
+        None => (get_token_ata(&recipient, &mint), None),
+    };
+
+    if program.rpc().get_account(&recipient_token_account).is_err() {
+        return Err(anyhow!(
+            "Token account {} does not exist. The user should run 'user create-profile' first.",
+            recipient_token_account
+        ));
+    }
+
+    println!(
+        "Minting {} {:?} to {} ({})",
+        amount, token_type, recipient, recipient_token_account
+    );
+    println!("  Reason: {}", reason);
+
+    let accounts = AccountsAll::AdminMint {
+        state: state_pda,
+        authority: program.payer(),
+        recipient,
+        mint,
+        recipient_token_account,
+        user_topic_balance,
+        token_program: anchor_spl::token_2022::ID,
+    };
+
+    let mut request = program.request();
+    if let Some(ix) = init_balance_ix {
+        request = request.instruction(ix);
+    }
+    let tx_sig = request
+        .accounts(accounts)
+        .args(InstructionAll::AdminMint {
+            token_type,
+            amount,
+            reason,
+        })
+        .send()?;
+
+    println!("Tokens minted successfully (txSig: {})", tx_sig);
     Ok(())
 }
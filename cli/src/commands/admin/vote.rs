@@ -7,6 +7,7 @@ use std::str::FromStr;
 use alignment_protocol::{accounts as AccountsAll, instruction as InstructionAll};
 
 use crate::commands::common::pda::{get_state_pda, get_submission_topic_link_pda, get_topic_pda};
+use crate::commands::common::role::find_topic_role;
 
 /// Set arbitrary timestamps for voting phases (admin function)
 pub fn cmd_set_voting_phases(
@@ -49,6 +50,7 @@ pub fn cmd_set_voting_phases(
         submission: submission_pda,
         submission_topic_link: submission_topic_link_pda,
         topic: topic_pda,
+        topic_role: find_topic_role(program, &topic_pda, &program.payer()),
         system_program: system_program::ID,
     };
 
@@ -0,0 +1,14 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// Parse committee member public keys from their string form
+pub fn parse_committee_members(members: &[String]) -> Result<Vec<Pubkey>> {
+    members
+        .iter()
+        .map(|member| {
+            Pubkey::from_str(member)
+                .map_err(|e| anyhow!("Invalid committee member pubkey {}: {}", member, e))
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, system_program, sysvar,
+};
+use anchor_client::Program;
+use anyhow::Result;
+use std::rc::Rc;
+
+use alignment_protocol::{
+    accounts as AccountsAll, instruction as InstructionAll, Topic as TopicAccount,
+    UserProfile as UserProfileAccount,
+};
+
+use crate::commands::common::pda::{get_user_profile_pda, get_user_topic_balance_pda};
+
+/// Look up the optional accounts a topic's participation rules need for `user`
+///
+/// Returns `(rep_ata, parent_topic_balance)`. Each is `None` when the topic has no
+/// matching rule or the account does not exist yet, so the on-chain check reports
+/// the unmet requirement.
+pub fn find_eligibility_accounts(
+    program: &Program<Rc<Keypair>>,
+    topic_pda: &Pubkey,
+    user: &Pubkey,
+) -> (Option<Pubkey>, Option<Pubkey>) {
+    let topic: TopicAccount = match program.account(*topic_pda) {
+        Ok(topic) => topic,
+        Err(_) => return (None, None),
+    };
+
+    let rep_ata = if topic.min_rep_balance > 0 {
+        let (user_profile_pda, _) = get_user_profile_pda(program, user);
+        program
+            .account::<UserProfileAccount>(user_profile_pda)
+            .ok()
+            .map(|profile| profile.user_rep_ata)
+            .filter(|ata| *ata != Pubkey::default())
+    } else {
+        None
+    };
+
+    let parent_topic_balance = topic.parent_topic.and_then(|parent_topic| {
+        let (balance_pda, _) = get_user_topic_balance_pda(program, user, &parent_topic);
+        program
+            .rpc()
+            .get_account(&balance_pda)
+            .ok()
+            .map(|_| balance_pda)
+    });
+
+    (rep_ata, parent_topic_balance)
+}
+
+/// Build `initialize_user_topic_balance` for `user` if their balance in the topic does not
+/// exist yet, so callers can put it in the same transaction as their first interaction
+pub fn init_topic_balance_ix_if_missing(
+    program: &Program<Rc<Keypair>>,
+    topic_pda: &Pubkey,
+    user: &Pubkey,
+) -> Result<Option<Instruction>> {
+    let (user_topic_balance_pda, _) = get_user_topic_balance_pda(program, user, topic_pda);
+    if program.rpc().get_account(&user_topic_balance_pda).is_ok() {
+        return Ok(None);
+    }
+
+    println!(
+        "No UserTopicBalance for topic {} yet; creating {} in the same transaction",
+        topic_pda, user_topic_balance_pda
+    );
+    let (user_profile_pda, _) = get_user_profile_pda(program, user);
+    let (user_rep_ata, parent_topic_balance) = find_eligibility_accounts(program, topic_pda, user);
+
+    let mut instructions = program
+        .request()
+        .accounts(AccountsAll::InitializeUserTopicBalance {
+            payer: program.payer(),
+            user: *user,
+            user_profile: user_profile_pda,
+            topic: *topic_pda,
+            user_topic_balance: user_topic_balance_pda,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            user_rep_ata,
+            parent_topic_balance,
+        })
+        .args(InstructionAll::InitializeUserTopicBalance {})
+        .instructions()?;
+    Ok(instructions.pop())
+}
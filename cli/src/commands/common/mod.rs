@@ -1,3 +1,6 @@
+pub mod committee;
+pub mod eligibility;
 pub mod pda;
+pub mod role;
 pub mod time;
 pub mod vote;
@@ -1,6 +1,6 @@
 use anchor_client::solana_sdk::signature::Keypair;
 use anchor_client::{solana_sdk::pubkey::Pubkey, Program};
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
 use std::rc::Rc;
 
 /// Get the program-derived address (PDA) for the state account
@@ -8,6 +8,44 @@ pub fn get_state_pda(program: &Program<Rc<Keypair>>) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[b"state"], &program.id())
 }
 
+/// Get the PDA for the oracle committee account
+pub fn get_oracle_committee_pda(program: &Program<Rc<Keypair>>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"oracle_committee"], &program.id())
+}
+
+/// Get the PDA for the fee vault that pays finalization rewards
+pub fn get_fee_vault_pda(program: &Program<Rc<Keypair>>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_vault"], &program.id())
+}
+
+/// Get the PDA for the starter tempAlign faucet's settings
+pub fn get_faucet_config_pda(program: &Program<Rc<Keypair>>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"faucet_config"], &program.id())
+}
+
+/// Get the PDA recording a user's last faucet claim
+pub fn get_faucet_claim_pda(program: &Program<Rc<Keypair>>, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"faucet_claim", user.as_ref()], &program.id())
+}
+
+/// Get the fee vault PDA if the vault has been created, so finalizations can claim the reward
+pub fn get_existing_fee_vault(program: &Program<Rc<Keypair>>) -> Option<Pubkey> {
+    let (fee_vault_pda, _) = get_fee_vault_pda(program);
+    program
+        .rpc()
+        .get_account(&fee_vault_pda)
+        .ok()
+        .map(|_| fee_vault_pda)
+}
+
+/// Get the PDA for a topic's own oracle committee account
+pub fn get_topic_oracle_committee_pda(
+    program: &Program<Rc<Keypair>>,
+    topic: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"oracle_committee", topic.as_ref()], &program.id())
+}
+
 /// Get the PDA for a specific mint
 pub fn get_mint_pda(program: &Program<Rc<Keypair>>, mint_type: &str) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[mint_type.as_bytes()], &program.id())
@@ -66,7 +104,7 @@ pub fn get_user_temp_token_account_pda(
 
 /// Get the ATA for a token mint
 pub fn get_token_ata(user: &Pubkey, mint: &Pubkey) -> Pubkey {
-    get_associated_token_address(user, mint)
+    get_associated_token_address_with_program_id(user, mint, &anchor_spl::token_2022::ID)
 }
 
 /// Get the PDA for a user's topic balance account
@@ -80,3 +118,45 @@ pub fn get_user_topic_balance_pda(
         &program.id(),
     )
 }
+
+/// Get the PDA for a user's delegation in a topic
+pub fn get_delegation_pda(
+    program: &Program<Rc<Keypair>>,
+    delegator: &Pubkey,
+    topic: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"delegation", delegator.as_ref(), topic.as_ref()],
+        &program.id(),
+    )
+}
+
+/// Get the PDA for a wallet's roles in a topic
+pub fn get_topic_role_pda(
+    program: &Program<Rc<Keypair>>,
+    topic: &Pubkey,
+    holder: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"topic_role", topic.as_ref(), holder.as_ref()],
+        &program.id(),
+    )
+}
+
+/// Get the PDA for a requester's AI validation request on a submission-topic link
+pub fn get_ai_request_pda(
+    program: &Program<Rc<Keypair>>,
+    submission_topic_link: &Pubkey,
+    requester: &Pubkey,
+    request_index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"ai_request",
+            submission_topic_link.as_ref(),
+            requester.as_ref(),
+            &request_index.to_le_bytes(),
+        ],
+        &program.id(),
+    )
+}
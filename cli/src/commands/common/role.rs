@@ -0,0 +1,57 @@
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use anchor_client::Program;
+use anyhow::{anyhow, Result};
+use std::rc::Rc;
+
+use alignment_protocol::{TOPIC_ROLE_MODERATOR, TOPIC_ROLE_ORACLE, TOPIC_ROLE_PHASE_MANAGER};
+
+use crate::commands::common::pda::get_topic_role_pda;
+
+/// Return the holder's TopicRole PDA if it exists on-chain, for passing as an optional account
+pub fn find_topic_role(
+    program: &Program<Rc<Keypair>>,
+    topic: &Pubkey,
+    holder: &Pubkey,
+) -> Option<Pubkey> {
+    let (topic_role_pda, _) = get_topic_role_pda(program, topic, holder);
+    program
+        .rpc()
+        .get_account(&topic_role_pda)
+        .ok()
+        .map(|_| topic_role_pda)
+}
+
+/// Build a role bitmask from CLI flags
+pub fn roles_from_flags(moderator: bool, phase_manager: bool, oracle: bool) -> Result<u8> {
+    let mut roles = 0u8;
+    if moderator {
+        roles |= TOPIC_ROLE_MODERATOR;
+    }
+    if phase_manager {
+        roles |= TOPIC_ROLE_PHASE_MANAGER;
+    }
+    if oracle {
+        roles |= TOPIC_ROLE_ORACLE;
+    }
+    if roles == 0 {
+        return Err(anyhow!(
+            "Specify at least one role: --moderator, --phase-manager or --oracle"
+        ));
+    }
+    Ok(roles)
+}
+
+/// Describe a role bitmask for display
+pub fn describe_roles(roles: u8) -> String {
+    let mut names = Vec::new();
+    if roles & TOPIC_ROLE_MODERATOR != 0 {
+        names.push("moderator");
+    }
+    if roles & TOPIC_ROLE_PHASE_MANAGER != 0 {
+        names.push("phase-manager");
+    }
+    if roles & TOPIC_ROLE_ORACLE != 0 {
+        names.push("oracle");
+    }
+    names.join(", ")
+}
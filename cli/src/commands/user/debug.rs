@@ -48,7 +48,9 @@ pub fn cmd_debug_token_account(
                     println!("   - Data length: {} bytes", account.data.len());
 
                     // Try to get token account info
-                    match program.account::<anchor_spl::token::TokenAccount>(temp_account_pda) {
+                    match program
+                        .account::<anchor_spl::token_interface::TokenAccount>(temp_account_pda)
+                    {
                         Ok(token_account) => {
                             println!("   - Token account data:");
                             println!("     * Mint: {}", token_account.mint);
@@ -83,7 +85,7 @@ pub fn cmd_debug_token_account(
                     println!("   - Data length: {} bytes", account.data.len());
 
                     // Try to get token account info
-                    match program.account::<anchor_spl::token::TokenAccount>(ata) {
+                    match program.account::<anchor_spl::token_interface::TokenAccount>(ata) {
                         Ok(token_account) => {
                             println!("   - Token account data:");
                             println!("     * Mint: {}", token_account.mint);
@@ -121,7 +123,9 @@ pub fn cmd_debug_token_account(
                     println!("   - Data length: {} bytes", account.data.len());
 
                     // Try to get token account info
-                    match program.account::<anchor_spl::token::TokenAccount>(temp_account_pda) {
+                    match program
+                        .account::<anchor_spl::token_interface::TokenAccount>(temp_account_pda)
+                    {
                         Ok(token_account) => {
                             println!("   - Token account data:");
                             println!("     * Mint: {}", token_account.mint);
@@ -156,7 +160,7 @@ pub fn cmd_debug_token_account(
                     println!("   - Data length: {} bytes", account.data.len());
 
                     // Try to get token account info
-                    match program.account::<anchor_spl::token::TokenAccount>(ata) {
+                    match program.account::<anchor_spl::token_interface::TokenAccount>(ata) {
                         Ok(token_account) => {
                             println!("   - Token account data:");
                             println!("     * Mint: {}", token_account.mint);
@@ -5,14 +5,15 @@ use std::rc::Rc;
 use std::str::FromStr;
 
 use alignment_protocol::{
+    Delegation as DelegationAccount, OracleCommittee as OracleCommitteeAccount,
     State as StateAccount, Submission as SubmissionAccount,
     SubmissionTopicLink as SubmissionTopicLinkAccount, UserProfile as UserProfileAccount,
     UserTopicBalance as UserTopicBalanceAccount, VoteCommit as VoteCommitAccount,
 };
 
 use crate::commands::common::pda::{
-    get_state_pda, get_submission_topic_link_pda, get_topic_pda, get_user_profile_pda,
-    get_user_topic_balance_pda, get_vote_commit_pda,
+    get_delegation_pda, get_oracle_committee_pda, get_state_pda, get_submission_topic_link_pda,
+    get_topic_pda, get_user_profile_pda, get_user_topic_balance_pda, get_vote_commit_pda,
 };
 use crate::commands::common::time::get_current_timestamp;
 
@@ -30,6 +31,18 @@ pub fn cmd_query_state(program: &Program<Rc<Keypair>>) -> Result<()> {
             println!("Rep Mint: {}", state.rep_mint);
             println!("Topic Count: {}", state.topic_count);
             println!("Tokens to Mint: {}", state.tokens_to_mint);
+            println!(
+                "Validator Reward Emission: {}",
+                state.validator_reward_emission
+            );
+            println!(
+                "Delegation Commission: {} bps",
+                state.delegation_commission_bps
+            );
+            println!(
+                "Finalization Reward: {} lamports",
+                state.finalization_reward
+            );
             println!(
                 "Default Commit Phase Duration: {} seconds",
                 state.default_commit_phase_duration
@@ -38,6 +51,29 @@ pub fn cmd_query_state(program: &Program<Rc<Keypair>>) -> Result<()> {
                 "Default Reveal Phase Duration: {} seconds",
                 state.default_reveal_phase_duration
             );
+            println!("Admin Minted / Cap:");
+            for (name, index) in [("tempAlign", 0), ("Align", 1), ("tempRep", 2), ("Rep", 3)] {
+                println!(
+                    "  {}: {} / {}",
+                    name, state.admin_minted[index], state.admin_mint_caps[index]
+                );
+            }
+            println!("Oracle: {}", state.oracle_pubkey);
+            if state.oracle_committee_active {
+                let (committee_pda, _) = get_oracle_committee_pda(program);
+                let committee = program.account::<OracleCommitteeAccount>(committee_pda)?;
+                println!(
+                    "Oracle Committee ({}): threshold {} of {}",
+                    committee_pda,
+                    committee.threshold,
+                    committee.members.len()
+                );
+                for member in &committee.members {
+                    println!("  Member: {}", member);
+                }
+            } else {
+                println!("Oracle Committee: not active");
+            }
             Ok(())
         }
         Err(e) => Err(anyhow::anyhow!(
@@ -216,6 +252,12 @@ pub fn cmd_query_submission_topic(
             println!("No Voting Power: {}", link.no_voting_power);
             println!("Total Committed Votes: {}", link.total_committed_votes);
             println!("Total Revealed Votes: {}", link.total_revealed_votes);
+            println!("Yes TempRep Revealed: {}", link.yes_temp_rep);
+            println!("No TempRep Revealed: {}", link.no_temp_rep);
+
+            println!("\nValidator Reward Pool:");
+            println!("Pool: {}", link.reward_pool);
+            println!("Claimed: {}", link.reward_pool_claimed);
 
             Ok(())
         }
@@ -253,6 +295,11 @@ pub fn cmd_query_vote(
             println!("Commit Timestamp: {}", vote.commit_timestamp);
             println!("Temporary REP Amount: {}", vote.temp_rep_amount);
             println!("Permanent REP Amount: {}", vote.perm_rep_amount);
+            println!(
+                "Delegated Temp REP Amount: {} ({} delegations)",
+                vote.delegated_temp_rep_amount,
+                vote.delegations.len()
+            );
 
             if vote.temp_rep_amount > 0 && vote.perm_rep_amount > 0 {
                 println!("Reputation Type: Mixed (Temporary and Permanent)");
@@ -315,6 +362,15 @@ pub fn cmd_view_user_topic_balance(
                 "  Locked Temp Rep Amount: {}",
                 balance.locked_temp_rep_amount
             );
+            println!("  Rep Earned: {}", balance.rep_earned);
+            println!(
+                "  Pending Submissions: {}",
+                balance.pending_submission_count
+            );
+            println!(
+                "  Submissions This Window: {} (since {})",
+                balance.window_submission_count, balance.submission_window_start
+            );
         }
         Err(e) => {
             if e.to_string().contains("AccountNotFound")
@@ -330,3 +386,46 @@ pub fn cmd_view_user_topic_balance(
 
     Ok(())
 }
+
+/// Get a user's delegation for a specific topic
+pub fn cmd_query_delegation(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    delegator_str: Option<String>,
+) -> Result<()> {
+    let delegator = match delegator_str {
+        Some(pubkey_str) => Pubkey::from_str(&pubkey_str)?,
+        None => program.payer(),
+    };
+
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (delegation_pda, _) = get_delegation_pda(program, &delegator, &topic_pda);
+
+    println!(
+        "Querying delegation for Delegator: {} on Topic index: {}",
+        delegator, topic_index
+    );
+    println!("Delegation PDA: {}", delegation_pda);
+
+    match program.account::<DelegationAccount>(delegation_pda) {
+        Ok(delegation) => {
+            println!("\nDelegation Found:");
+            println!("  Delegator: {}", delegation.delegator);
+            println!("  Validator: {}", delegation.validator);
+            println!("  Topic: {}", delegation.topic);
+            println!("  Available Amount: {}", delegation.amount);
+            println!("  Locked Amount: {}", delegation.locked_amount);
+        }
+        Err(e) => {
+            if e.to_string().contains("AccountNotFound")
+                || e.to_string().contains("Could not deserialize account data")
+            {
+                println!("\nNo delegation found for this delegator/topic combination.");
+            } else {
+                println!("\nError fetching delegation account: {}", e);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_client::solana_sdk::signature::Keypair;
 use anchor_client::{
-    solana_sdk::{pubkey::Pubkey, system_program, sysvar},
+    solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, system_program, sysvar},
     Program,
 };
 use anyhow::{anyhow, Result};
@@ -8,13 +8,18 @@ use std::rc::Rc;
 use std::str::FromStr;
 
 use alignment_protocol::{
-    accounts as AccountsAll, instruction as InstructionAll, State as StateAccount,
-    Submission as SubmissionAccount,
+    accounts as AccountsAll, instruction as InstructionAll,
+    AiValidationRequest as AiValidationRequestAccount, AiValidationStatus, State as StateAccount,
+    Submission as SubmissionAccount, UserTopicBalance as UserTopicBalanceAccount,
 };
 
+use crate::commands::common::eligibility::{
+    find_eligibility_accounts, init_topic_balance_ix_if_missing,
+};
 use crate::commands::common::pda::{
-    get_state_pda, get_submission_topic_link_pda, get_token_ata, get_topic_pda,
-    get_user_profile_pda, get_user_temp_token_account_pda, get_user_topic_balance_pda,
+    get_ai_request_pda, get_existing_fee_vault, get_state_pda, get_submission_topic_link_pda,
+    get_token_ata, get_topic_pda, get_user_profile_pda, get_user_temp_token_account_pda,
+    get_user_topic_balance_pda,
 };
 
 /// Submit data to a topic
@@ -28,29 +33,6 @@ pub fn cmd_submit_data_to_topic(
     let (topic_pda, _) = get_topic_pda(program, topic_index);
     let (user_topic_balance_pda, _) = get_user_topic_balance_pda(program, &contributor, &topic_pda);
 
-    match program.rpc().get_account(&user_topic_balance_pda) {
-        Ok(_) => {
-            println!(
-                "UserTopicBalance account found ({}). Proceeding with submission.",
-                user_topic_balance_pda
-            );
-        }
-        Err(e) => {
-            eprintln!(
-                "\nError: User balance account for this topic (PDA: {}) not found.",
-                user_topic_balance_pda
-            );
-            eprintln!(
-                "This usually means the user hasn't interacted with this specific topic yet."
-            );
-            eprintln!(
-                "Please run 'alignment-protocol-cli user initialize-topic-balance {}' first.",
-                topic_index
-            );
-            return Err(anyhow!("UserTopicBalance account not initialized: {}", e));
-        }
-    }
-
     let state_data: StateAccount = program.account(state_pda)?;
 
     let (contributor_profile_pda, _) = get_user_profile_pda(program, &contributor);
@@ -103,9 +85,15 @@ pub fn cmd_submit_data_to_topic(
     );
     println!("Data reference: {}", data_reference);
 
+    let (contributor_rep_ata, parent_topic_balance) =
+        find_eligibility_accounts(program, &topic_pda, &contributor);
+    let init_balance_ix = init_topic_balance_ix_if_missing(program, &topic_pda, &contributor)?;
+
     let accounts = AccountsAll::SubmitDataToTopic {
         payer: program.payer(),
         contributor,
+        acting_signer: contributor,
+        session_key: None,
         topic: topic_pda,
         submission: submission_pda,
         submission_topic_link: submission_topic_link_pda,
@@ -114,12 +102,17 @@ pub fn cmd_submit_data_to_topic(
         contributor_temp_align_account: contributor_temp_align_account_pda,
         contributor_profile: contributor_profile_pda,
         user_topic_balance: user_topic_balance_pda,
-        token_program: anchor_spl::token::ID,
+        token_program: anchor_spl::token_2022::ID,
         system_program: system_program::ID,
+        contributor_rep_ata,
+        parent_topic_balance,
     };
 
-    let tx_sig = program
-        .request()
+    let mut request = program.request();
+    if let Some(ix) = init_balance_ix {
+        request = request.instruction(ix);
+    }
+    let tx_sig = request
         .accounts(accounts)
         .args(InstructionAll::SubmitDataToTopic {
             data_reference,
@@ -224,13 +217,36 @@ pub fn cmd_finalize_submission(
         user_topic_balance: user_topic_balance_pda,
         temp_align_mint,
         align_mint,
-        token_program: anchor_spl::token::ID,
+        fee_vault: get_existing_fee_vault(program),
+        token_program: anchor_spl::token_2022::ID,
         system_program: system_program::ID,
     };
 
+    // Every completed AI request on the link must be passed so its verdict is counted
+    let ai_request_metas: Vec<AccountMeta> = program
+        .accounts::<AiValidationRequestAccount>(vec![])?
+        .into_iter()
+        .filter(|(_, r)| {
+            r.submission_topic_link == submission_topic_link_pda
+                && r.status == AiValidationStatus::Completed
+        })
+        .map(|(pubkey, r)| {
+            println!(
+                "Including AI verdict {:?} (power {}, confidence {} bps, model {}) from request {}",
+                r.ai_decision,
+                r.ai_voting_power,
+                r.confidence_bps,
+                hex::encode(r.model_hash),
+                pubkey
+            );
+            AccountMeta::new_readonly(pubkey, false)
+        })
+        .collect();
+
     let tx_sig = program
         .request()
         .accounts(accounts)
+        .accounts(ai_request_metas)
         .args(InstructionAll::FinalizeSubmission {})
         .send()?;
 
@@ -281,23 +297,23 @@ pub fn cmd_request_ai_validation(
         &program.id(),
     );
 
-    // Fetch the SubmissionTopicLink account to get the current total_committed_votes
-    let link_data: alignment_protocol::SubmissionTopicLink = program.account(link_pda)?;
-    let expected_ai_request_index = link_data.total_committed_votes; // Get index BEFORE sending TX
+    // Fetch the requester's UserTopicBalance to get their next AI request index
+    let balance_data: UserTopicBalanceAccount = program.account(user_balance_pda).map_err(|e| {
+        anyhow!(
+            "Could not fetch UserTopicBalance {}: {}",
+            user_balance_pda,
+            e
+        )
+    })?;
+    let expected_ai_request_index = balance_data.user_ai_request_count; // Get index BEFORE sending TX
     println!(
-        "  Current total_committed_votes on link (used for expected index): {}",
+        "  Current user_ai_request_count (used for expected index): {}",
         expected_ai_request_index
     );
 
-    // AiValidationRequest PDA (Needs the specific index)
-    let (ai_request_pda, _ai_request_bump) = Pubkey::find_program_address(
-        &[
-            b"ai_request",
-            link_pda.as_ref(),
-            &expected_ai_request_index.to_le_bytes(), // Use the fetched index for derivation
-        ],
-        &program.id(),
-    );
+    // AiValidationRequest PDA (Needs the requester and the specific index)
+    let (ai_request_pda, _ai_request_bump) =
+        get_ai_request_pda(program, &link_pda, &requester, expected_ai_request_index);
 
     println!("Derived PDAs:");
     println!("  Topic: {}", topic_pda);
@@ -331,3 +347,40 @@ pub fn cmd_request_ai_validation(
 
     Ok(())
 }
+
+/// Expire an AI validation request that timed out without an oracle response
+pub fn cmd_expire_ai_request(
+    program: &Program<Rc<Keypair>>,
+    ai_request_pda_str: String,
+) -> Result<()> {
+    let ai_request_pda = Pubkey::from_str(&ai_request_pda_str)
+        .map_err(|e| anyhow!("Invalid AI request PDA: {}", e))?;
+
+    let ai_request: AiValidationRequestAccount = program
+        .account(ai_request_pda)
+        .map_err(|e| anyhow!("Could not fetch AI request {}: {}", ai_request_pda, e))?;
+    let link: alignment_protocol::SubmissionTopicLink =
+        program.account(ai_request.submission_topic_link)?;
+    let (user_balance_pda, _) =
+        get_user_topic_balance_pda(program, &ai_request.requester, &link.topic);
+
+    println!("Expiring AI validation request {}", ai_request_pda);
+    println!("  Status: {:?}", ai_request.status);
+    println!(
+        "  Refunding {} tempRep to {}",
+        ai_request.temp_rep_staked, ai_request.requester
+    );
+
+    let tx_sig = program
+        .request()
+        .accounts(AccountsAll::ExpireAiRequest {
+            ai_validation_request: ai_request_pda,
+            submission_topic_link: ai_request.submission_topic_link,
+            user_topic_balance: user_balance_pda,
+        })
+        .args(InstructionAll::ExpireAiRequest {})
+        .send()?;
+
+    println!("AI request expired successfully (txSig: {})", tx_sig);
+    Ok(())
+}
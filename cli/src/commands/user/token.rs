@@ -1,15 +1,22 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signature::Keypair;
-use anchor_client::Program;
+use anchor_client::{
+    solana_sdk::{system_program, sysvar},
+    Program,
+};
 use anyhow::Result;
 use std::rc::Rc;
+use std::str::FromStr;
 
 use alignment_protocol::{
-    accounts as AccountsAll, instruction as InstructionAll, State as StateAccount,
+    accounts as AccountsAll, instruction as InstructionAll, Delegation as DelegationAccount,
+    FaucetConfig as FaucetConfigAccount, State as StateAccount,
 };
 
+use crate::commands::common::eligibility::init_topic_balance_ix_if_missing;
 use crate::commands::common::pda::{
-    get_state_pda, get_topic_pda, get_user_profile_pda, get_user_temp_token_account_pda,
-    get_user_topic_balance_pda,
+    get_delegation_pda, get_faucet_claim_pda, get_faucet_config_pda, get_state_pda, get_topic_pda,
+    get_user_profile_pda, get_user_temp_token_account_pda, get_user_topic_balance_pda,
 };
 
 /// Stake temporary alignment tokens for a topic to earn reputation
@@ -57,8 +64,11 @@ pub fn cmd_stake_topic_specific_tokens(
         amount, topic_index
     );
 
+    let init_balance_ix = init_topic_balance_ix_if_missing(program, &topic_pda, &user)?;
+
     let accounts = AccountsAll::StakeTopicSpecificTokens {
         user,
+        acting_signer: user,
         payer: user,
         user_profile: user_profile_pda,
         topic: topic_pda,
@@ -68,11 +78,14 @@ pub fn cmd_stake_topic_specific_tokens(
         user_temp_align_account: user_temp_align_account_pda,
         temp_rep_mint,
         user_temp_rep_account: user_temp_rep_account_pda,
-        token_program: anchor_spl::token::ID,
+        token_program: anchor_spl::token_2022::ID,
     };
 
-    let tx_sig = program
-        .request()
+    let mut request = program.request();
+    if let Some(ix) = init_balance_ix {
+        request = request.instruction(ix);
+    }
+    let tx_sig = request
         .accounts(accounts)
         .args(InstructionAll::StakeTopicSpecificTokens { amount })
         .send()?;
@@ -80,3 +93,165 @@ pub fn cmd_stake_topic_specific_tokens(
     println!("Tokens staked successfully (txSig: {})", tx_sig);
     Ok(())
 }
+
+/// Claim starter tempAlign for a topic from the faucet
+pub fn cmd_faucet_claim(program: &Program<Rc<Keypair>>, topic_index: u64) -> Result<()> {
+    let user = program.payer();
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (faucet_config_pda, _) = get_faucet_config_pda(program);
+    let (faucet_claim_pda, _) = get_faucet_claim_pda(program, &user);
+    let (user_topic_balance_pda, _) = get_user_topic_balance_pda(program, &user, &topic_pda);
+    let (user_temp_align_account_pda, _) =
+        get_user_temp_token_account_pda(program, &user, "user_temp_align");
+
+    if program
+        .rpc()
+        .get_account(&user_temp_align_account_pda)
+        .is_err()
+    {
+        return Err(anyhow::anyhow!(
+            "User profile or token accounts not set up. Please run 'alignment-protocol-cli user create-profile' first."
+        ));
+    }
+    let faucet_config: FaucetConfigAccount = program
+        .account(faucet_config_pda)
+        .map_err(|_| anyhow::anyhow!("The faucet has not been set up on this cluster."))?;
+
+    let state_data: StateAccount = program.account(state_pda)?;
+    let init_balance_ix = init_topic_balance_ix_if_missing(program, &topic_pda, &user)?;
+
+    println!(
+        "Claiming {} tempAlign from the faucet for topic #{}",
+        faucet_config.amount, topic_index
+    );
+
+    let accounts = AccountsAll::FaucetClaim {
+        state: state_pda,
+        faucet_config: faucet_config_pda,
+        user,
+        payer: user,
+        topic: topic_pda,
+        user_topic_balance: user_topic_balance_pda,
+        faucet_claim: faucet_claim_pda,
+        temp_align_mint: state_data.temp_align_mint,
+        user_temp_align_account: user_temp_align_account_pda,
+        token_program: anchor_spl::token_2022::ID,
+        system_program: system_program::ID,
+    };
+
+    let mut request = program.request();
+    if let Some(ix) = init_balance_ix {
+        request = request.instruction(ix);
+    }
+    let tx_sig = request
+        .accounts(accounts)
+        .args(InstructionAll::FaucetClaim {})
+        .send()?;
+
+    println!("Faucet claimed successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Delegate topic-specific tempRep to a validator, creating the delegation if needed
+pub fn cmd_delegate_temp_rep(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    validator_str: String,
+    amount: u64,
+) -> Result<()> {
+    let validator = Pubkey::from_str(&validator_str)
+        .map_err(|e| anyhow::anyhow!("Invalid validator pubkey format: {}", e))?;
+
+    let delegator = program.payer();
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (user_topic_balance_pda, _) = get_user_topic_balance_pda(program, &delegator, &topic_pda);
+    let (delegation_pda, _) = get_delegation_pda(program, &delegator, &topic_pda);
+
+    let tx_sig = match program.account::<DelegationAccount>(delegation_pda) {
+        Ok(delegation) => {
+            if delegation.validator != validator {
+                return Err(anyhow::anyhow!(
+                    "You already delegate to validator {} in topic #{}. Withdraw first to change validators.",
+                    delegation.validator,
+                    topic_index
+                ));
+            }
+
+            println!(
+                "Adding {} tempRep to delegation for validator {} in topic #{}",
+                amount, validator, topic_index
+            );
+
+            program
+                .request()
+                .accounts(AccountsAll::ModifyDelegation {
+                    topic: topic_pda,
+                    user_topic_balance: user_topic_balance_pda,
+                    delegation: delegation_pda,
+                    delegator,
+                })
+                .args(InstructionAll::DelegateTempRep { amount })
+                .send()?
+        }
+        Err(_) => {
+            let (validator_profile_pda, _) = get_user_profile_pda(program, &validator);
+
+            println!(
+                "Delegating {} tempRep to validator {} in topic #{}",
+                amount, validator, topic_index
+            );
+
+            program
+                .request()
+                .accounts(AccountsAll::CreateDelegation {
+                    topic: topic_pda,
+                    user_topic_balance: user_topic_balance_pda,
+                    delegation: delegation_pda,
+                    validator_profile: validator_profile_pda,
+                    validator,
+                    delegator,
+                    payer: delegator,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                })
+                .args(InstructionAll::CreateDelegation { amount })
+                .send()?
+        }
+    };
+
+    println!("tempRep delegated successfully (txSig: {})", tx_sig);
+    println!("Delegation PDA: {}", delegation_pda);
+    Ok(())
+}
+
+/// Withdraw unlocked tempRep from the payer's delegation in a topic
+pub fn cmd_undelegate_temp_rep(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    amount: u64,
+) -> Result<()> {
+    let delegator = program.payer();
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (user_topic_balance_pda, _) = get_user_topic_balance_pda(program, &delegator, &topic_pda);
+    let (delegation_pda, _) = get_delegation_pda(program, &delegator, &topic_pda);
+
+    println!(
+        "Withdrawing {} delegated tempRep in topic #{}",
+        amount, topic_index
+    );
+
+    let tx_sig = program
+        .request()
+        .accounts(AccountsAll::ModifyDelegation {
+            topic: topic_pda,
+            user_topic_balance: user_topic_balance_pda,
+            delegation: delegation_pda,
+            delegator,
+        })
+        .args(InstructionAll::UndelegateTempRep { amount })
+        .send()?;
+
+    println!("tempRep withdrawn successfully (txSig: {})", tx_sig);
+    Ok(())
+}
@@ -1,16 +1,22 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signature::Keypair;
-use anchor_client::Program;
+use anchor_client::{solana_sdk::system_program, Program};
 use anyhow::Result;
 use std::rc::Rc;
+use std::str::FromStr;
 
 use alignment_protocol::{
     accounts as AccountsAll, instruction as InstructionAll, State as StateAccount,
-    Topic as TopicAccount,
+    Topic as TopicAccount, TopicRole as TopicRoleAccount,
 };
 
+use crate::commands::common::committee::parse_committee_members;
+use crate::commands::common::eligibility::find_eligibility_accounts;
 use crate::commands::common::pda::{
-    get_state_pda, get_topic_pda, get_user_profile_pda, get_user_topic_balance_pda,
+    get_state_pda, get_topic_oracle_committee_pda, get_topic_pda, get_topic_role_pda,
+    get_user_profile_pda, get_user_topic_balance_pda,
 };
+use crate::commands::common::role::{describe_roles, find_topic_role, roles_from_flags};
 
 /// List all topics
 pub fn cmd_list_topics(program: &Program<Rc<Keypair>>) -> Result<()> {
@@ -68,6 +74,57 @@ pub fn cmd_view_topic(program: &Program<Rc<Keypair>>, topic_index: u64) -> Resul
                 "Reveal phase duration: {} seconds",
                 topic.reveal_phase_duration
             );
+            match topic.tokens_to_mint {
+                Some(tokens) => println!("Tokens to mint: {} (topic override)", tokens),
+                None => println!("Tokens to mint: global default"),
+            }
+            match topic.rep_mint_ratio_bps {
+                Some(bps) => println!("Rep mint ratio: {} bps (topic override)", bps),
+                None => println!("Rep mint ratio: 1:1 (default)"),
+            }
+            match topic.emission_budget_per_epoch {
+                Some(budget) => println!(
+                    "Emission budget: {} tempAlign per {} seconds ({} minted in epoch starting {})",
+                    budget,
+                    topic.emission_epoch_duration,
+                    topic.emission_epoch_minted,
+                    topic.emission_epoch_start
+                ),
+                None => println!(
+                    "Emission budget: unlimited ({} minted in current epoch)",
+                    topic.emission_epoch_minted
+                ),
+            }
+            println!("Minimum Rep balance: {}", topic.min_rep_balance);
+            println!(
+                "Minimum accepted submissions: {}",
+                topic.min_accepted_submissions
+            );
+            match topic.parent_topic {
+                Some(parent) => println!(
+                    "Parent topic: {} (minimum Rep earned: {})",
+                    parent, topic.min_parent_topic_rep
+                ),
+                None => println!("Parent topic: none"),
+            }
+            match topic.max_submissions_per_window {
+                Some(max) => println!(
+                    "Submission rate limit: {} per {} seconds per user",
+                    max, topic.submission_window_duration
+                ),
+                None => println!("Submission rate limit: unlimited"),
+            }
+            match topic.max_pending_submissions {
+                Some(max) => println!("Pending submissions limit: {} per user", max),
+                None => println!("Pending submissions limit: unlimited"),
+            }
+            println!("AI weight: {} bps", topic.ai_weight_bps);
+            println!("AI confidence scaling: {}", topic.ai_confidence_scaling);
+            match topic.oracle_pubkey {
+                Some(oracle) => println!("Oracle: {}", oracle),
+                None => println!("Oracle: global"),
+            }
+            println!("Oracle committee active: {}", topic.oracle_committee_active);
             Ok(())
         }
         Err(e) => Err(anyhow::anyhow!("Topic not found: {}", e)),
@@ -118,6 +175,9 @@ pub fn cmd_initialize_user_topic_balance(
     println!("  Topic PDA: {}", topic_pda);
     println!("  UserTopicBalance PDA: {}", user_topic_balance_pda);
 
+    let (user_rep_ata, parent_topic_balance) =
+        find_eligibility_accounts(program, &topic_pda, &user);
+
     let accounts = AccountsAll::InitializeUserTopicBalance {
         payer: program.payer(),
         user,
@@ -126,6 +186,8 @@ pub fn cmd_initialize_user_topic_balance(
         user_topic_balance: user_topic_balance_pda,
         system_program: anchor_client::solana_sdk::system_program::ID, // Use fully qualified path
         rent: anchor_client::solana_sdk::sysvar::rent::ID,             // Use fully qualified path
+        user_rep_ata,
+        parent_topic_balance,
     };
 
     let tx_sig = program
@@ -192,21 +254,37 @@ pub fn cmd_create_topic(
     Ok(())
 }
 
-/// Update an existing topic's settings (durations / active flag)
+/// Turn a `--value` / `--clear-value` flag pair into the nested option `update_topic` expects
+pub fn override_arg<T>(value: Option<T>, clear: bool) -> Option<Option<T>> {
+    if clear {
+        Some(None)
+    } else {
+        value.map(Some)
+    }
+}
+
+/// Update an existing topic's settings (durations / active flag / reward overrides)
 pub fn cmd_update_topic(
     program: &Program<Rc<Keypair>>,
     topic_index: u64,
     commit_duration: Option<u64>,
     reveal_duration: Option<u64>,
     active: Option<bool>,
+    tokens_to_mint: Option<Option<u64>>,
+    rep_mint_ratio_bps: Option<Option<u16>>,
 ) -> Result<()> {
     let (state_pda, _) = get_state_pda(program);
     let (topic_pda, _) = get_topic_pda(program, topic_index);
 
     println!("Updating topic #{} (PDA: {})", topic_index, topic_pda);
 
-    if commit_duration.is_none() && reveal_duration.is_none() && active.is_none() {
-        println!("Nothing to update – provide at least one --commit-duration, --reveal-duration or --active flag");
+    if commit_duration.is_none()
+        && reveal_duration.is_none()
+        && active.is_none()
+        && tokens_to_mint.is_none()
+        && rep_mint_ratio_bps.is_none()
+    {
+        println!("Nothing to update – provide at least one --commit-duration, --reveal-duration, --active, --tokens-to-mint or --rep-mint-ratio-bps flag");
         return Ok(());
     }
 
@@ -214,6 +292,7 @@ pub fn cmd_update_topic(
         authority: program.payer(),
         state: state_pda,
         topic: topic_pda,
+        topic_role: find_topic_role(program, &topic_pda, &program.payer()),
     };
 
     let tx_sig = program
@@ -223,9 +302,367 @@ pub fn cmd_update_topic(
             commit_phase_duration: commit_duration,
             reveal_phase_duration: reveal_duration,
             is_active: active,
+            tokens_to_mint,
+            rep_mint_ratio_bps,
         })
         .send()?;
 
     println!("Topic updated successfully (txSig: {})", tx_sig);
     Ok(())
 }
+
+/// Set a topic's per-epoch tempAlign emission budget
+pub fn cmd_update_topic_emission_budget(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    budget: Option<u64>,
+    epoch_duration: Option<u64>,
+) -> Result<()> {
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+
+    match budget {
+        Some(b) => println!(
+            "Setting emission budget of topic #{} to {} tempAlign per epoch",
+            topic_index, b
+        ),
+        None => println!("Removing emission budget of topic #{}", topic_index),
+    }
+
+    let accounts = AccountsAll::UpdateTopic {
+        authority: program.payer(),
+        state: state_pda,
+        topic: topic_pda,
+        topic_role: find_topic_role(program, &topic_pda, &program.payer()),
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateTopicEmissionBudget {
+            emission_budget_per_epoch: budget,
+            emission_epoch_duration: epoch_duration,
+        })
+        .send()?;
+
+    println!(
+        "Topic emission budget updated successfully (txSig: {})",
+        tx_sig
+    );
+    Ok(())
+}
+
+/// Set the weight of AI voting power in a topic (protocol authority, topic creator or moderator)
+pub fn cmd_update_topic_ai_weight(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    ai_weight_bps: u16,
+    ai_confidence_scaling: bool,
+) -> Result<()> {
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+
+    println!(
+        "Setting AI weight of topic #{} to {} bps (confidence scaling: {})",
+        topic_index, ai_weight_bps, ai_confidence_scaling
+    );
+
+    let accounts = AccountsAll::UpdateTopic {
+        authority: program.payer(),
+        state: state_pda,
+        topic: topic_pda,
+        topic_role: find_topic_role(program, &topic_pda, &program.payer()),
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateTopicAiWeight {
+            ai_weight_bps,
+            ai_confidence_scaling,
+        })
+        .send()?;
+
+    println!("Topic AI weight updated successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Assign a topic its own oracle, or fall back to the global one (protocol authority only)
+pub fn cmd_update_topic_oracle(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    oracle_str: Option<String>,
+) -> Result<()> {
+    let oracle_pubkey = oracle_str
+        .map(|s| Pubkey::from_str(&s))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid oracle pubkey format: {}", e))?;
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+
+    match oracle_pubkey {
+        Some(oracle) => println!("Setting oracle of topic #{} to {}", topic_index, oracle),
+        None => println!("Topic #{} will use the global oracle", topic_index),
+    }
+
+    let accounts = AccountsAll::UpdateTopic {
+        authority: program.payer(),
+        state: state_pda,
+        topic: topic_pda,
+        topic_role: find_topic_role(program, &topic_pda, &program.payer()),
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateTopicOracle { oracle_pubkey })
+        .send()?;
+
+    println!("Topic oracle updated successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Create a topic's own oracle committee (protocol authority only)
+pub fn cmd_init_topic_oracle_committee(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    members: Vec<String>,
+    threshold: u8,
+) -> Result<()> {
+    let members = parse_committee_members(&members)?;
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (oracle_committee_pda, _) = get_topic_oracle_committee_pda(program, &topic_pda);
+
+    println!(
+        "Creating oracle committee for topic #{} with {} members, threshold {}",
+        topic_index,
+        members.len(),
+        threshold
+    );
+
+    let tx_sig = program
+        .request()
+        .accounts(AccountsAll::InitializeTopicOracleCommittee {
+            state: state_pda,
+            topic: topic_pda,
+            oracle_committee: oracle_committee_pda,
+            authority: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(InstructionAll::InitializeTopicOracleCommittee { members, threshold })
+        .send()?;
+
+    println!(
+        "Topic oracle committee created at {} (txSig: {})",
+        oracle_committee_pda, tx_sig
+    );
+    Ok(())
+}
+
+/// Replace a topic committee's members and threshold, or switch it on or off
+/// (protocol authority only)
+pub fn cmd_update_topic_oracle_committee(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    members: Vec<String>,
+    threshold: u8,
+    active: bool,
+) -> Result<()> {
+    let members = parse_committee_members(&members)?;
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (oracle_committee_pda, _) = get_topic_oracle_committee_pda(program, &topic_pda);
+
+    println!(
+        "Updating oracle committee for topic #{} to {} members, threshold {} (active: {})",
+        topic_index,
+        members.len(),
+        threshold,
+        active
+    );
+
+    let tx_sig = program
+        .request()
+        .accounts(AccountsAll::UpdateTopicOracleCommittee {
+            state: state_pda,
+            topic: topic_pda,
+            oracle_committee: oracle_committee_pda,
+            authority: program.payer(),
+        })
+        .args(InstructionAll::UpdateTopicOracleCommittee {
+            members,
+            threshold,
+            active,
+        })
+        .send()?;
+
+    println!(
+        "Topic oracle committee updated successfully (txSig: {})",
+        tx_sig
+    );
+    Ok(())
+}
+
+/// Set a topic's per-user submission rate limits (protocol authority, topic creator or moderator)
+pub fn cmd_update_topic_rate_limits(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    max_per_window: Option<u64>,
+    window_duration: Option<u64>,
+    max_pending: Option<u64>,
+) -> Result<()> {
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+
+    println!("Updating submission rate limits of topic #{}", topic_index);
+
+    let accounts = AccountsAll::UpdateTopic {
+        authority: program.payer(),
+        state: state_pda,
+        topic: topic_pda,
+        topic_role: find_topic_role(program, &topic_pda, &program.payer()),
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateTopicRateLimits {
+            max_submissions_per_window: max_per_window,
+            submission_window_duration: window_duration,
+            max_pending_submissions: max_pending,
+        })
+        .send()?;
+
+    println!("Topic rate limits updated successfully (txSig: {})", tx_sig);
+    Ok(())
+}
+
+/// Set a topic's participation rules (protocol authority, topic creator or moderator)
+pub fn cmd_update_topic_eligibility(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    min_rep_balance: Option<u64>,
+    min_accepted_submissions: Option<u64>,
+    parent_topic_index: Option<Option<u64>>,
+    min_parent_topic_rep: Option<u64>,
+) -> Result<()> {
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let parent_topic = parent_topic_index.map(|index| index.map(|i| get_topic_pda(program, i).0));
+
+    println!("Updating eligibility rules of topic #{}", topic_index);
+    if let Some(Some(parent)) = parent_topic {
+        println!("Parent topic PDA: {}", parent);
+    }
+
+    let accounts = AccountsAll::UpdateTopic {
+        authority: program.payer(),
+        state: state_pda,
+        topic: topic_pda,
+        topic_role: find_topic_role(program, &topic_pda, &program.payer()),
+    };
+
+    let tx_sig = program
+        .request()
+        .accounts(accounts)
+        .args(InstructionAll::UpdateTopicEligibility {
+            min_rep_balance,
+            min_accepted_submissions,
+            parent_topic,
+            min_parent_topic_rep,
+        })
+        .send()?;
+
+    println!(
+        "Topic eligibility rules updated successfully (txSig: {})",
+        tx_sig
+    );
+    Ok(())
+}
+
+/// Grant topic-scoped roles to a wallet (topic creator or protocol authority only)
+pub fn cmd_grant_topic_role(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    holder_str: String,
+    moderator: bool,
+    phase_manager: bool,
+    oracle: bool,
+) -> Result<()> {
+    let holder = Pubkey::from_str(&holder_str)
+        .map_err(|e| anyhow::anyhow!("Invalid holder pubkey format: {}", e))?;
+    let roles = roles_from_flags(moderator, phase_manager, oracle)?;
+
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (topic_role_pda, _) = get_topic_role_pda(program, &topic_pda, &holder);
+
+    println!(
+        "Granting roles [{}] on topic #{} to {}",
+        describe_roles(roles),
+        topic_index,
+        holder
+    );
+
+    let tx_sig = program
+        .request()
+        .accounts(AccountsAll::GrantTopicRole {
+            state: state_pda,
+            topic: topic_pda,
+            topic_role: topic_role_pda,
+            holder,
+            authority: program.payer(),
+            system_program: system_program::ID,
+        })
+        .args(InstructionAll::GrantTopicRole { roles })
+        .send()?;
+
+    println!("Roles granted successfully (txSig: {})", tx_sig);
+    println!("TopicRole PDA: {}", topic_role_pda);
+    Ok(())
+}
+
+/// Revoke all of a wallet's topic-scoped roles (topic creator or protocol authority only)
+pub fn cmd_revoke_topic_role(
+    program: &Program<Rc<Keypair>>,
+    topic_index: u64,
+    holder_str: String,
+) -> Result<()> {
+    let holder = Pubkey::from_str(&holder_str)
+        .map_err(|e| anyhow::anyhow!("Invalid holder pubkey format: {}", e))?;
+
+    let (state_pda, _) = get_state_pda(program);
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (topic_role_pda, _) = get_topic_role_pda(program, &topic_pda, &holder);
+
+    let topic_role: TopicRoleAccount = program.account(topic_role_pda).map_err(|e| {
+        anyhow::anyhow!(
+            "No roles found for {} on topic #{}: {}",
+            holder,
+            topic_index,
+            e
+        )
+    })?;
+
+    println!(
+        "Revoking roles [{}] on topic #{} from {}",
+        describe_roles(topic_role.roles),
+        topic_index,
+        holder
+    );
+
+    let tx_sig = program
+        .request()
+        .accounts(AccountsAll::RevokeTopicRole {
+            state: state_pda,
+            topic: topic_pda,
+            topic_role: topic_role_pda,
+            authority: program.payer(),
+        })
+        .args(InstructionAll::RevokeTopicRole {})
+        .send()?;
+
+    println!("Roles revoked successfully (txSig: {})", tx_sig);
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_client::solana_sdk::signature::Keypair;
 use anchor_client::{
-    solana_sdk::{pubkey::Pubkey, system_program, sysvar::rent::ID as RENT_ID},
+    solana_sdk::{pubkey::Pubkey, system_program},
     Program,
 };
 use anyhow::Result;
@@ -17,6 +17,9 @@ use crate::commands::common::pda::{
 };
 
 /// Create a complete user profile with all necessary token accounts
+///
+/// Uses `onboard_user`, which skips anything that already exists, so rerunning this after a
+/// partial setup completes it.
 pub fn cmd_create_user_profile(program: &Program<Rc<Keypair>>) -> Result<()> {
     let user = program.payer();
     let (user_profile_pda, _) = get_user_profile_pda(program, &user);
@@ -25,138 +28,50 @@ pub fn cmd_create_user_profile(program: &Program<Rc<Keypair>>) -> Result<()> {
     println!("Creating complete user profile for {}", user);
     println!("User profile PDA: {}", user_profile_pda);
 
-    // Step 1: Create user profile
-    println!("Step 1: Creating user profile...");
-    let accounts = AccountsAll::CreateUserProfile {
-        payer: user,
-        user,
-        user_profile: user_profile_pda,
-        system_program: system_program::ID,
-    };
-
-    let tx_sig = program
-        .request()
-        .accounts(accounts)
-        .args(InstructionAll::CreateUserProfile {})
-        .send()?;
-
-    println!("User profile created successfully (txSig: {})", tx_sig);
-
     // Get state account data to access token mint addresses
     let state_data: StateAccount = program.account(state_pda)?;
 
-    // Step 2: Create ATA for permanent alignment token
-    println!("Step 2: Creating permanent alignment token account...");
-    let align_mint = state_data.align_mint;
-    let align_ata = get_token_ata(&user, &align_mint);
-
-    let accounts = AccountsAll::CreateUserAta {
-        payer: user,
-        user,
-        user_profile: user_profile_pda,
-        state: state_pda,
-        mint: align_mint,
-        user_ata: align_ata,
-        system_program: system_program::ID,
-        token_program: anchor_spl::token::ID,
-        associated_token_program: anchor_spl::associated_token::ID,
-        rent: RENT_ID,
-    };
-
-    let tx_sig = program
-        .request()
-        .accounts(accounts)
-        .args(InstructionAll::CreateUserAta {})
-        .send()?;
-
-    println!("Permanent alignment token account created: {}", align_ata);
-    println!("Transaction signature: {}", tx_sig);
-
-    // Step 3: Create ATA for permanent reputation token
-    println!("Step 3: Creating permanent reputation token account...");
-    let rep_mint = state_data.rep_mint;
-    let rep_ata = get_token_ata(&user, &rep_mint);
-
-    let accounts = AccountsAll::CreateUserAta {
-        payer: user,
-        user,
-        user_profile: user_profile_pda,
-        state: state_pda,
-        mint: rep_mint,
-        user_ata: rep_ata,
-        system_program: system_program::ID,
-        token_program: anchor_spl::token::ID,
-        associated_token_program: anchor_spl::associated_token::ID,
-        rent: RENT_ID,
-    };
-
-    let tx_sig = program
-        .request()
-        .accounts(accounts)
-        .args(InstructionAll::CreateUserAta {})
-        .send()?;
-
-    println!("Permanent reputation token account created: {}", rep_ata);
-    println!("Transaction signature: {}", tx_sig);
-
-    // Step 4: Create protocol-owned temporary alignment token account
-    println!("Step 4: Creating protocol-owned temporary alignment token account...");
-    let temp_align_mint = state_data.temp_align_mint;
     let (temp_align_account_pda, _) =
         get_user_temp_token_account_pda(program, &user, "user_temp_align");
+    let (temp_rep_account_pda, _) =
+        get_user_temp_token_account_pda(program, &user, "user_temp_rep");
+    let align_ata = get_token_ata(&user, &state_data.align_mint);
+    let rep_ata = get_token_ata(&user, &state_data.rep_mint);
 
-    let accounts = AccountsAll::CreateUserTempAlignAccount {
+    let accounts = AccountsAll::OnboardUser {
+        state: state_pda,
         payer: user,
         user,
         user_profile: user_profile_pda,
-        state: state_pda,
-        mint: temp_align_mint,
-        token_account: temp_align_account_pda,
+        temp_align_mint: state_data.temp_align_mint,
+        align_mint: state_data.align_mint,
+        temp_rep_mint: state_data.temp_rep_mint,
+        rep_mint: state_data.rep_mint,
+        user_temp_align_account: temp_align_account_pda,
+        user_temp_rep_account: temp_rep_account_pda,
+        user_align_ata: align_ata,
+        user_rep_ata: rep_ata,
         system_program: system_program::ID,
-        token_program: anchor_spl::token::ID,
-        rent: RENT_ID,
+        token_program: anchor_spl::token_2022::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
     };
 
     let tx_sig = program
         .request()
         .accounts(accounts)
-        .args(InstructionAll::CreateUserTempAlignAccount {})
+        .args(InstructionAll::OnboardUser {})
         .send()?;
 
     println!(
-        "Temporary alignment token account created: {}",
+        "Temporary alignment token account: {}",
         temp_align_account_pda
     );
-    println!("Transaction signature: {}", tx_sig);
-
-    // Step 5: Create protocol-owned temporary reputation token account
-    println!("Step 5: Creating protocol-owned temporary reputation token account...");
-    let temp_rep_mint = state_data.temp_rep_mint;
-    let (temp_rep_account_pda, _) =
-        get_user_temp_token_account_pda(program, &user, "user_temp_rep");
-
-    let accounts = AccountsAll::CreateUserTempRepAccount {
-        payer: user,
-        user,
-        user_profile: user_profile_pda,
-        state: state_pda,
-        mint: temp_rep_mint,
-        token_account: temp_rep_account_pda,
-        system_program: system_program::ID,
-        token_program: anchor_spl::token::ID,
-        rent: RENT_ID,
-    };
-
-    let tx_sig = program
-        .request()
-        .accounts(accounts)
-        .args(InstructionAll::CreateUserTempRepAccount {})
-        .send()?;
-
     println!(
-        "Temporary reputation token account created: {}",
+        "Temporary reputation token account: {}",
         temp_rep_account_pda
     );
+    println!("Permanent alignment token account: {}", align_ata);
+    println!("Permanent reputation token account: {}", rep_ata);
     println!("Transaction signature: {}", tx_sig);
 
     println!("\nUser profile setup completed successfully!");
@@ -209,6 +124,10 @@ pub fn cmd_view_user_profile(
             } else {
                 println!("  User Rep ATA: Not Set");
             }
+            println!(
+                "  Accepted Submissions: {}",
+                profile.accepted_submission_count
+            );
 
             // Display UserTopicBalance accounts
             println!("\nTopic Balances:");
@@ -235,6 +154,7 @@ pub fn cmd_view_user_profile(
                                 "    Locked Temp Rep: {}",
                                 balance_account.locked_temp_rep_amount
                             );
+                            println!("    Rep Earned: {}", balance_account.rep_earned);
                         }
                     }
                 }
@@ -251,11 +171,12 @@ pub fn cmd_view_user_profile(
 
                     // Permanent align token account
                     let align_ata = get_token_ata(&user, &state_data.align_mint);
-                    let align_balance =
-                        match program.account::<anchor_spl::token::TokenAccount>(align_ata) {
-                            Ok(token_account) => token_account.amount.to_string(),
-                            Err(_) => "Not found/created".to_string(),
-                        };
+                    let align_balance = match program
+                        .account::<anchor_spl::token_interface::TokenAccount>(align_ata)
+                    {
+                        Ok(token_account) => token_account.amount.to_string(),
+                        Err(_) => "Not found/created".to_string(),
+                    };
                     println!(
                         "  Permanent Align Token ATA: {} (Balance: {})",
                         align_ata, align_balance
@@ -263,11 +184,12 @@ pub fn cmd_view_user_profile(
 
                     // Permanent rep token account
                     let rep_ata = get_token_ata(&user, &state_data.rep_mint);
-                    let rep_balance =
-                        match program.account::<anchor_spl::token::TokenAccount>(rep_ata) {
-                            Ok(token_account) => token_account.amount.to_string(),
-                            Err(_) => "Not found/created".to_string(),
-                        };
+                    let rep_balance = match program
+                        .account::<anchor_spl::token_interface::TokenAccount>(rep_ata)
+                    {
+                        Ok(token_account) => token_account.amount.to_string(),
+                        Err(_) => "Not found/created".to_string(),
+                    };
                     println!(
                         "  Permanent Rep Token ATA: {} (Balance: {})",
                         rep_ata, rep_balance
@@ -277,8 +199,9 @@ pub fn cmd_view_user_profile(
                     let (temp_align_account_pda, _) =
                         get_user_temp_token_account_pda(program, &user, "user_temp_align");
                     let temp_align_balance = match program
-                        .account::<anchor_spl::token::TokenAccount>(temp_align_account_pda)
-                    {
+                        .account::<anchor_spl::token_interface::TokenAccount>(
+                            temp_align_account_pda,
+                        ) {
                         Ok(token_account) => token_account.amount.to_string(),
                         Err(_) => "Not found/created".to_string(),
                     };
@@ -291,7 +214,7 @@ pub fn cmd_view_user_profile(
                     let (temp_rep_account_pda, _) =
                         get_user_temp_token_account_pda(program, &user, "user_temp_rep");
                     let temp_rep_balance = match program
-                        .account::<anchor_spl::token::TokenAccount>(temp_rep_account_pda)
+                        .account::<anchor_spl::token_interface::TokenAccount>(temp_rep_account_pda)
                     {
                         Ok(token_account) => token_account.amount.to_string(),
                         Err(_) => "Not found/created".to_string(),
@@ -1,3 +1,4 @@
+use anchor_client::solana_sdk::instruction::AccountMeta;
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signature::Keypair;
 use anchor_client::{
@@ -11,17 +12,22 @@ use std::rc::Rc;
 use std::str::FromStr;
 
 use alignment_protocol::{
-    accounts as AccountsAll, instruction as InstructionAll, State as StateAccount,
+    accounts as AccountsAll, instruction as InstructionAll, Delegation as DelegationAccount,
+    State as StateAccount, VoteCommit as VoteCommitAccount, MAX_DELEGATIONS_PER_VOTE,
 };
 
+use crate::commands::common::eligibility::{
+    find_eligibility_accounts, init_topic_balance_ix_if_missing,
+};
 use crate::commands::common::pda::{
-    get_state_pda, get_submission_topic_link_pda, get_token_ata, get_topic_pda,
-    get_user_profile_pda, get_user_temp_token_account_pda, get_user_topic_balance_pda,
-    get_vote_commit_pda,
+    get_existing_fee_vault, get_state_pda, get_submission_topic_link_pda, get_token_ata,
+    get_topic_pda, get_user_profile_pda, get_user_temp_token_account_pda,
+    get_user_topic_balance_pda, get_vote_commit_pda,
 };
 use crate::commands::common::vote::{generate_vote_hash, parse_vote_choice};
 
 /// Commit a vote (first phase)
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_commit_vote(
     program: &Program<Rc<Keypair>>,
     submission_pda_str: String,
@@ -29,6 +35,7 @@ pub fn cmd_commit_vote(
     choice_str: String,
     temp_rep_amount: u64,
     perm_rep_amount: u64,
+    delegated_temp_rep: Option<u64>,
     nonce_opt: Option<String>,
 ) -> Result<()> {
     let submission_pda = Pubkey::from_str(&submission_pda_str)
@@ -99,8 +106,37 @@ pub fn cmd_commit_vote(
     // println!("Nonce: {}", actual_nonce);
     println!("Generated hash: {:?}", vote_hash);
 
+    // Draw on delegations to this validator in the topic that have unlocked tempRep, up to
+    // `delegated_temp_rep` in total when given
+    let mut delegation_metas: Vec<AccountMeta> = Vec::new();
+    let mut delegation_amounts: Vec<u64> = Vec::new();
+    let mut left_to_draw = delegated_temp_rep.unwrap_or(u64::MAX);
+    for (pubkey, d) in program
+        .accounts::<DelegationAccount>(vec![])?
+        .into_iter()
+        .filter(|(_, d)| d.validator == validator && d.topic == topic_pda && d.amount > 0)
+        .take(MAX_DELEGATIONS_PER_VOTE)
+    {
+        if left_to_draw == 0 {
+            break;
+        }
+        let amount = d.amount.min(left_to_draw);
+        left_to_draw -= amount;
+        println!(
+            "Drawing {} delegated tempRep from delegator {}",
+            amount, d.delegator
+        );
+        delegation_metas.push(AccountMeta::new(pubkey, false));
+        delegation_amounts.push(amount);
+    }
+
+    let (_, parent_topic_balance) = find_eligibility_accounts(program, &topic_pda, &validator);
+    let init_balance_ix = init_topic_balance_ix_if_missing(program, &topic_pda, &validator)?;
+
     let accounts = AccountsAll::CommitVote {
         validator,
+        acting_signer: validator,
+        session_key: None,
         payer: validator,
         user_profile: user_profile_pda,
         submission_topic_link: submission_topic_link_pda,
@@ -112,15 +148,21 @@ pub fn cmd_commit_vote(
         validator_rep_ata,
         system_program: system_program::ID,
         rent: sysvar::rent::ID,
+        parent_topic_balance,
     };
 
-    let tx_sig = program
-        .request()
+    let mut request = program.request();
+    if let Some(ix) = init_balance_ix {
+        request = request.instruction(ix);
+    }
+    let tx_sig = request
         .accounts(accounts)
+        .accounts(delegation_metas)
         .args(InstructionAll::CommitVote {
             vote_hash,
             temp_rep_amount,
             perm_rep_amount,
+            delegation_amounts,
         })
         .send()?;
 
@@ -177,6 +219,8 @@ pub fn cmd_reveal_vote(
 
     let accounts = AccountsAll::RevealVote {
         validator,
+        acting_signer: validator,
+        session_key: None,
         payer: validator,
         submission_topic_link: submission_topic_link_pda,
         submission: submission_pda,
@@ -240,16 +284,86 @@ pub fn cmd_finalize_vote(
         validator_rep_ata,
         temp_rep_mint,
         rep_mint,
-        token_program: anchor_spl::token::ID,
+        fee_vault: get_existing_fee_vault(program),
+        token_program: anchor_spl::token_2022::ID,
         system_program: system_program::ID,
     };
 
+    // Pass back every delegation drawn into the vote: delegation, delegator tempRep account,
+    // delegator Rep ATA, delegator topic balance
+    let vote_commit: VoteCommitAccount = program.account(vote_commit_pda)?;
+    let mut delegation_metas = Vec::with_capacity(vote_commit.delegations.len() * 4);
+    for stake in vote_commit.delegations.iter() {
+        let delegation: DelegationAccount = program.account(stake.delegation)?;
+        let (delegator_temp_rep_pda, _) =
+            get_user_temp_token_account_pda(program, &delegation.delegator, "user_temp_rep");
+        delegation_metas.push(AccountMeta::new(stake.delegation, false));
+        delegation_metas.push(AccountMeta::new(delegator_temp_rep_pda, false));
+        delegation_metas.push(AccountMeta::new(
+            get_token_ata(&delegation.delegator, &rep_mint),
+            false,
+        ));
+        let (delegator_topic_balance_pda, _) =
+            get_user_topic_balance_pda(program, &delegation.delegator, &topic_pda);
+        delegation_metas.push(AccountMeta::new(delegator_topic_balance_pda, false));
+    }
+
     let tx_sig = program
         .request()
         .accounts(accounts)
+        .accounts(delegation_metas)
         .args(InstructionAll::FinalizeVote {})
         .send()?;
 
     println!("Vote finalized successfully (txSig: {})", tx_sig);
     Ok(())
 }
+
+/// Release the tempRep locked in a vote that was not revealed before the reveal phase ended
+pub fn cmd_settle_unrevealed_vote(
+    program: &Program<Rc<Keypair>>,
+    submission_pda_str: String,
+    topic_index: u64,
+    validator_str: Option<String>,
+) -> Result<()> {
+    let submission_pda = Pubkey::from_str(&submission_pda_str)
+        .map_err(|e| anyhow::anyhow!("Invalid Submission PDA format: {}", e))?;
+    let validator = match validator_str {
+        Some(pubkey_str) => Pubkey::from_str(&pubkey_str)?,
+        None => program.payer(),
+    };
+
+    let (topic_pda, _) = get_topic_pda(program, topic_index);
+    let (submission_topic_link_pda, _) =
+        get_submission_topic_link_pda(program, &submission_pda, &topic_pda);
+    let (vote_commit_pda, _) = get_vote_commit_pda(program, &submission_topic_link_pda, &validator);
+    let (user_topic_balance_pda, _) = get_user_topic_balance_pda(program, &validator, &topic_pda);
+
+    // Pass back every delegation drawn into the vote so its locked amount is released
+    let vote_commit: VoteCommitAccount = program.account(vote_commit_pda)?;
+    let delegation_metas: Vec<AccountMeta> = vote_commit
+        .delegations
+        .iter()
+        .map(|stake| AccountMeta::new(stake.delegation, false))
+        .collect();
+
+    println!(
+        "Releasing unrevealed vote by {} on submission {} in topic #{}",
+        validator, submission_pda, topic_index
+    );
+
+    let tx_sig = program
+        .request()
+        .accounts(AccountsAll::SettleUnrevealedVote {
+            submission_topic_link: submission_topic_link_pda,
+            vote_commit: vote_commit_pda,
+            user_topic_balance: user_topic_balance_pda,
+            authority: program.payer(),
+        })
+        .accounts(delegation_metas)
+        .args(InstructionAll::SettleUnrevealedVote {})
+        .send()?;
+
+    println!("Unrevealed vote settled successfully (txSig: {})", tx_sig);
+    Ok(())
+}
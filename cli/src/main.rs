@@ -1,5 +1,4 @@
 mod cli;
-mod client;
 mod commands;
 
 use anyhow::Result;
@@ -26,8 +25,8 @@ fn main() -> Result<()> {
         }
     };
 
-    // Setup client using our client module
-    let program = client::setup_client(&cli.keypair, &cluster, &cli.program_id)?;
+    // Setup client using the shared client crate
+    let program = alignment_client::setup_client(&cli.keypair, &cluster, &cli.program_id)?;
 
     // Handle commands
     match cli.command {
@@ -56,12 +55,109 @@ fn main() -> Result<()> {
                 commit_duration,
                 reveal_duration,
                 active,
+                tokens_to_mint,
+                clear_tokens_to_mint,
+                rep_mint_ratio_bps,
+                clear_rep_mint_ratio,
             } => user::topic::cmd_update_topic(
                 &program,
                 topic_index,
                 commit_duration,
                 reveal_duration,
                 active,
+                user::topic::override_arg(tokens_to_mint, clear_tokens_to_mint),
+                user::topic::override_arg(rep_mint_ratio_bps, clear_rep_mint_ratio),
+            )?,
+            TopicCommands::GrantRole {
+                topic_index,
+                holder,
+                moderator,
+                phase_manager,
+                oracle,
+            } => user::topic::cmd_grant_topic_role(
+                &program,
+                topic_index,
+                holder,
+                moderator,
+                phase_manager,
+                oracle,
+            )?,
+            TopicCommands::RevokeRole {
+                topic_index,
+                holder,
+            } => user::topic::cmd_revoke_topic_role(&program, topic_index, holder)?,
+            TopicCommands::SetEmissionBudget {
+                topic_index,
+                budget,
+                epoch_duration,
+            } => user::topic::cmd_update_topic_emission_budget(
+                &program,
+                topic_index,
+                budget,
+                epoch_duration,
+            )?,
+            TopicCommands::SetAiWeight {
+                topic_index,
+                ai_weight_bps,
+                confidence_scaling,
+            } => user::topic::cmd_update_topic_ai_weight(
+                &program,
+                topic_index,
+                ai_weight_bps,
+                confidence_scaling,
+            )?,
+            TopicCommands::SetOracle {
+                topic_index,
+                oracle,
+            } => user::topic::cmd_update_topic_oracle(&program, topic_index, oracle)?,
+            TopicCommands::InitOracleCommittee {
+                topic_index,
+                members,
+                threshold,
+            } => user::topic::cmd_init_topic_oracle_committee(
+                &program,
+                topic_index,
+                members,
+                threshold,
+            )?,
+            TopicCommands::UpdateOracleCommittee {
+                topic_index,
+                members,
+                threshold,
+                inactive,
+            } => user::topic::cmd_update_topic_oracle_committee(
+                &program,
+                topic_index,
+                members,
+                threshold,
+                !inactive,
+            )?,
+            TopicCommands::SetRateLimits {
+                topic_index,
+                max_per_window,
+                window_duration,
+                max_pending,
+            } => user::topic::cmd_update_topic_rate_limits(
+                &program,
+                topic_index,
+                max_per_window,
+                window_duration,
+                max_pending,
+            )?,
+            TopicCommands::SetEligibility {
+                topic_index,
+                min_rep,
+                min_accepted_submissions,
+                parent_topic,
+                clear_parent_topic,
+                min_parent_rep,
+            } => user::topic::cmd_update_topic_eligibility(
+                &program,
+                topic_index,
+                min_rep,
+                min_accepted_submissions,
+                user::topic::override_arg(parent_topic, clear_parent_topic),
+                min_parent_rep,
             )?,
         },
         Commands::User { subcommand } => match subcommand {
@@ -98,6 +194,9 @@ fn main() -> Result<()> {
                 topic_index,
                 amount,
             )?,
+            SubmissionCommands::ExpireAiRequest { ai_request_pda } => {
+                user::submission::cmd_expire_ai_request(&program, ai_request_pda)?
+            }
         },
         Commands::Vote { subcommand } => match subcommand {
             VoteCommands::Commit {
@@ -106,6 +205,7 @@ fn main() -> Result<()> {
                 choice,
                 temp_rep_amount,
                 perm_rep_amount,
+                delegated_temp_rep,
                 nonce,
             } => user::vote::cmd_commit_vote(
                 &program,
@@ -114,6 +214,7 @@ fn main() -> Result<()> {
                 choice,
                 temp_rep_amount,
                 perm_rep_amount,
+                delegated_temp_rep,
                 nonce,
             )?,
             VoteCommands::Reveal {
@@ -126,6 +227,16 @@ fn main() -> Result<()> {
                 submission_pda,
                 topic_index,
             } => user::vote::cmd_finalize_vote(&program, submission_pda, topic_index)?,
+            VoteCommands::SettleUnrevealed {
+                submission_pda,
+                topic_index,
+                validator,
+            } => user::vote::cmd_settle_unrevealed_vote(
+                &program,
+                submission_pda,
+                topic_index,
+                validator,
+            )?,
             VoteCommands::SetPhases {
                 submission_pda,
                 topic_index,
@@ -151,13 +262,34 @@ fn main() -> Result<()> {
                 topic_index,
                 amount,
             } => user::token::cmd_stake_topic_specific_tokens(&program, topic_index, amount)?,
+            TokenCommands::Delegate {
+                topic_index,
+                validator,
+                amount,
+            } => user::token::cmd_delegate_temp_rep(&program, topic_index, validator, amount)?,
+            TokenCommands::Undelegate {
+                topic_index,
+                amount,
+            } => user::token::cmd_undelegate_temp_rep(&program, topic_index, amount)?,
+            TokenCommands::Faucet { topic_index } => {
+                user::token::cmd_faucet_claim(&program, topic_index)?
+            }
             TokenCommands::Mint {
                 token_type,
                 to,
                 amount,
+                topic,
+                reason,
             } => {
                 println!("[ADMIN] Minting tokens...");
-                admin::token::cmd_admin_mint_tokens(&program, &token_type, &to, amount)?
+                admin::token::cmd_admin_mint_tokens(
+                    &program,
+                    &token_type,
+                    &to,
+                    amount,
+                    topic,
+                    reason,
+                )?
             }
         },
         Commands::Query { subcommand } => match subcommand {
@@ -178,6 +310,10 @@ fn main() -> Result<()> {
             QueryCommands::TopicBalance { topic_index, user } => {
                 user::query::cmd_view_user_topic_balance(&program, topic_index, user)?
             }
+            QueryCommands::Delegation {
+                topic_index,
+                delegator,
+            } => user::query::cmd_query_delegation(&program, topic_index, delegator)?,
         },
         Commands::Debug { subcommand } => match subcommand {
             DebugCommands::TokenAccount { token_type, user } => {
@@ -198,6 +334,12 @@ fn main() -> Result<()> {
                 InitCommands::All { oracle_pubkey } => {
                     admin::init::cmd_init_all(&program, oracle_pubkey)?
                 }
+                InitCommands::Metadata {
+                    token_type,
+                    name,
+                    symbol,
+                    uri,
+                } => admin::init::cmd_init_metadata(&program, &token_type, name, symbol, uri)?,
             }
         }
         Commands::Config { subcommand } => match subcommand {
@@ -205,6 +347,51 @@ fn main() -> Result<()> {
                 println!("[ADMIN] Updating token configuration...");
                 admin::config::cmd_admin_update_tokens_to_mint(&program, tokens)?
             }
+            ConfigCommands::UpdateValidatorRewardEmission { amount } => {
+                println!("[ADMIN] Updating validator reward emission...");
+                admin::config::cmd_admin_update_validator_reward_emission(&program, amount)?
+            }
+            ConfigCommands::UpdateDelegationCommission { bps } => {
+                println!("[ADMIN] Updating delegation commission...");
+                admin::config::cmd_admin_update_delegation_commission(&program, bps)?
+            }
+            ConfigCommands::UpdateFinalizationReward {
+                lamports,
+                min_voting_power,
+            } => {
+                println!("[ADMIN] Updating finalization reward...");
+                admin::config::cmd_admin_update_finalization_reward(
+                    &program,
+                    lamports,
+                    min_voting_power,
+                )?
+            }
+            ConfigCommands::UpdateAdminMintCap { token_type, cap } => {
+                println!("[ADMIN] Updating admin mint cap...");
+                admin::config::cmd_admin_update_admin_mint_cap(&program, &token_type, cap)?
+            }
+            ConfigCommands::InitFeeVault => {
+                println!("[ADMIN] Initializing fee vault...");
+                admin::config::cmd_admin_init_fee_vault(&program)?
+            }
+            ConfigCommands::FundFeeVault { lamports } => {
+                admin::config::cmd_fund_fee_vault(&program, lamports)?
+            }
+            ConfigCommands::Faucet {
+                amount,
+                claim_interval,
+            } => {
+                println!("[ADMIN] Configuring faucet...");
+                admin::config::cmd_admin_configure_faucet(&program, amount, claim_interval)?
+            }
+            ConfigCommands::InitOracleCommittee { members, threshold } => {
+                println!("[ADMIN] Initializing oracle committee...");
+                admin::config::cmd_admin_init_oracle_committee(&program, members, threshold)?
+            }
+            ConfigCommands::UpdateOracleCommittee { members, threshold } => {
+                println!("[ADMIN] Updating oracle committee...");
+                admin::config::cmd_admin_update_oracle_committee(&program, members, threshold)?
+            }
             ConfigCommands::SetCluster { cluster } => {
                 println!("[ADMIN] Setting cluster configuration...");
                 admin::config::cmd_admin_set_cluster(cluster)?
@@ -10,7 +10,10 @@ use anyhow::Result;
 use std::rc::Rc;
 use std::str::FromStr;
 
-/// Setup the program client
+/// Setup the program client, signing and paying fees with the keypair at `keypair_path`
+///
+/// Shared by the CLI, the oracle, the keeper and the relayer. `cluster` is `localnet`,
+/// `devnet`, `mainnet` or an RPC URL.
 pub fn setup_client(
     keypair_path: &str,
     cluster: &str,
@@ -22,6 +25,7 @@ pub fn setup_client(
         .map_err(|e| anyhow::anyhow!("Failed to read keypair file: {}", e))?;
 
     let cluster = match cluster {
+        "localnet" => Cluster::Localnet,
         "devnet" => Cluster::Devnet,
         "mainnet" => Cluster::Mainnet,
         url => Cluster::Custom(url.to_string(), url.to_string()),
@@ -29,7 +33,7 @@ pub fn setup_client(
 
     let client = Client::new_with_options(cluster, Rc::new(payer), CommitmentConfig::confirmed());
     let program_id = Pubkey::from_str(program_id)?;
-    let program = client.program(program_id).expect("Failed to load program");
+    let program = client.program(program_id)?;
 
     Ok(program)
 }
@@ -0,0 +1,448 @@
+use anchor_client::solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    system_program,
+};
+use anchor_client::Program;
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use anyhow::Result;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alignment_protocol::{
+    accounts as AccountsAll, instruction as InstructionAll,
+    AiValidationRequest as AiValidationRequestAccount, AiValidationStatus,
+    Delegation as DelegationAccount, State as StateAccount, Submission as SubmissionAccount,
+    SubmissionStatus, SubmissionTopicLink as SubmissionTopicLinkAccount,
+    VoteCommit as VoteCommitAccount,
+};
+
+/// Fixed accounts in a `finalize_votes_batch` transaction
+const BATCH_FIXED_ACCOUNTS: usize = 7;
+/// Accounts per vote in a `finalize_votes_batch` transaction, excluding delegations
+const BATCH_VOTE_ACCOUNTS: usize = 4;
+
+/// Finalizes submissions and votes on links whose reveal phase has ended
+pub struct Keeper {
+    program: Program<Rc<Keypair>>,
+    /// Upper bound on accounts per batch transaction, to stay within the transaction size limit
+    max_batch_accounts: usize,
+}
+
+impl Keeper {
+    pub fn new(program: Program<Rc<Keypair>>, max_batch_accounts: usize) -> Self {
+        Self {
+            program,
+            max_batch_accounts,
+        }
+    }
+
+    /// Run one pass over all links, returning
+    /// (submissions finalized, votes finalized, unrevealed votes settled)
+    pub fn poll_once(&self) -> Result<(usize, usize, usize)> {
+        let now = current_timestamp();
+        let state: StateAccount = self.program.account(self.state_pda())?;
+
+        let links: Vec<(Pubkey, SubmissionTopicLinkAccount)> = self
+            .program
+            .accounts::<SubmissionTopicLinkAccount>(vec![])?
+            .into_iter()
+            .filter(|(_, link)| now > link.reveal_phase_end)
+            .collect();
+
+        let mut submissions = 0;
+        for (link_pda, link) in links.iter() {
+            if link.status != SubmissionStatus::Pending {
+                continue;
+            }
+            match self.finalize_submission(&state, link_pda, link) {
+                Ok(()) => submissions += 1,
+                Err(e) => println!("Failed to finalize link {}: {}", link_pda, e),
+            }
+        }
+
+        let vote_commits = self.program.accounts::<VoteCommitAccount>(vec![])?;
+
+        // Votes never revealed only hold locked tempRep, which can be released once the
+        // reveal phase is over whatever the link's outcome
+        let mut unrevealed = 0;
+        for (vote_pda, vote) in vote_commits.iter() {
+            let link = match links
+                .iter()
+                .find(|(pda, _)| *pda == vote.submission_topic_link)
+            {
+                Some((_, link)) => link,
+                None => continue,
+            };
+            if vote.revealed || vote.finalized {
+                continue;
+            }
+            match self.settle_unrevealed_vote(vote_pda, vote, link) {
+                Ok(()) => unrevealed += 1,
+                Err(e) => println!("Failed to settle unrevealed vote {}: {}", vote_pda, e),
+            }
+        }
+
+        // Revealed votes can be settled once their link has an outcome
+        let mut votes = 0;
+        for (link_pda, link) in links.iter() {
+            let link: SubmissionTopicLinkAccount = if link.status == SubmissionStatus::Pending {
+                match self.program.account(*link_pda) {
+                    Ok(link) => link,
+                    Err(_) => continue,
+                }
+            } else {
+                link.clone()
+            };
+            if link.status == SubmissionStatus::Pending {
+                continue;
+            }
+
+            let pending: Vec<&(Pubkey, VoteCommitAccount)> = vote_commits
+                .iter()
+                .filter(|(_, vote)| {
+                    vote.submission_topic_link == *link_pda && vote.revealed && !vote.finalized
+                })
+                .collect();
+            if pending.is_empty() {
+                continue;
+            }
+            match self.finalize_votes(&state, link_pda, &link, &pending) {
+                Ok(count) => votes += count,
+                Err(e) => println!("Failed to finalize votes on link {}: {}", link_pda, e),
+            }
+        }
+
+        Ok((submissions, votes, unrevealed))
+    }
+
+    /// Finalize a link, passing every completed AI request so its verdict is counted
+    fn finalize_submission(
+        &self,
+        state: &StateAccount,
+        link_pda: &Pubkey,
+        link: &SubmissionTopicLinkAccount,
+    ) -> Result<()> {
+        let submission: SubmissionAccount = self.program.account(link.submission)?;
+        let contributor = submission.contributor;
+        let contributor_align_ata = token_ata(&contributor, &state.align_mint);
+
+        let ai_request_metas: Vec<AccountMeta> = self
+            .program
+            .accounts::<AiValidationRequestAccount>(vec![])?
+            .into_iter()
+            .filter(|(_, r)| {
+                r.submission_topic_link == *link_pda && r.status == AiValidationStatus::Completed
+            })
+            .map(|(pubkey, _)| AccountMeta::new_readonly(pubkey, false))
+            .collect();
+
+        let mut request = self.program.request();
+        if let Some(ix) = self.create_ata_if_missing(&contributor, &state.align_mint) {
+            request = request.instruction(ix);
+        }
+        let tx_sig = request
+            .accounts(AccountsAll::FinalizeSubmission {
+                authority: self.program.payer(),
+                state: self.state_pda(),
+                topic: link.topic,
+                submission: link.submission,
+                submission_topic_link: *link_pda,
+                contributor_profile: self.pda(&[b"user_profile", contributor.as_ref()]),
+                contributor_temp_align_account: self
+                    .pda(&[b"user_temp_align", contributor.as_ref()]),
+                contributor_align_ata,
+                user_topic_balance: self.pda(&[
+                    b"user_topic_balance",
+                    contributor.as_ref(),
+                    link.topic.as_ref(),
+                ]),
+                temp_align_mint: state.temp_align_mint,
+                align_mint: state.align_mint,
+                fee_vault: self.fee_vault(),
+                token_program: anchor_spl::token_2022::ID,
+                system_program: system_program::ID,
+            })
+            .accounts(ai_request_metas)
+            .args(InstructionAll::FinalizeSubmission {})
+            .send()?;
+
+        println!("Finalized link {} (txSig: {})", link_pda, tx_sig);
+        Ok(())
+    }
+
+    /// Release the tempRep locked by a vote that was never revealed, returning the drawn
+    /// delegations to their delegators
+    fn settle_unrevealed_vote(
+        &self,
+        vote_pda: &Pubkey,
+        vote: &VoteCommitAccount,
+        link: &SubmissionTopicLinkAccount,
+    ) -> Result<()> {
+        let delegation_metas: Vec<AccountMeta> = vote
+            .delegations
+            .iter()
+            .map(|stake| AccountMeta::new(stake.delegation, false))
+            .collect();
+        let tx_sig = self
+            .program
+            .request()
+            .accounts(AccountsAll::SettleUnrevealedVote {
+                submission_topic_link: vote.submission_topic_link,
+                vote_commit: *vote_pda,
+                user_topic_balance: self.pda(&[
+                    b"user_topic_balance",
+                    vote.validator.as_ref(),
+                    link.topic.as_ref(),
+                ]),
+                authority: self.program.payer(),
+            })
+            .accounts(delegation_metas)
+            .args(InstructionAll::SettleUnrevealedVote {})
+            .send()?;
+
+        println!("Settled unrevealed vote {} (txSig: {})", vote_pda, tx_sig);
+        Ok(())
+    }
+
+    /// Settle revealed votes on a finalized link in as few transactions as fit
+    fn finalize_votes(
+        &self,
+        state: &StateAccount,
+        link_pda: &Pubkey,
+        link: &SubmissionTopicLinkAccount,
+        votes: &[&(Pubkey, VoteCommitAccount)],
+    ) -> Result<usize> {
+        let mut vote_groups = Vec::with_capacity(votes.len());
+        for (vote_pda, vote) in votes.iter().map(|v| (&v.0, &v.1)) {
+            vote_groups.push(self.vote_metas(state, link, vote_pda, vote)?);
+        }
+        let account_counts: Vec<usize> = vote_groups.iter().map(|(metas, _)| metas.len()).collect();
+
+        let mut finalized = 0;
+        let mut vote_groups = vote_groups.into_iter();
+        for batch_votes in batch_sizes(&account_counts, self.max_batch_accounts) {
+            let (batch, ata_ixs): (Vec<_>, Vec<_>) = vote_groups.by_ref().take(batch_votes).unzip();
+            self.send_batch(
+                state,
+                link_pda,
+                link,
+                ata_ixs.into_iter().flatten().collect(),
+                batch.into_iter().flatten().collect(),
+            )?;
+            finalized += batch_votes;
+        }
+
+        Ok(finalized)
+    }
+
+    /// Remaining accounts for one vote: the vote group followed by its delegation groups,
+    /// along with the creation of any Rep ATA the settlement will mint to that does not
+    /// exist yet
+    fn vote_metas(
+        &self,
+        state: &StateAccount,
+        link: &SubmissionTopicLinkAccount,
+        vote_pda: &Pubkey,
+        vote: &VoteCommitAccount,
+    ) -> Result<(Vec<AccountMeta>, Vec<Instruction>)> {
+        let validator = vote.validator;
+        let mut ata_ixs = Vec::new();
+        ata_ixs.extend(self.create_ata_if_missing(&validator, &state.rep_mint));
+        let mut metas = Vec::with_capacity(BATCH_VOTE_ACCOUNTS + vote.delegations.len() * 4);
+        metas.extend([
+            AccountMeta::new(*vote_pda, false),
+            AccountMeta::new(
+                self.pda(&[
+                    b"user_topic_balance",
+                    validator.as_ref(),
+                    link.topic.as_ref(),
+                ]),
+                false,
+            ),
+            AccountMeta::new(self.pda(&[b"user_temp_rep", validator.as_ref()]), false),
+            AccountMeta::new(token_ata(&validator, &state.rep_mint), false),
+        ]);
+        for stake in vote.delegations.iter() {
+            let delegation: DelegationAccount = self.program.account(stake.delegation)?;
+            let delegator = delegation.delegator;
+            ata_ixs.extend(self.create_ata_if_missing(&delegator, &state.rep_mint));
+            metas.push(AccountMeta::new(stake.delegation, false));
+            metas.push(AccountMeta::new(
+                self.pda(&[b"user_temp_rep", delegator.as_ref()]),
+                false,
+            ));
+            metas.push(AccountMeta::new(
+                token_ata(&delegator, &state.rep_mint),
+                false,
+            ));
+            metas.push(AccountMeta::new(
+                self.pda(&[
+                    b"user_topic_balance",
+                    delegator.as_ref(),
+                    link.topic.as_ref(),
+                ]),
+                false,
+            ));
+        }
+        Ok((metas, ata_ixs))
+    }
+
+    fn send_batch(
+        &self,
+        state: &StateAccount,
+        link_pda: &Pubkey,
+        link: &SubmissionTopicLinkAccount,
+        ata_ixs: Vec<Instruction>,
+        batch: Vec<AccountMeta>,
+    ) -> Result<()> {
+        let mut request = self.program.request();
+        for ix in ata_ixs {
+            request = request.instruction(ix);
+        }
+        let tx_sig = request
+            .accounts(AccountsAll::FinalizeVotesBatch {
+                state: self.state_pda(),
+                submission_topic_link: *link_pda,
+                topic: link.topic,
+                temp_rep_mint: state.temp_rep_mint,
+                rep_mint: state.rep_mint,
+                authority: self.program.payer(),
+                fee_vault: self.fee_vault(),
+                token_program: anchor_spl::token_2022::ID,
+            })
+            .accounts(batch)
+            .args(InstructionAll::FinalizeVotesBatch {})
+            .send()?;
+
+        println!("Finalized votes on link {} (txSig: {})", link_pda, tx_sig);
+        Ok(())
+    }
+
+    /// Rewards are minted to ATAs, so create any missing one at the keeper's expense
+    fn create_ata_if_missing(&self, owner: &Pubkey, mint: &Pubkey) -> Option<Instruction> {
+        let ata = token_ata(owner, mint);
+        if self.program.rpc().get_account(&ata).is_ok() {
+            return None;
+        }
+        Some(create_associated_token_account_idempotent(
+            &self.program.payer(),
+            owner,
+            mint,
+            &anchor_spl::token_2022::ID,
+        ))
+    }
+
+    /// The fee vault, if created, so the keeper collects the finalization reward
+    fn fee_vault(&self) -> Option<Pubkey> {
+        let fee_vault = self.pda(&[b"fee_vault"]);
+        self.program
+            .rpc()
+            .get_account(&fee_vault)
+            .ok()
+            .map(|_| fee_vault)
+    }
+
+    fn pda(&self, seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &self.program.id()).0
+    }
+
+    fn state_pda(&self) -> Pubkey {
+        self.pda(&[b"state"])
+    }
+}
+
+/// Split votes, given the remaining accounts each one needs, into consecutive batches that
+/// fit `max_batch_accounts` alongside the fixed accounts, returning the votes per batch
+///
+/// Votes are never split across batches, so a vote too large to fit on its own still gets
+/// a batch of its own.
+fn batch_sizes(account_counts: &[usize], max_batch_accounts: usize) -> Vec<usize> {
+    let mut batches = Vec::new();
+    let mut batch_accounts = 0;
+    let mut batch_votes = 0;
+    for &accounts in account_counts {
+        if batch_votes > 0 && BATCH_FIXED_ACCOUNTS + batch_accounts + accounts > max_batch_accounts
+        {
+            batches.push(batch_votes);
+            batch_accounts = 0;
+            batch_votes = 0;
+        }
+        batch_accounts += accounts;
+        batch_votes += 1;
+    }
+    if batch_votes > 0 {
+        batches.push(batch_votes);
+    }
+    batches
+}
+
+/// Protocol mints are Token-2022 mints, so their ATAs are derived under that program
+fn token_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &anchor_spl::token_2022::ID)
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A vote with `delegations` delegations needs 4 accounts for itself and 4 per delegation
+    fn vote(delegations: usize) -> usize {
+        BATCH_VOTE_ACCOUNTS + delegations * 4
+    }
+
+    #[test]
+    fn no_votes_need_no_batches() {
+        assert!(batch_sizes(&[], 30).is_empty());
+    }
+
+    #[test]
+    fn votes_fill_a_batch_up_to_the_limit() {
+        // 7 fixed + 5 * 4 = 27 fits under 30, a sixth vote would make 31
+        assert_eq!(batch_sizes(&[vote(0); 6], 30), vec![5, 1]);
+        // Exactly reaching the limit is fine
+        assert_eq!(
+            batch_sizes(&[vote(0); 6], BATCH_FIXED_ACCOUNTS + 6 * vote(0)),
+            vec![6]
+        );
+    }
+
+    #[test]
+    fn delegations_count_towards_the_limit() {
+        // 7 + 12 = 19, then 7 + 12 + 12 = 31 > 30
+        assert_eq!(batch_sizes(&[vote(2), vote(2), vote(0)], 30), vec![1, 2]);
+    }
+
+    #[test]
+    fn oversized_votes_get_a_batch_of_their_own() {
+        assert_eq!(
+            batch_sizes(&[vote(0), vote(10), vote(0)], 30),
+            vec![1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn batches_keep_vote_order() {
+        let counts = [vote(0), vote(1), vote(3), vote(0), vote(0)];
+        let sizes = batch_sizes(&counts, 30);
+        assert_eq!(sizes.iter().sum::<usize>(), counts.len());
+
+        let mut start = 0;
+        for size in sizes {
+            let accounts: usize = counts[start..start + size].iter().sum();
+            assert!(size == 1 || BATCH_FIXED_ACCOUNTS + accounts <= 30);
+            start += size;
+        }
+    }
+}
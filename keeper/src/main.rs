@@ -0,0 +1,62 @@
+mod keeper;
+
+use anyhow::Result;
+use clap::Parser;
+use std::thread;
+use std::time::Duration;
+
+use keeper::Keeper;
+
+/// Permissionless keeper: finalizes submissions and votes once reveal phases end
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Cli {
+    /// Path to the keeper's keypair file (pays transaction fees)
+    #[arg(long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Solana cluster (localnet, devnet, mainnet or custom URL)
+    #[arg(long, default_value = "localnet")]
+    cluster: String,
+
+    /// Program ID for the Alignment Protocol
+    #[arg(long, default_value = "FtHfXYCwuVEb8LVkNwNMmqMVooyg2fxkoT8i9bkEcvKW")]
+    program_id: String,
+
+    /// Seconds to wait between passes
+    #[arg(long, default_value_t = 30)]
+    poll_interval: u64,
+
+    /// Run a single pass and exit
+    #[arg(long)]
+    once: bool,
+
+    /// Maximum accounts per vote batch transaction
+    #[arg(long, default_value_t = 30)]
+    max_batch_accounts: usize,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let program = alignment_client::setup_client(&cli.keypair, &cli.cluster, &cli.program_id)?;
+    println!("Keeper {} watching {}", program.payer(), program.id());
+
+    let keeper = Keeper::new(program, cli.max_batch_accounts);
+
+    loop {
+        match keeper.poll_once() {
+            Ok((0, 0, 0)) => {}
+            Ok((submissions, votes, unrevealed)) => println!(
+                "Finalized {} submission(s) and {} vote(s), settled {} unrevealed vote(s)",
+                submissions, votes, unrevealed
+            ),
+            Err(e) if cli.once => return Err(e),
+            Err(e) => println!("Pass failed: {}", e),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(cli.poll_interval));
+    }
+}
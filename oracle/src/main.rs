@@ -0,0 +1,81 @@
+mod resolver;
+mod scorer;
+mod service;
+
+use alignment_protocol::VoteChoice;
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use std::thread;
+use std::time::Duration;
+
+use resolver::LocalResolver;
+use scorer::StubScorer;
+use service::OracleService;
+
+/// Reference off-chain oracle: answers AI validation requests with a pluggable model
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Cli {
+    /// Path to the oracle's keypair file
+    #[arg(long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Solana cluster (localnet, devnet, mainnet or custom URL)
+    #[arg(long, default_value = "localnet")]
+    cluster: String,
+
+    /// Program ID for the Alignment Protocol
+    #[arg(long, default_value = "FtHfXYCwuVEb8LVkNwNMmqMVooyg2fxkoT8i9bkEcvKW")]
+    program_id: String,
+
+    /// Seconds to wait between polls
+    #[arg(long, default_value_t = 5)]
+    poll_interval: u64,
+
+    /// Process the currently open requests once and exit
+    #[arg(long)]
+    once: bool,
+
+    /// Make the stub model always return this decision
+    #[arg(long, value_enum)]
+    stub_decision: Option<StubDecision>,
+
+    /// Minimum content length the stub model accepts
+    #[arg(long, default_value_t = 8)]
+    stub_min_length: usize,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StubDecision {
+    Yes,
+    No,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let program = alignment_client::setup_client(&cli.keypair, &cli.cluster, &cli.program_id)?;
+    println!("Oracle {} watching {}", program.payer(), program.id());
+
+    let scorer = StubScorer {
+        fixed_decision: cli.stub_decision.map(|decision| match decision {
+            StubDecision::Yes => VoteChoice::Yes,
+            StubDecision::No => VoteChoice::No,
+        }),
+        min_length: cli.stub_min_length,
+    };
+    let service = OracleService::new(program, Box::new(LocalResolver), Box::new(scorer));
+
+    loop {
+        match service.poll_once() {
+            Ok(answered) if answered > 0 => println!("Answered {} AI request(s)", answered),
+            Ok(_) => {}
+            Err(e) if cli.once => return Err(e),
+            Err(e) => println!("Poll failed: {}", e),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(cli.poll_interval));
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+
+/// Fetches the content behind a submission's `data_reference`
+pub trait DataResolver {
+    fn resolve(&self, data_reference: &str) -> Result<String>;
+}
+
+/// Resolver for local testing
+///
+/// `file://<path>` references are read from disk. References without a scheme are
+/// treated as the content itself. Any other scheme (e.g. `ipfs://`) is rejected so the
+/// request is marked as failed and the requester's stake refunded.
+pub struct LocalResolver;
+
+impl DataResolver for LocalResolver {
+    fn resolve(&self, data_reference: &str) -> Result<String> {
+        if let Some(path) = data_reference.strip_prefix("file://") {
+            return fs::read_to_string(path).map_err(|e| anyhow!("Could not read {}: {}", path, e));
+        }
+        if let Some((scheme, _)) = data_reference.split_once("://") {
+            return Err(anyhow!("Unsupported data reference scheme: {}", scheme));
+        }
+        Ok(data_reference.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_references_are_the_content() {
+        assert_eq!(
+            LocalResolver.resolve("A short proof").unwrap(),
+            "A short proof"
+        );
+    }
+
+    #[test]
+    fn file_references_are_read_from_disk() {
+        let path = std::env::temp_dir().join(format!("oracle-resolver-{}", std::process::id()));
+        fs::write(&path, "On disk").unwrap();
+        let content = LocalResolver.resolve(&format!("file://{}", path.display()));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(content.unwrap(), "On disk");
+
+        assert!(LocalResolver
+            .resolve("file:///definitely/not/here")
+            .is_err());
+    }
+
+    #[test]
+    fn other_schemes_are_rejected() {
+        let err = LocalResolver.resolve("ipfs://Qm123").unwrap_err();
+        assert_eq!(err.to_string(), "Unsupported data reference scheme: ipfs");
+    }
+}
@@ -0,0 +1,113 @@
+use alignment_protocol::VoteChoice;
+use anyhow::Result;
+
+/// Everything a model gets to see when judging a submission
+pub struct ScoringInput<'a> {
+    /// Name of the topic the submission is linked to
+    pub topic_name: &'a str,
+    /// The submission's on-chain data reference
+    pub data_reference: &'a str,
+    /// The content the reference resolved to
+    pub content: &'a str,
+}
+
+/// A model's judgement on a submission
+pub struct Verdict {
+    pub decision: VoteChoice,
+    /// Confidence in the decision, in basis points (0-10000)
+    pub confidence_bps: u16,
+    /// Human-readable explanation; only its hash goes on-chain
+    pub rationale: String,
+}
+
+/// Judges whether a submission should be accepted in its topic
+pub trait ModelScorer {
+    /// Identifier of the model and prompt version, hashed into each vote's provenance
+    fn model_id(&self) -> &str;
+
+    fn score(&self, input: &ScoringInput) -> Result<Verdict>;
+}
+
+/// Deterministic stand-in for a real model, for local testing
+///
+/// Returns `fixed_decision` with full confidence when set, otherwise votes Yes for content
+/// with at least `min_length` non-whitespace characters, at half confidence.
+pub struct StubScorer {
+    pub fixed_decision: Option<VoteChoice>,
+    pub min_length: usize,
+}
+
+impl ModelScorer for StubScorer {
+    fn model_id(&self) -> &str {
+        "stub-scorer/v1"
+    }
+
+    fn score(&self, input: &ScoringInput) -> Result<Verdict> {
+        println!(
+            "Stub model scoring {} in topic '{}'",
+            input.data_reference, input.topic_name
+        );
+        if let Some(decision) = self.fixed_decision {
+            return Ok(Verdict {
+                decision,
+                confidence_bps: 10_000,
+                rationale: format!("Fixed stub decision {:?}", decision),
+            });
+        }
+        let length = input.content.chars().filter(|c| !c.is_whitespace()).count();
+        let decision = if length >= self.min_length {
+            VoteChoice::Yes
+        } else {
+            VoteChoice::No
+        };
+        Ok(Verdict {
+            decision,
+            confidence_bps: 5_000,
+            rationale: format!(
+                "Content has {} non-whitespace characters (minimum {})",
+                length, self.min_length
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(scorer: &StubScorer, content: &str) -> Verdict {
+        scorer
+            .score(&ScoringInput {
+                topic_name: "Topic",
+                data_reference: "ref",
+                content,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn fixed_decision_wins_with_full_confidence() {
+        let scorer = StubScorer {
+            fixed_decision: Some(VoteChoice::No),
+            min_length: 0,
+        };
+        let verdict = score(&scorer, "long enough content");
+        assert_eq!(verdict.decision, VoteChoice::No);
+        assert_eq!(verdict.confidence_bps, 10_000);
+    }
+
+    #[test]
+    fn length_threshold_ignores_whitespace() {
+        let scorer = StubScorer {
+            fixed_decision: None,
+            min_length: 4,
+        };
+        let verdict = score(&scorer, "a b c d");
+        assert_eq!(verdict.decision, VoteChoice::Yes);
+        assert_eq!(verdict.confidence_bps, 5_000);
+
+        let verdict = score(&scorer, " a b  c ");
+        assert_eq!(verdict.decision, VoteChoice::No);
+        assert_eq!(verdict.confidence_bps, 5_000);
+    }
+}
@@ -0,0 +1,540 @@
+use anchor_client::solana_sdk::{hash::hash, pubkey::Pubkey, signature::Keypair};
+use anchor_client::Program;
+use anyhow::{anyhow, Result};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alignment_protocol::{
+    accounts as AccountsAll, instruction as InstructionAll,
+    AiValidationRequest as AiValidationRequestAccount, AiValidationStatus,
+    OracleCommittee as OracleCommitteeAccount, State as StateAccount,
+    Submission as SubmissionAccount, SubmissionTopicLink as SubmissionTopicLinkAccount,
+    Topic as TopicAccount, TopicRole as TopicRoleAccount, TOPIC_ROLE_ORACLE,
+};
+
+use crate::resolver::DataResolver;
+use crate::scorer::{ModelScorer, ScoringInput, Verdict};
+
+/// How the oracle keypair is allowed to answer a given request
+#[derive(Debug, PartialEq)]
+enum Authority {
+    /// Is the oracle assigned to the request's topic (or the global one), or holds the
+    /// topic's oracle role, in which case its TopicRole PDA is passed along
+    Oracle { topic_role: Option<Pubkey> },
+    /// Is a member of the committee deciding the request's topic
+    CommitteeMember { committee: Pubkey },
+}
+
+impl Authority {
+    /// Only the oracle moves requests to Processing or Failed; committee members just attest
+    fn can_mark(&self) -> bool {
+        matches!(self, Authority::Oracle { .. })
+    }
+
+    /// Whether to claim a request with the given status before answering it
+    fn should_mark_processing(&self, status: &AiValidationStatus) -> bool {
+        self.can_mark() && *status == AiValidationStatus::Pending
+    }
+}
+
+/// Polls pending AI validation requests and answers them on-chain
+pub struct OracleService {
+    program: Program<Rc<Keypair>>,
+    resolver: Box<dyn DataResolver>,
+    scorer: Box<dyn ModelScorer>,
+}
+
+impl OracleService {
+    pub fn new(
+        program: Program<Rc<Keypair>>,
+        resolver: Box<dyn DataResolver>,
+        scorer: Box<dyn ModelScorer>,
+    ) -> Self {
+        Self {
+            program,
+            resolver,
+            scorer,
+        }
+    }
+
+    /// Process every open request the oracle can answer, returning how many were answered
+    pub fn poll_once(&self) -> Result<usize> {
+        let oracle = self.program.payer();
+        let (state_pda, _) = Pubkey::find_program_address(&[b"state"], &self.program.id());
+        let state: StateAccount = self.program.account(state_pda)?;
+
+        let requests = self
+            .program
+            .accounts::<AiValidationRequestAccount>(vec![])?
+            .into_iter()
+            .filter(|(_, req)| is_open_for(req, &oracle));
+
+        let mut answered = 0;
+        for (request_pda, request) in requests {
+            let link: SubmissionTopicLinkAccount =
+                self.program.account(request.submission_topic_link)?;
+            if !voting_open(link.reveal_phase_end, current_timestamp()) {
+                // Too late to vote; anyone can expire it after AI_REQUEST_TIMEOUT
+                continue;
+            }
+
+            let topic: TopicAccount = self.program.account(link.topic)?;
+            let authority = match self.authority_for(&state, &link.topic, &topic) {
+                Some(authority) => authority,
+                None => continue,
+            };
+
+            match self.process_request(request_pda, &request, &link, &topic, &authority) {
+                Ok(()) => answered += 1,
+                Err(e) => println!("Failed to process AI request {}: {}", request_pda, e),
+            }
+        }
+
+        Ok(answered)
+    }
+
+    /// Work out how (if at all) the oracle keypair may answer requests in a topic
+    fn authority_for(
+        &self,
+        state: &StateAccount,
+        topic_pda: &Pubkey,
+        topic: &TopicAccount,
+    ) -> Option<Authority> {
+        authority_for(
+            &self.program.id(),
+            &self.program.payer(),
+            state,
+            topic_pda,
+            topic,
+            |role_pda| {
+                self.program
+                    .account::<TopicRoleAccount>(role_pda)
+                    .ok()
+                    .map(|role| role.roles)
+            },
+            |committee| {
+                self.program
+                    .account::<OracleCommitteeAccount>(committee)
+                    .ok()
+                    .map(|committee| committee.members)
+            },
+        )
+    }
+
+    /// Resolve, score and answer a single request
+    fn process_request(
+        &self,
+        request_pda: Pubkey,
+        request: &AiValidationRequestAccount,
+        link: &SubmissionTopicLinkAccount,
+        topic: &TopicAccount,
+        authority: &Authority,
+    ) -> Result<()> {
+        let topic_role = match authority {
+            Authority::Oracle { topic_role } => *topic_role,
+            Authority::CommitteeMember { .. } => None,
+        };
+        // Claim the request so other instances and users can see it is being worked on
+        if authority.should_mark_processing(&request.status) {
+            self.program
+                .request()
+                .accounts(AccountsAll::MarkAiProcessing {
+                    oracle: self.program.payer(),
+                    state: self.state_pda(),
+                    ai_validation_request: request_pda,
+                    submission_topic_link: request.submission_topic_link,
+                    topic_role,
+                    topic: link.topic,
+                })
+                .args(InstructionAll::MarkAiProcessing {})
+                .send()?;
+        }
+
+        let verdict = match self.evaluate(link, topic) {
+            Ok(verdict) => verdict,
+            Err(e) => {
+                println!("Could not evaluate AI request {}: {}", request_pda, e);
+                if authority.can_mark() {
+                    self.mark_failed(request_pda, request, link, topic_role)?;
+                }
+                return Err(e);
+            }
+        };
+        let decision = verdict.decision;
+        println!(
+            "Model {} decided {:?} ({} bps): {}",
+            self.scorer.model_id(),
+            decision,
+            verdict.confidence_bps,
+            verdict.rationale
+        );
+
+        let tx_sig = match authority {
+            Authority::CommitteeMember { committee } => self
+                .program
+                .request()
+                .accounts(AccountsAll::SubmitOracleAttestation {
+                    oracle: self.program.payer(),
+                    oracle_committee: *committee,
+                    topic: link.topic,
+                    ai_validation_request: request_pda,
+                    submission_topic_link: request.submission_topic_link,
+                })
+                .args(InstructionAll::SubmitOracleAttestation {
+                    ai_request_index: request.request_index,
+                    decision,
+                })
+                .send()?,
+            Authority::Oracle { .. } => self
+                .program
+                .request()
+                .accounts(AccountsAll::SubmitAiVote {
+                    oracle: self.program.payer(),
+                    state: self.state_pda(),
+                    ai_validation_request: request_pda,
+                    submission_topic_link: request.submission_topic_link,
+                    topic_role,
+                    topic: link.topic,
+                })
+                .args(InstructionAll::SubmitAiVote {
+                    ai_request_index: request.request_index,
+                    ai_decision: decision,
+                    model_hash: hash(self.scorer.model_id().as_bytes()).to_bytes(),
+                    rationale_hash: hash(verdict.rationale.as_bytes()).to_bytes(),
+                    confidence_bps: verdict.confidence_bps,
+                })
+                .send()?,
+        };
+
+        println!(
+            "Answered AI request {} with {:?} (txSig: {})",
+            request_pda, decision, tx_sig
+        );
+        Ok(())
+    }
+
+    /// Fetch the submission's content and run it through the scorer
+    fn evaluate(&self, link: &SubmissionTopicLinkAccount, topic: &TopicAccount) -> Result<Verdict> {
+        let submission: SubmissionAccount = self.program.account(link.submission)?;
+        let content = self
+            .resolver
+            .resolve(&submission.data_reference)
+            .map_err(|e| anyhow!("Resolving {}: {}", submission.data_reference, e))?;
+
+        self.scorer.score(&ScoringInput {
+            topic_name: &topic.name,
+            data_reference: &submission.data_reference,
+            content: &content,
+        })
+    }
+
+    /// Give up on a request, refunding the requester's stake
+    fn mark_failed(
+        &self,
+        request_pda: Pubkey,
+        request: &AiValidationRequestAccount,
+        link: &SubmissionTopicLinkAccount,
+        topic_role: Option<Pubkey>,
+    ) -> Result<()> {
+        let (user_balance_pda, _) = Pubkey::find_program_address(
+            &[
+                b"user_topic_balance",
+                request.requester.as_ref(),
+                link.topic.as_ref(),
+            ],
+            &self.program.id(),
+        );
+        let tx_sig = self
+            .program
+            .request()
+            .accounts(AccountsAll::MarkAiFailed {
+                oracle: self.program.payer(),
+                state: self.state_pda(),
+                ai_validation_request: request_pda,
+                submission_topic_link: request.submission_topic_link,
+                user_topic_balance: user_balance_pda,
+                topic_role,
+                topic: link.topic,
+            })
+            .args(InstructionAll::MarkAiFailed {})
+            .send()?;
+        println!(
+            "Marked AI request {} as failed (txSig: {})",
+            request_pda, tx_sig
+        );
+        Ok(())
+    }
+
+    fn state_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"state"], &self.program.id()).0
+    }
+}
+
+/// Whether a request still needs an answer from `oracle`
+///
+/// Committee members answer each request once, so requests they attested are skipped.
+fn is_open_for(request: &AiValidationRequestAccount, oracle: &Pubkey) -> bool {
+    matches!(
+        request.status,
+        AiValidationStatus::Pending | AiValidationStatus::Processing
+    ) && !request.attestations.iter().any(|a| a.oracle == *oracle)
+}
+
+/// Whether AI votes are still accepted on a link at `now`
+///
+/// Past the reveal phase it is too late to vote; anyone can expire the request after
+/// AI_REQUEST_TIMEOUT instead.
+fn voting_open(reveal_phase_end: u64, now: u64) -> bool {
+    now <= reveal_phase_end
+}
+
+/// Work out how (if at all) `oracle` may answer requests in a topic, mirroring the
+/// program's rules: a committee that applies to the topic (its own, or the global one when
+/// the topic has no oracle) overrides everything, then the topic role, then the topic's
+/// oracle, then the global oracle
+///
+/// `topic_roles` loads the role bitmask of a TopicRole PDA and `committee_members` the
+/// members of an OracleCommittee PDA, returning None for accounts that don't exist.
+fn authority_for(
+    program_id: &Pubkey,
+    oracle: &Pubkey,
+    state: &StateAccount,
+    topic_pda: &Pubkey,
+    topic: &TopicAccount,
+    topic_roles: impl Fn(Pubkey) -> Option<u8>,
+    committee_members: impl Fn(Pubkey) -> Option<Vec<Pubkey>>,
+) -> Option<Authority> {
+    let committee_member_of = |committee: Pubkey| {
+        committee_members(committee)?
+            .contains(oracle)
+            .then_some(Authority::CommitteeMember { committee })
+    };
+    if topic.oracle_committee_active {
+        let (committee, _) =
+            Pubkey::find_program_address(&[b"oracle_committee", topic_pda.as_ref()], program_id);
+        return committee_member_of(committee);
+    }
+    if topic.oracle_pubkey.is_none() && state.oracle_committee_active {
+        let (committee, _) = Pubkey::find_program_address(&[b"oracle_committee"], program_id);
+        return committee_member_of(committee);
+    }
+
+    let (topic_role_pda, _) = Pubkey::find_program_address(
+        &[b"topic_role", topic_pda.as_ref(), oracle.as_ref()],
+        program_id,
+    );
+    if topic_roles(topic_role_pda).is_some_and(|roles| roles & TOPIC_ROLE_ORACLE != 0) {
+        return Some(Authority::Oracle {
+            topic_role: Some(topic_role_pda),
+        });
+    }
+
+    let assigned_oracle = topic.oracle_pubkey.unwrap_or(state.oracle_pubkey);
+    (assigned_oracle == *oracle).then_some(Authority::Oracle { topic_role: None })
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alignment_protocol::{OracleAttestation, VoteChoice, TOPIC_ROLE_MODERATOR};
+    use anchor_client::anchor_lang::AnchorDeserialize;
+
+    /// An account with every field zeroed (None, empty, false, first variant)
+    fn zeroed<T: AnchorDeserialize>() -> T {
+        T::deserialize(&mut &[0u8; 1024][..]).unwrap()
+    }
+
+    struct Fixture {
+        program_id: Pubkey,
+        oracle: Pubkey,
+        topic_pda: Pubkey,
+        state: StateAccount,
+        topic: TopicAccount,
+    }
+
+    impl Fixture {
+        /// `oracle` is the global oracle and the topic has no overrides
+        fn new() -> Self {
+            let oracle = Pubkey::new_unique();
+            let mut state: StateAccount = zeroed();
+            state.oracle_pubkey = oracle;
+            Self {
+                program_id: alignment_protocol::ID,
+                oracle,
+                topic_pda: Pubkey::new_unique(),
+                state,
+                topic: zeroed(),
+            }
+        }
+
+        fn topic_role_pda(&self) -> Pubkey {
+            Pubkey::find_program_address(
+                &[b"topic_role", self.topic_pda.as_ref(), self.oracle.as_ref()],
+                &self.program_id,
+            )
+            .0
+        }
+
+        fn topic_committee(&self) -> Pubkey {
+            Pubkey::find_program_address(
+                &[b"oracle_committee", self.topic_pda.as_ref()],
+                &self.program_id,
+            )
+            .0
+        }
+
+        fn global_committee(&self) -> Pubkey {
+            Pubkey::find_program_address(&[b"oracle_committee"], &self.program_id).0
+        }
+
+        /// Resolve the authority with an oracle-role holder and a committee whose only
+        /// member is the oracle, each present only when given
+        fn authority(&self, roles: Option<u8>, committee: Option<Pubkey>) -> Option<Authority> {
+            let role_pda = self.topic_role_pda();
+            authority_for(
+                &self.program_id,
+                &self.oracle,
+                &self.state,
+                &self.topic_pda,
+                &self.topic,
+                |pda| if pda == role_pda { roles } else { None },
+                |pda| (Some(pda) == committee).then(|| vec![self.oracle]),
+            )
+        }
+    }
+
+    fn request(status: AiValidationStatus) -> AiValidationRequestAccount {
+        let mut request: AiValidationRequestAccount = zeroed();
+        request.status = status;
+        request
+    }
+
+    #[test]
+    fn global_oracle_answers_topics_without_overrides() {
+        let fixture = Fixture::new();
+        assert_eq!(
+            fixture.authority(None, None),
+            Some(Authority::Oracle { topic_role: None })
+        );
+    }
+
+    #[test]
+    fn topic_oracle_overrides_global_oracle() {
+        let mut fixture = Fixture::new();
+        fixture.topic.oracle_pubkey = Some(Pubkey::new_unique());
+        assert_eq!(fixture.authority(None, None), None);
+
+        fixture.topic.oracle_pubkey = Some(fixture.oracle);
+        fixture.state.oracle_pubkey = Pubkey::new_unique();
+        assert_eq!(
+            fixture.authority(None, None),
+            Some(Authority::Oracle { topic_role: None })
+        );
+    }
+
+    #[test]
+    fn topic_role_grants_oracle_authority() {
+        let mut fixture = Fixture::new();
+        fixture.state.oracle_pubkey = Pubkey::new_unique();
+        assert_eq!(
+            fixture.authority(Some(TOPIC_ROLE_ORACLE), None),
+            Some(Authority::Oracle {
+                topic_role: Some(fixture.topic_role_pda())
+            })
+        );
+        // Other roles don't count
+        assert_eq!(fixture.authority(Some(TOPIC_ROLE_MODERATOR), None), None);
+    }
+
+    #[test]
+    fn topic_committee_overrides_topic_role() {
+        let mut fixture = Fixture::new();
+        fixture.topic.oracle_committee_active = true;
+        let committee = fixture.topic_committee();
+        assert_eq!(
+            fixture.authority(Some(TOPIC_ROLE_ORACLE), Some(committee)),
+            Some(Authority::CommitteeMember { committee })
+        );
+        // Holding the role is not enough when the oracle isn't on the committee
+        assert_eq!(fixture.authority(Some(TOPIC_ROLE_ORACLE), None), None);
+    }
+
+    #[test]
+    fn global_committee_applies_only_to_topics_without_an_oracle() {
+        let mut fixture = Fixture::new();
+        fixture.state.oracle_committee_active = true;
+        let committee = fixture.global_committee();
+        assert_eq!(
+            fixture.authority(Some(TOPIC_ROLE_ORACLE), Some(committee)),
+            Some(Authority::CommitteeMember { committee })
+        );
+
+        fixture.topic.oracle_pubkey = Some(fixture.oracle);
+        assert_eq!(
+            fixture.authority(None, Some(committee)),
+            Some(Authority::Oracle { topic_role: None })
+        );
+    }
+
+    #[test]
+    fn only_pending_and_processing_requests_are_open() {
+        let oracle = Pubkey::new_unique();
+        assert!(is_open_for(&request(AiValidationStatus::Pending), &oracle));
+        assert!(is_open_for(
+            &request(AiValidationStatus::Processing),
+            &oracle
+        ));
+        for status in [
+            AiValidationStatus::Completed,
+            AiValidationStatus::Failed,
+            AiValidationStatus::Expired,
+        ] {
+            assert!(!is_open_for(&request(status), &oracle));
+        }
+    }
+
+    #[test]
+    fn attested_requests_are_not_answered_again() {
+        let oracle = Pubkey::new_unique();
+        let mut request = request(AiValidationStatus::Processing);
+        request.attestations.push(OracleAttestation {
+            oracle: Pubkey::new_unique(),
+            decision: VoteChoice::Yes,
+        });
+        assert!(is_open_for(&request, &oracle));
+
+        request.attestations.push(OracleAttestation {
+            oracle,
+            decision: VoteChoice::No,
+        });
+        assert!(!is_open_for(&request, &oracle));
+    }
+
+    #[test]
+    fn voting_closes_after_the_reveal_phase() {
+        assert!(voting_open(100, 99));
+        assert!(voting_open(100, 100));
+        assert!(!voting_open(100, 101));
+    }
+
+    #[test]
+    fn only_the_oracle_marks_requests() {
+        let oracle = Authority::Oracle { topic_role: None };
+        let member = Authority::CommitteeMember {
+            committee: Pubkey::new_unique(),
+        };
+        assert!(oracle.can_mark());
+        assert!(!member.can_mark());
+
+        assert!(oracle.should_mark_processing(&AiValidationStatus::Pending));
+        assert!(!oracle.should_mark_processing(&AiValidationStatus::Processing));
+        assert!(!member.should_mark_processing(&AiValidationStatus::Pending));
+    }
+}
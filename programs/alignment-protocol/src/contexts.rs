@@ -2,8 +2,9 @@ use crate::data::*;
 use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 // Removed legacy context structures
@@ -38,6 +39,23 @@ pub struct CreateTopic<'info> {
                 8 + // commit_phase_duration
                 8 + // reveal_phase_duration
                 1 + // is_active
+                (1 + 8) + // tokens_to_mint (option)
+                (1 + 2) + // rep_mint_ratio_bps (option)
+                (1 + 8) + // emission_budget_per_epoch (option)
+                8 + // emission_epoch_duration
+                8 + // emission_epoch_start
+                8 + // emission_epoch_minted
+                8 + // min_rep_balance
+                8 + // min_accepted_submissions
+                (1 + 32) + // parent_topic (option)
+                8 + // min_parent_topic_rep
+                (1 + 8) + // max_submissions_per_window (option)
+                8 + // submission_window_duration
+                (1 + 8) + // max_pending_submissions (option)
+                2 + // ai_weight_bps
+                1 + // ai_confidence_scaling
+                (1 + 32) + // oracle_pubkey (option)
+                1 + // oracle_committee_active
                 1   // bump
     )]
     pub topic: Account<'info, Topic>,
@@ -65,8 +83,15 @@ pub struct UpdateTopic<'info> {
     #[account(mut)]
     pub topic: Account<'info, Topic>,
 
-    /// Signer must be either the global protocol authority or the topic creator
+    /// Signer must be the global protocol authority, the topic creator or a topic moderator
     pub authority: Signer<'info>,
+
+    /// The signer's TopicRole for this topic, if they are acting as a moderator
+    #[account(
+        seeds = [b"topic_role", topic.key().as_ref(), authority.key().as_ref()],
+        bump = topic_role.bump
+    )]
+    pub topic_role: Option<Account<'info, TopicRole>>,
 }
 
 /// Account constraints for submitting data to a specific topic
@@ -84,7 +109,7 @@ pub struct SubmitDataToTopic<'info> {
         mut,
         constraint = temp_align_mint.key() == state.temp_align_mint @ ErrorCode::TokenMintMismatch
     )]
-    pub temp_align_mint: Account<'info, Mint>,
+    pub temp_align_mint: InterfaceAccount<'info, Mint>,
 
     /// The signer covering the rent for new PDAs (payer)
     #[account(mut)]
@@ -98,7 +123,7 @@ pub struct SubmitDataToTopic<'info> {
         constraint = contributor_temp_align_account.mint == temp_align_mint.key() @ ErrorCode::TokenMintMismatch,
         constraint = contributor_temp_align_account.owner == state.key() @ ErrorCode::InvalidTokenAccount
     )]
-    pub contributor_temp_align_account: Account<'info, TokenAccount>,
+    pub contributor_temp_align_account: InterfaceAccount<'info, TokenAccount>,
 
     /// The new Submission account - Seeds now use user key + user counter index
     #[account(
@@ -110,8 +135,9 @@ pub struct SubmitDataToTopic<'info> {
             current_submission_index.to_le_bytes().as_ref(),
         ],
         bump,
-        // Discriminator + contributor pubkey + timestamp + data field + submission PDA bump
-        space = 8 + 32 + 8 + (4 + MAX_DATA_REFERENCE_LENGTH) + 1
+        // Discriminator + contributor pubkey + timestamp + data field + tempAlign minted +
+        // submission PDA bump
+        space = 8 + 32 + 8 + (4 + MAX_DATA_REFERENCE_LENGTH) + 8 + 1
     )]
     pub submission: Account<'info, Submission>,
 
@@ -125,8 +151,9 @@ pub struct SubmitDataToTopic<'info> {
             topic.key().as_ref(),
         ],
         bump,
-        // Discriminator + submission pubkey + topic pubkey + status + phase timestamps + vote counts + committed/revealed counts + bump
-        space = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1
+        // Discriminator + submission pubkey + topic pubkey + status + phase timestamps + vote counts + committed/revealed counts
+        // + revealed tempRep per side + reward pool/claimed + completed AI requests + bump
+        space = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1
     )]
     pub submission_topic_link: Account<'info, SubmissionTopicLink>,
 
@@ -140,7 +167,8 @@ pub struct SubmitDataToTopic<'info> {
     pub contributor_profile: Box<Account<'info, UserProfile>>,
 
     /// The UserTopicBalance account for this contributor and topic.
-    /// MUST be initialized separately via `initialize_user_topic_balance` first.
+    /// Not created here: the CLI and relayer prepend `initialize_user_topic_balance` to the
+    /// same transaction when it does not exist yet.
     #[account(
         mut,
         seeds = [b"user_topic_balance", contributor.key().as_ref(), topic.key().as_ref()],
@@ -150,13 +178,33 @@ pub struct SubmitDataToTopic<'info> {
     )]
     pub user_topic_balance: Account<'info, UserTopicBalance>,
 
-    /// The user whose submission this is (does not need to sign; the payer covers fees)
+    /// The user whose submission this is (signs as `acting_signer` or through a session key)
     pub contributor: SystemAccount<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// The contributor or one of their session keys. `submit_data_to_topic_meta` passes the
+    /// relayer, since the contributor's payload signature authorizes it instead.
+    pub acting_signer: Signer<'info>,
+
+    /// Required unless `acting_signer` is the contributor
+    #[account(
+        seeds = [b"session_key", contributor.key().as_ref(), acting_signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 
     pub system_program: Program<'info, System>,
+
+    /// The contributor's permanent Rep ATA, required if the topic sets `min_rep_balance`
+    #[account(
+        constraint = contributor_rep_ata.mint == state.rep_mint @ ErrorCode::TokenMintMismatch,
+        constraint = contributor_rep_ata.key() == contributor_profile.user_rep_ata @ ErrorCode::InvalidTokenAccount
+    )]
+    pub contributor_rep_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The contributor's balance in the topic's parent topic, required if the topic sets one
+    pub parent_topic_balance: Option<Box<Account<'info, UserTopicBalance>>>,
 }
 
 /// Account constraints for linking an existing submission to a topic
@@ -184,8 +232,9 @@ pub struct LinkSubmissionToTopic<'info> {
             topic.key().as_ref(),
         ],
         bump,
-        // Discriminator + submission pubkey + topic pubkey + status + phase timestamps + vote counts + committed/revealed counts + bump
-        space = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1
+        // Discriminator + submission pubkey + topic pubkey + status + phase timestamps + vote counts + committed/revealed counts
+        // + revealed tempRep per side + reward pool/claimed + completed AI requests + bump
+        space = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1
     )]
     pub submission_topic_link: Account<'info, SubmissionTopicLink>,
 
@@ -229,8 +278,9 @@ pub struct CommitVote<'info> {
         ],
         bump,
         // Discriminator + submission_topic_link pubkey + validator pubkey + vote_hash + revealed + finalized + 
-        // vote_choice (option) + commit_timestamp + temp_rep_amount + perm_rep_amount + bump
-        space = 8 + 32 + 32 + 32 + 1 + 1 + (1 + 1) + 8 + 8 + 8 + 1
+        // vote_choice (option) + commit_timestamp + temp_rep_amount + perm_rep_amount +
+        // delegated_temp_rep_amount + delegations (vec of pubkey + u64) + bump
+        space = 8 + 32 + 32 + 32 + 1 + 1 + (1 + 1) + 8 + 8 + 8 + 8 + (4 + MAX_DELEGATIONS_PER_VOTE * (32 + 8)) + 1
     )]
     pub vote_commit: Account<'info, VoteCommit>,
 
@@ -243,7 +293,8 @@ pub struct CommitVote<'info> {
     pub user_profile: Account<'info, UserProfile>,
 
     /// Validator's topic-specific balance account for this topic.
-    /// MUST be initialized first. Only used if is_permanent_rep is false.
+    /// Not created here: the CLI and relayer prepend `initialize_user_topic_balance` to the
+    /// same transaction when it does not exist yet.
     #[account(
         mut,
         seeds = [b"user_topic_balance", validator.key().as_ref(), topic.key().as_ref()],
@@ -263,17 +314,31 @@ pub struct CommitVote<'info> {
         // Ensure ATA corresponds to the profile's stored ATA key
         constraint = validator_rep_ata.key() == user_profile.user_rep_ata @ ErrorCode::InvalidTokenAccount
     )]
-    pub validator_rep_ata: Account<'info, TokenAccount>,
+    pub validator_rep_ata: InterfaceAccount<'info, TokenAccount>,
 
-    /// The account committing the vote (does not pay fees)
+    /// The account committing the vote (signs as `acting_signer` or through a session key)
     pub validator: SystemAccount<'info>,
 
+    /// The validator or one of their session keys. `commit_vote_meta` passes the relayer,
+    /// since the validator's payload signature authorizes it instead.
+    pub acting_signer: Signer<'info>,
+
+    /// Required unless `acting_signer` is the validator
+    #[account(
+        seeds = [b"session_key", validator.key().as_ref(), acting_signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
     /// The payer covering transaction fees and rent. Signs the transaction.
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// The validator's balance in the topic's parent topic, required if the topic sets one
+    pub parent_topic_balance: Option<Account<'info, UserTopicBalance>>,
 }
 
 /// Account constraints for revealing a previously committed vote
@@ -310,9 +375,20 @@ pub struct RevealVote<'info> {
     )]
     pub vote_commit: Account<'info, VoteCommit>,
 
-    /// The original voter (readonly, no signature required)
+    /// The original voter (signs as `acting_signer` or through a session key)
     pub validator: SystemAccount<'info>,
 
+    /// The validator or one of their session keys. `reveal_vote_meta` passes the relayer,
+    /// since the validator's payload signature authorizes it instead.
+    pub acting_signer: Signer<'info>,
+
+    /// Required unless `acting_signer` is the validator
+    #[account(
+        seeds = [b"session_key", validator.key().as_ref(), acting_signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
     /// The payer covering transaction fees (signer)
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -328,14 +404,22 @@ pub struct SetVotingPhases<'info> {
     #[account(mut, constraint = submission_topic_link.status == SubmissionStatus::Pending)]
     pub submission_topic_link: Account<'info, SubmissionTopicLink>,
 
+    #[account(constraint = topic.key() == submission_topic_link.topic @ ErrorCode::InvalidTopic)]
     pub topic: Account<'info, Topic>,
 
     pub submission: Account<'info, Submission>,
 
-    /// Only authority can modify phases
-    #[account(mut, constraint = state.authority == authority.key())]
+    /// The protocol authority, the topic creator, or a topic moderator / phase manager
+    #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// The signer's TopicRole for this topic, if they are acting as a role holder
+    #[account(
+        seeds = [b"topic_role", topic.key().as_ref(), authority.key().as_ref()],
+        bump = topic_role.bump
+    )]
+    pub topic_role: Option<Account<'info, TopicRole>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -362,8 +446,9 @@ pub struct FinalizeSubmission<'info> {
     )]
     pub submission: Account<'info, Submission>,
 
-    /// The contributor's user profile (needed for token account constraints)
+    /// The contributor's user profile (token account constraints and accepted submission count)
     #[account(
+        mut,
         seeds = [b"user_profile", submission.contributor.as_ref()],
         bump = contributor_profile.bump,
         constraint = contributor_profile.user == submission.contributor @ ErrorCode::UserAccountMismatch
@@ -390,7 +475,7 @@ pub struct FinalizeSubmission<'info> {
         constraint = contributor_temp_align_account.owner == state.key() @ ErrorCode::InvalidTokenAccount,
         constraint = contributor_temp_align_account.key() == contributor_profile.user_temp_align_account @ ErrorCode::InvalidTokenAccount
     )]
-    pub contributor_temp_align_account: Account<'info, TokenAccount>,
+    pub contributor_temp_align_account: InterfaceAccount<'info, TokenAccount>,
 
     /// The contributor's ATA for permanent alignment tokens (regular user-owned ATA)
     #[account(
@@ -399,28 +484,31 @@ pub struct FinalizeSubmission<'info> {
         constraint = contributor_align_ata.owner == submission.contributor @ ErrorCode::UserAccountMismatch,
         constraint = contributor_align_ata.key() == contributor_profile.user_align_ata @ ErrorCode::InvalidTokenAccount
     )]
-    pub contributor_align_ata: Account<'info, TokenAccount>,
+    pub contributor_align_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// The tempAlign mint (for burning)
     #[account(
         mut,
         constraint = temp_align_mint.key() == state.temp_align_mint @ ErrorCode::TokenMintMismatch
     )]
-    pub temp_align_mint: Account<'info, Mint>,
+    pub temp_align_mint: InterfaceAccount<'info, Mint>,
 
     /// The Align mint (for minting)
     #[account(
         mut,
         constraint = align_mint.key() == state.align_mint @ ErrorCode::TokenMintMismatch
     )]
-    pub align_mint: Account<'info, Mint>,
+    pub align_mint: InterfaceAccount<'info, Mint>,
 
     /// The authority calling this instruction (can be any user, acts as payer)
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// Pays the finalization reward to `authority` when passed
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 
     pub system_program: Program<'info, System>,
 }
@@ -429,9 +517,9 @@ pub struct FinalizeSubmission<'info> {
 ///
 /// Note: This design allows anyone to call finalize_vote, not just the validator themselves.
 /// This ensures validators receive rewards or penalties even if they don't explicitly claim them.
-/// Future enhancements could include:
-/// 1. Batch processing multiple vote finalizations in a single transaction for efficiency
-/// 2. An escrow-based approach where tokens are locked during voting and auto-converted after
+/// Several votes can be finalized in one transaction with `finalize_votes_batch`.
+/// A future enhancement could be an escrow-based approach where tokens are locked during
+/// voting and auto-converted after.
 #[derive(Accounts)]
 pub struct FinalizeVote<'info> {
     #[account(seeds = [b"state"], bump)]
@@ -443,6 +531,7 @@ pub struct FinalizeVote<'info> {
     )]
     pub submission_topic_link: Account<'info, SubmissionTopicLink>,
 
+    #[account(constraint = topic.key() == submission_topic_link.topic @ ErrorCode::InvalidTopic)]
     pub topic: Account<'info, Topic>,
 
     pub submission: Account<'info, Submission>,
@@ -483,7 +572,7 @@ pub struct FinalizeVote<'info> {
         constraint = validator_temp_rep_account.mint == state.temp_rep_mint,
         constraint = validator_temp_rep_account.owner == state.key()
     )]
-    pub validator_temp_rep_account: Account<'info, TokenAccount>,
+    pub validator_temp_rep_account: InterfaceAccount<'info, TokenAccount>,
 
     /// The validator's ATA for permanent reputation tokens (for minting)
     /// This remains user-owned since permanent tokens belong to users
@@ -492,32 +581,110 @@ pub struct FinalizeVote<'info> {
         constraint = validator_rep_ata.mint == state.rep_mint,
         constraint = validator_rep_ata.owner == validator_profile.user
     )]
-    pub validator_rep_ata: Account<'info, TokenAccount>,
+    pub validator_rep_ata: InterfaceAccount<'info, TokenAccount>,
 
     /// The tempRep mint (for burning)
     #[account(
         mut,
         constraint = temp_rep_mint.key() == state.temp_rep_mint
     )]
-    pub temp_rep_mint: Account<'info, Mint>,
+    pub temp_rep_mint: InterfaceAccount<'info, Mint>,
 
     /// The Rep mint (for minting)
     #[account(
         mut,
         constraint = rep_mint.key() == state.rep_mint
     )]
-    pub rep_mint: Account<'info, Mint>,
+    pub rep_mint: InterfaceAccount<'info, Mint>,
 
     /// The signer finalizing the vote (can be anyone, not just the validator)
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// Pays the finalization reward to `authority` when passed
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 
     pub system_program: Program<'info, System>,
 }
 
+/// Account constraints for finalizing many revealed votes on one link in a single transaction
+///
+/// Each vote is passed through remaining accounts as a
+/// (vote_commit, user_topic_balance, validator_temp_rep_account, validator_rep_ata) group,
+/// followed by a (delegation, delegator tempRep account, delegator Rep ATA, delegator topic
+/// balance) group for each delegation drawn at commit. Anyone can call it, e.g. a keeper bot.
+#[derive(Accounts)]
+pub struct FinalizeVotesBatch<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        constraint = submission_topic_link.status != SubmissionStatus::Pending,
+    )]
+    pub submission_topic_link: Account<'info, SubmissionTopicLink>,
+
+    #[account(constraint = topic.key() == submission_topic_link.topic @ ErrorCode::InvalidTopic)]
+    pub topic: Account<'info, Topic>,
+
+    /// The tempRep mint (for burning)
+    #[account(
+        mut,
+        constraint = temp_rep_mint.key() == state.temp_rep_mint
+    )]
+    pub temp_rep_mint: InterfaceAccount<'info, Mint>,
+
+    /// The Rep mint (for minting)
+    #[account(
+        mut,
+        constraint = rep_mint.key() == state.rep_mint
+    )]
+    pub rep_mint: InterfaceAccount<'info, Mint>,
+
+    /// The signer finalizing the votes (can be anyone)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Pays the finalization reward for each vote to `authority` when passed
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Account constraints for releasing the tempRep locked in a vote that was never revealed
+///
+/// The delegations drawn at commit are passed as remaining accounts. Anyone can call it once
+/// the reveal phase has ended, e.g. a keeper bot.
+#[derive(Accounts)]
+pub struct SettleUnrevealedVote<'info> {
+    pub submission_topic_link: Account<'info, SubmissionTopicLink>,
+
+    #[account(
+        mut,
+        constraint = vote_commit.submission_topic_link == submission_topic_link.key()
+    )]
+    pub vote_commit: Account<'info, VoteCommit>,
+
+    /// The validator's topic balance, holding the vote's own locked tempRep
+    #[account(
+        mut,
+        seeds = [
+            b"user_topic_balance",
+            vote_commit.validator.as_ref(),
+            submission_topic_link.topic.as_ref(),
+        ],
+        bump = user_topic_balance.bump,
+    )]
+    pub user_topic_balance: Account<'info, UserTopicBalance>,
+
+    /// The signer settling the vote (can be anyone)
+    pub authority: Signer<'info>,
+}
+
 /// Instruction: Initialize the protocol state (Part 1)
 ///
 /// 1) Creates the `State` account (PDA with seeds=["state"]).
@@ -530,7 +697,7 @@ pub struct InitializeState<'info> {
         seeds = [b"state"],
         bump,
         payer = authority,
-        space = 8 + (32 * 6) + 1 + (8 * 4) // Updated space (233 bytes) for 6 pubkeys, 1 bump, 4 u64s
+        space = 8 + (32 * 6) + 1 + (8 * 7) + 2 + 1 + (8 * 4) + (8 * 4) // 324 bytes: 6 pubkeys, 1 bump, 7 u64s, 1 u16, 1 bool, 2 [u64; 4] admin mint ledgers
     )]
     pub state: Account<'info, State>,
 
@@ -550,22 +717,14 @@ pub struct InitializeTempAlignMint<'info> {
     #[account(mut, seeds = [b"state"], bump)]
     pub state: Account<'info, State>,
 
-    #[account(
-        init,
-        seeds = [b"temp_align_mint"],
-        bump,
-        payer = authority,
-        mint::decimals = 0,
-        mint::authority = state.key(), // The state PDA is the mint authority
-        mint::freeze_authority = state.key()
-    )]
-    pub temp_align_mint: Account<'info, Mint>,
+    /// CHECK: Created by the handler as a non-transferable Token-2022 mint
+    #[account(mut, seeds = [b"temp_align_mint"], bump)]
+    pub temp_align_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Program<'info, Token2022>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -574,7 +733,8 @@ pub struct InitializeTempAlignMint<'info> {
 /// Instruction: Initialize permanent alignment token mint
 ///
 /// Creates the align_mint token mint with seeds=["align_mint"]
-/// This is a transferable permanent alignment token
+/// This is a transferable permanent alignment token. It is a plain Token-2022 mint so all
+/// four protocol mints share one token program.
 #[derive(Accounts)]
 pub struct InitializeAlignMint<'info> {
     #[account(mut, seeds = [b"state"], bump)]
@@ -587,15 +747,16 @@ pub struct InitializeAlignMint<'info> {
         payer = authority,
         mint::decimals = 0,
         mint::authority = state.key(),
-        mint::freeze_authority = state.key()
+        mint::freeze_authority = state.key(),
+        extensions::metadata_pointer::authority = state.key(),
+        extensions::metadata_pointer::metadata_address = align_mint.key()
     )]
-    pub align_mint: Account<'info, Mint>,
+    pub align_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Program<'info, Token2022>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -610,22 +771,14 @@ pub struct InitializeTempRepMint<'info> {
     #[account(mut, seeds = [b"state"], bump)]
     pub state: Account<'info, State>,
 
-    #[account(
-        init,
-        seeds = [b"temp_rep_mint"],
-        bump,
-        payer = authority,
-        mint::decimals = 0,
-        mint::authority = state.key(),
-        mint::freeze_authority = state.key()
-    )]
-    pub temp_rep_mint: Account<'info, Mint>,
+    /// CHECK: Created by the handler as a non-transferable Token-2022 mint
+    #[account(mut, seeds = [b"temp_rep_mint"], bump)]
+    pub temp_rep_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Program<'info, Token2022>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -640,22 +793,14 @@ pub struct InitializeRepMint<'info> {
     #[account(mut, seeds = [b"state"], bump)]
     pub state: Account<'info, State>,
 
-    #[account(
-        init,
-        seeds = [b"rep_mint"],
-        bump,
-        payer = authority,
-        mint::decimals = 0,
-        mint::authority = state.key(),
-        mint::freeze_authority = state.key()
-    )]
-    pub rep_mint: Account<'info, Mint>,
+    /// CHECK: Created by the handler as a non-transferable Token-2022 mint
+    #[account(mut, seeds = [b"rep_mint"], bump)]
+    pub rep_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Program<'info, Token2022>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -674,6 +819,79 @@ pub struct UpdateTokensToMint<'info> {
     pub authority: Signer<'info>,
 }
 
+/// Instruction: Update the Rep emitted into each link's validator reward pool
+///
+/// 1) Updates the `validator_reward_emission` field in the `State` account.
+/// 2) Requires the authority to sign.
+#[derive(Accounts)]
+pub struct UpdateValidatorRewardEmission<'info> {
+    #[account(mut, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Instruction: Update the delegation commission
+///
+/// 1) Updates the `delegation_commission_bps` field in the `State` account.
+/// 2) Requires the authority to sign.
+#[derive(Accounts)]
+pub struct UpdateDelegationCommission<'info> {
+    #[account(mut, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Instruction: Update the finalization reward
+///
+/// 1) Updates the `finalization_reward` and `finalization_reward_min_voting_power` fields in
+///    the `State` account.
+/// 2) Requires the authority to sign.
+#[derive(Accounts)]
+pub struct UpdateFinalizationReward<'info> {
+    #[account(mut, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Instruction: Create the fee vault that pays finalization rewards (authority only)
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"fee_vault"],
+        bump,
+        space = 8 + 1 // Discriminator + bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction: Deposit lamports into the fee vault (anyone)
+#[derive(Accounts)]
+pub struct FundFeeVault<'info> {
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct CreateUserAta<'info> {
     /// The state account containing all mint references
@@ -702,7 +920,7 @@ pub struct CreateUserAta<'info> {
         *mint.to_account_info().key == state.rep_mint
             @ ErrorCode::TokenMintMismatch // Added error code for clarity
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// The Associated Token Account (will be created if it doesn't exist)
     /// We do not use `init_if_needed`; we do a CPI call to the ATA program explicitly below.
@@ -712,7 +930,7 @@ pub struct CreateUserAta<'info> {
 
     /// Programs
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -745,7 +963,7 @@ pub struct CreateUserTempAlignAccount<'info> {
         *mint.to_account_info().key == state.temp_align_mint
             @ ErrorCode::TokenMintMismatch // Added error code for clarity
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// The token account will be a PDA owned by the program
     /// With the state as the authority, not the user
@@ -757,10 +975,10 @@ pub struct CreateUserTempAlignAccount<'info> {
         seeds = [b"user_temp_align", user.key().as_ref()],
         bump
     )]
-    pub token_account: Account<'info, TokenAccount>,
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -792,7 +1010,7 @@ pub struct CreateUserTempRepAccount<'info> {
         *mint.to_account_info().key == state.temp_rep_mint
             @ ErrorCode::TokenMintMismatch // Added error code for clarity
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// The token account will be a PDA owned by the program
     /// With the state as the authority, not the user
@@ -804,10 +1022,10 @@ pub struct CreateUserTempRepAccount<'info> {
         seeds = [b"user_temp_rep", user.key().as_ref()],
         bump
     )]
-    pub token_account: Account<'info, TokenAccount>,
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -823,7 +1041,7 @@ pub struct CreateUserProfile<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 8 + (32 * 4) + 1, // discriminator + UserProfile fields
+        space = UserProfile::SPACE,
         seeds = [b"user_profile", user.key().as_ref()],
         bump
     )]
@@ -865,7 +1083,7 @@ pub struct InitializeUserTopicBalance<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 32 + 8 + 8 + 8 + 1, // Space: Discriminator + user + topic + 3*u64 + bump
+        space = 8 + 32 + 32 + (9 * 8) + 1, // Space: Discriminator + user + topic + 9*u64 + bump
         seeds = [b"user_topic_balance", user.key().as_ref(), topic.key().as_ref()],
         bump,
     )]
@@ -873,6 +1091,15 @@ pub struct InitializeUserTopicBalance<'info> {
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// The user's permanent Rep ATA, required if the topic sets `min_rep_balance`
+    #[account(
+        constraint = user_rep_ata.key() == user_profile.user_rep_ata @ ErrorCode::InvalidTokenAccount
+    )]
+    pub user_rep_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user's balance in the topic's parent topic, required if the topic sets one
+    pub parent_topic_balance: Option<Account<'info, UserTopicBalance>>,
 }
 
 /// Account constraints for staking temporary alignment tokens for a specific topic
@@ -895,7 +1122,8 @@ pub struct StakeTopicSpecificTokens<'info> {
     pub user_profile: Account<'info, UserProfile>,
 
     /// The user's topic-specific balance account for this topic.
-    /// MUST be initialized separately via `initialize_user_topic_balance` first.
+    /// Not created here: the CLI and relayer prepend `initialize_user_topic_balance` to the
+    /// same transaction when it does not exist yet.
     #[account(
         mut,
         seeds = [b"user_topic_balance", user.key().as_ref(), topic.key().as_ref()],
@@ -913,7 +1141,7 @@ pub struct StakeTopicSpecificTokens<'info> {
         seeds = [b"temp_align_mint"],
         bump,
     )]
-    pub temp_align_mint: Account<'info, Mint>,
+    pub temp_align_mint: InterfaceAccount<'info, Mint>,
 
     /// The temporary reputation token mint (target tokens to mint)
     #[account(
@@ -921,7 +1149,7 @@ pub struct StakeTopicSpecificTokens<'info> {
         seeds = [b"temp_rep_mint"],
         bump,
     )]
-    pub temp_rep_mint: Account<'info, Mint>,
+    pub temp_rep_mint: InterfaceAccount<'info, Mint>,
 
     /// The protocol-owned tempAlign token account for this user (source for burn)
     #[account(
@@ -932,7 +1160,7 @@ pub struct StakeTopicSpecificTokens<'info> {
         constraint = user_temp_align_account.mint == temp_align_mint.key() @ ErrorCode::TokenMintMismatch,
         constraint = user_temp_align_account.owner == state.key() @ ErrorCode::InvalidTokenAccount
     )]
-    pub user_temp_align_account: Account<'info, TokenAccount>,
+    pub user_temp_align_account: InterfaceAccount<'info, TokenAccount>,
 
     /// The protocol-owned tempRep token account for this user (target for mint)
     #[account(
@@ -943,17 +1171,21 @@ pub struct StakeTopicSpecificTokens<'info> {
         constraint = user_temp_rep_account.mint == temp_rep_mint.key() @ ErrorCode::TokenMintMismatch,
         constraint = user_temp_rep_account.owner == state.key() @ ErrorCode::InvalidTokenAccount
     )]
-    pub user_temp_rep_account: Account<'info, TokenAccount>,
+    pub user_temp_rep_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// The user for whom tokens are being staked (no signature required).
+    /// The user for whom tokens are being staked (signs as `acting_signer`)
     pub user: SystemAccount<'info>,
 
+    /// The user. `stake_topic_specific_tokens_meta` passes the relayer, since the user's
+    /// payload signature authorizes it instead.
+    pub acting_signer: Signer<'info>,
+
     /// The signer paying for transaction fees.
     #[account(mut)]
     pub payer: Signer<'info>,
 
     /// Token program for CPI calls
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 // --- NEW CONTEXTS FOR AI VALIDATION ---
@@ -975,13 +1207,12 @@ pub struct RequestAiValidation<'info> {
 
     /// The link between the submission and the topic
     #[account(
-        mut,
         seeds = [b"submission_topic_link", submission.key().as_ref(), topic.key().as_ref()],
         bump = submission_topic_link.bump,
     )]
     pub submission_topic_link: Account<'info, SubmissionTopicLink>,
 
-    /// User's balance account for this specific topic (to deduct tempRep)
+    /// User's balance account for this specific topic (to deduct tempRep and count requests)
     #[account(
         mut,
         seeds = [b"user_topic_balance", requester.key().as_ref(), topic.key().as_ref()],
@@ -1003,12 +1234,18 @@ pub struct RequestAiValidation<'info> {
                  1 + // status: AiValidationStatus (enum discriminator)
                  2 + // ai_decision: Option<VoteChoice> (option + enum discriminators)
                  8 + // ai_voting_power: u64
+                32 + // model_hash: [u8; 32]
+                32 + // rationale_hash: [u8; 32]
+                 2 + // confidence_bps: u16
                  8 + // request_index: u64 (the index used for PDA derivation)
+                 (4 + MAX_ORACLE_COMMITTEE_SIZE * (32 + 1)) + // attestations: Vec<OracleAttestation>
+                 1 + // disputed: bool
                  1 , // bump: u8
-                // TOTAL = 108 bytes
+                // TOTAL = 174 bytes + attestations + disputed
         seeds = [
             b"ai_request",
             submission_topic_link.key().as_ref(),
+            requester.key().as_ref(),
             expected_ai_request_index.to_le_bytes().as_ref()
         ],
         bump
@@ -1036,6 +1273,7 @@ pub struct SubmitAiVote<'info> {
         seeds = [
             b"ai_request",
             submission_topic_link.key().as_ref(),
+            ai_validation_request.requester.as_ref(),
             ai_request_index.to_le_bytes().as_ref() // Use the passed index
         ],
         bump, // Specify bump for Anchor to derive the PDA address using canonical bump
@@ -1049,8 +1287,760 @@ pub struct SubmitAiVote<'info> {
         // Constraint: Ensure link matches request (checked in instruction logic)
     )]
     pub submission_topic_link: Account<'info, SubmissionTopicLink>,
-    // Optional: Include Topic if needed for context or validation rules in future
-    // #[account(constraint = submission_topic_link.topic == topic.key())]
-    // pub topic: Account<'info, Topic>,
+
+    /// The oracle's TopicRole for the link's topic, if it is a topic-scoped oracle
+    #[account(
+        seeds = [b"topic_role", submission_topic_link.topic.as_ref(), oracle.key().as_ref()],
+        bump = topic_role.bump
+    )]
+    pub topic_role: Option<Account<'info, TopicRole>>,
+
+    /// The link's topic, which may assign its own oracle
+    #[account(constraint = topic.key() == submission_topic_link.topic @ ErrorCode::InvalidTopic)]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Account constraints for the oracle marking an AI request as being processed
+#[derive(Accounts)]
+pub struct MarkAiProcessing<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(seeds = [b"state"], bump = state.bump)]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        constraint = ai_validation_request.submission_topic_link == submission_topic_link.key() @ ErrorCode::MismatchedAiRequestLink
+    )]
+    pub ai_validation_request: Account<'info, AiValidationRequest>,
+
+    pub submission_topic_link: Account<'info, SubmissionTopicLink>,
+
+    /// The oracle's TopicRole for the link's topic, if it is a topic-scoped oracle
+    #[account(
+        seeds = [b"topic_role", submission_topic_link.topic.as_ref(), oracle.key().as_ref()],
+        bump = topic_role.bump
+    )]
+    pub topic_role: Option<Account<'info, TopicRole>>,
+
+    /// The link's topic, which may assign its own oracle
+    #[account(constraint = topic.key() == submission_topic_link.topic @ ErrorCode::InvalidTopic)]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Account constraints for the oracle marking an AI request as failed (refunds the stake)
+#[derive(Accounts)]
+pub struct MarkAiFailed<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(seeds = [b"state"], bump = state.bump)]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        constraint = ai_validation_request.submission_topic_link == submission_topic_link.key() @ ErrorCode::MismatchedAiRequestLink
+    )]
+    pub ai_validation_request: Account<'info, AiValidationRequest>,
+
+    pub submission_topic_link: Account<'info, SubmissionTopicLink>,
+
+    /// The requester's balance in the link's topic, credited with the refunded stake
+    #[account(
+        mut,
+        seeds = [b"user_topic_balance", ai_validation_request.requester.as_ref(), submission_topic_link.topic.as_ref()],
+        bump = user_topic_balance.bump
+    )]
+    pub user_topic_balance: Account<'info, UserTopicBalance>,
+
+    /// The oracle's TopicRole for the link's topic, if it is a topic-scoped oracle
+    #[account(
+        seeds = [b"topic_role", submission_topic_link.topic.as_ref(), oracle.key().as_ref()],
+        bump = topic_role.bump
+    )]
+    pub topic_role: Option<Account<'info, TopicRole>>,
+
+    /// The link's topic, which may assign its own oracle
+    #[account(constraint = topic.key() == submission_topic_link.topic @ ErrorCode::InvalidTopic)]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Account constraints for expiring an unanswered AI request (permissionless, refunds the stake)
+#[derive(Accounts)]
+pub struct ExpireAiRequest<'info> {
+    #[account(
+        mut,
+        constraint = ai_validation_request.submission_topic_link == submission_topic_link.key() @ ErrorCode::MismatchedAiRequestLink
+    )]
+    pub ai_validation_request: Account<'info, AiValidationRequest>,
+
+    pub submission_topic_link: Account<'info, SubmissionTopicLink>,
+
+    /// The requester's balance in the link's topic, credited with the refunded stake
+    #[account(
+        mut,
+        seeds = [b"user_topic_balance", ai_validation_request.requester.as_ref(), submission_topic_link.topic.as_ref()],
+        bump = user_topic_balance.bump
+    )]
+    pub user_topic_balance: Account<'info, UserTopicBalance>,
+}
+
+/// Account constraints for creating the oracle committee (authority only)
+#[derive(Accounts)]
+pub struct InitializeOracleCommittee<'info> {
+    #[account(mut, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"oracle_committee"],
+        bump,
+        // Discriminator + members (vec of pubkeys) + threshold + bump
+        space = 8 + (4 + MAX_ORACLE_COMMITTEE_SIZE * 32) + 1 + 1
+    )]
+    pub oracle_committee: Account<'info, OracleCommittee>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account constraints for changing the oracle committee (authority only)
+#[derive(Accounts)]
+pub struct UpdateOracleCommittee<'info> {
+    #[account(mut, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut, seeds = [b"oracle_committee"], bump = oracle_committee.bump)]
+    pub oracle_committee: Account<'info, OracleCommittee>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account constraints for creating a topic's own oracle committee (protocol authority only)
+#[derive(Accounts)]
+pub struct InitializeTopicOracleCommittee<'info> {
+    #[account(seeds = [b"state"], bump = state.bump, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut)]
+    pub topic: Account<'info, Topic>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"oracle_committee", topic.key().as_ref()],
+        bump,
+        // Discriminator + members (vec of pubkeys) + threshold + bump
+        space = 8 + (4 + MAX_ORACLE_COMMITTEE_SIZE * 32) + 1 + 1
+    )]
+    pub oracle_committee: Account<'info, OracleCommittee>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account constraints for changing a topic's oracle committee (protocol authority only)
+#[derive(Accounts)]
+pub struct UpdateTopicOracleCommittee<'info> {
+    #[account(seeds = [b"state"], bump = state.bump, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut)]
+    pub topic: Account<'info, Topic>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_committee", topic.key().as_ref()],
+        bump = oracle_committee.bump
+    )]
+    pub oracle_committee: Account<'info, OracleCommittee>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account constraints for an oracle committee member attesting to an AI request
+#[derive(Accounts)]
+#[instruction(ai_request_index: u64)]
+pub struct SubmitOracleAttestation<'info> {
+    pub oracle: Signer<'info>,
+
+    /// The topic's own committee if it has one, otherwise the global committee
+    /// (address checked in instruction logic)
+    pub oracle_committee: Account<'info, OracleCommittee>,
+
+    /// The link's topic, which may assign its own committee
+    #[account(constraint = topic.key() == submission_topic_link.topic @ ErrorCode::InvalidTopic)]
+    pub topic: Account<'info, Topic>,
+
+    /// The AI Request being attested to
+    #[account(
+        mut,
+        seeds = [
+            b"ai_request",
+            submission_topic_link.key().as_ref(),
+            ai_validation_request.requester.as_ref(),
+            ai_request_index.to_le_bytes().as_ref()
+        ],
+        bump = ai_validation_request.bump
+    )]
+    pub ai_validation_request: Account<'info, AiValidationRequest>,
+
+    /// The SubmissionTopicLink being voted on (counts completed requests)
+    #[account(mut)]
+    pub submission_topic_link: Account<'info, SubmissionTopicLink>,
+}
+// --- END OF NEW CONTEXTS ---
+
+/// Account constraints for delegating topic-specific tempRep to a validator
+#[derive(Accounts)]
+pub struct CreateDelegation<'info> {
+    /// The topic the delegated tempRep belongs to
+    pub topic: Account<'info, Topic>,
+
+    /// The delegator's topic-specific balance the tempRep is drawn from
+    #[account(
+        mut,
+        seeds = [b"user_topic_balance", delegator.key().as_ref(), topic.key().as_ref()],
+        bump = user_topic_balance.bump,
+        constraint = user_topic_balance.user == delegator.key() @ ErrorCode::UserAccountMismatch,
+        constraint = user_topic_balance.topic == topic.key() @ ErrorCode::InvalidTopic
+    )]
+    pub user_topic_balance: Account<'info, UserTopicBalance>,
+
+    /// The new Delegation PDA for this (delegator, topic) pair
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"delegation", delegator.key().as_ref(), topic.key().as_ref()],
+        bump,
+        // Discriminator + delegator + validator + topic + amount + locked_amount + bump
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 1
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    /// The validator receiving the delegation (does not need to sign)
+    #[account(
+        seeds = [b"user_profile", validator.key().as_ref()],
+        bump = validator_profile.bump,
+        constraint = validator_profile.user == validator.key() @ ErrorCode::UserAccountMismatch
+    )]
+    pub validator_profile: Account<'info, UserProfile>,
+
+    pub validator: SystemAccount<'info>,
+
+    /// The user delegating their tempRep. Must sign.
+    pub delegator: Signer<'info>,
+
+    /// The signer paying for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Account constraints for adding to or withdrawing from an existing delegation
+#[derive(Accounts)]
+pub struct ModifyDelegation<'info> {
+    /// The topic the delegated tempRep belongs to
+    pub topic: Account<'info, Topic>,
+
+    /// The delegator's topic-specific balance
+    #[account(
+        mut,
+        seeds = [b"user_topic_balance", delegator.key().as_ref(), topic.key().as_ref()],
+        bump = user_topic_balance.bump,
+        constraint = user_topic_balance.user == delegator.key() @ ErrorCode::UserAccountMismatch,
+        constraint = user_topic_balance.topic == topic.key() @ ErrorCode::InvalidTopic
+    )]
+    pub user_topic_balance: Account<'info, UserTopicBalance>,
+
+    /// The delegator's Delegation PDA for this topic
+    #[account(
+        mut,
+        seeds = [b"delegation", delegator.key().as_ref(), topic.key().as_ref()],
+        bump = delegation.bump,
+        constraint = delegation.delegator == delegator.key() @ ErrorCode::UserAccountMismatch
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    /// The user who owns the delegation. Must sign.
+    pub delegator: Signer<'info>,
+}
+
+/// Account constraints for granting topic-scoped roles to a wallet
+#[derive(Accounts)]
+pub struct GrantTopicRole<'info> {
+    /// Global protocol state (for the authority check)
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, State>,
+
+    /// The topic the roles apply to
+    pub topic: Account<'info, Topic>,
+
+    /// The new TopicRole PDA for this (topic, holder) pair
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"topic_role", topic.key().as_ref(), holder.key().as_ref()],
+        bump,
+        // Discriminator + topic + holder + roles + granted_by + bump
+        space = 8 + 32 + 32 + 1 + 32 + 1
+    )]
+    pub topic_role: Account<'info, TopicRole>,
+
+    /// The wallet receiving the roles (does not need to sign)
+    pub holder: SystemAccount<'info>,
+
+    /// Must be the protocol authority or the topic creator. Pays for the account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account constraints for revoking a wallet's topic-scoped roles
+#[derive(Accounts)]
+pub struct RevokeTopicRole<'info> {
+    /// Global protocol state (for the authority check)
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, State>,
+
+    /// The topic the roles apply to
+    pub topic: Account<'info, Topic>,
+
+    /// The TopicRole PDA to close; rent is returned to the revoking signer
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"topic_role", topic.key().as_ref(), topic_role.holder.as_ref()],
+        bump = topic_role.bump
+    )]
+    pub topic_role: Account<'info, TopicRole>,
+
+    /// Must be the protocol authority or the topic creator
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account constraints for creating a user's meta-transaction nonce (anyone can pay)
+#[derive(Accounts)]
+pub struct InitializeMetaNonce<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"meta_nonce", user.key().as_ref()],
+        bump,
+        space = 8 + 32 + 8 + 1 // Discriminator + user + nonce + bump
+    )]
+    pub meta_nonce: Account<'info, MetaNonce>,
+
+    /// The user whose relayed instructions the nonce guards (no signature required)
+    pub user: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Meta-transaction variants. Each wraps the base instruction's accounts and adds the
+// user's nonce PDA plus the instructions sysvar holding the user's ed25519 signature.
+// The relayer is the base context's `payer` and `acting_signer`.
+
+/// Account constraints for `submit_data_to_topic` relayed on the contributor's behalf
+#[derive(Accounts)]
+pub struct SubmitDataToTopicMeta<'info> {
+    pub inner: SubmitDataToTopic<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"meta_nonce", inner.contributor.key().as_ref()],
+        bump = meta_nonce.bump
+    )]
+    pub meta_nonce: Account<'info, MetaNonce>,
+
+    /// CHECK: Address checked; read by `verify_meta_tx`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Account constraints for `stake_topic_specific_tokens` relayed on the user's behalf
+#[derive(Accounts)]
+pub struct StakeTopicSpecificTokensMeta<'info> {
+    pub inner: StakeTopicSpecificTokens<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"meta_nonce", inner.user.key().as_ref()],
+        bump = meta_nonce.bump
+    )]
+    pub meta_nonce: Account<'info, MetaNonce>,
+
+    /// CHECK: Address checked; read by `verify_meta_tx`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Account constraints for `commit_vote` relayed on the validator's behalf
+#[derive(Accounts)]
+pub struct CommitVoteMeta<'info> {
+    pub inner: CommitVote<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"meta_nonce", inner.validator.key().as_ref()],
+        bump = meta_nonce.bump
+    )]
+    pub meta_nonce: Account<'info, MetaNonce>,
+
+    /// CHECK: Address checked; read by `verify_meta_tx`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Account constraints for `reveal_vote` relayed on the validator's behalf
+#[derive(Accounts)]
+pub struct RevealVoteMeta<'info> {
+    pub inner: RevealVote<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"meta_nonce", inner.validator.key().as_ref()],
+        bump = meta_nonce.bump
+    )]
+    pub meta_nonce: Account<'info, MetaNonce>,
+
+    /// CHECK: Address checked; read by `verify_meta_tx`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Account constraints for `request_ai_validation` relayed on the contributor's behalf
+///
+/// Mirrors `RequestAiValidation`, except the requester does not sign and the relayer pays.
+#[derive(Accounts)]
+#[instruction(temp_rep_to_stake: u64, expected_ai_request_index: u64)]
+pub struct RequestAiValidationMeta<'info> {
+    /// The contributor requesting validation (no signature required)
+    pub requester: SystemAccount<'info>,
+
+    /// The relayer paying fees and rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The submission made by the requester
+    pub submission: Account<'info, Submission>,
+
+    /// The topic the submission belongs to (needed for UserTopicBalance PDA derivation)
+    pub topic: Account<'info, Topic>,
+
+    /// The link between the submission and the topic
+    #[account(
+        seeds = [b"submission_topic_link", submission.key().as_ref(), topic.key().as_ref()],
+        bump = submission_topic_link.bump,
+    )]
+    pub submission_topic_link: Account<'info, SubmissionTopicLink>,
+
+    /// User's balance account for this specific topic (to deduct tempRep and count requests)
+    #[account(
+        mut,
+        seeds = [b"user_topic_balance", requester.key().as_ref(), topic.key().as_ref()],
+        bump = user_topic_balance.bump,
+        constraint = user_topic_balance.user == requester.key() @ ErrorCode::UserAccountMismatch,
+        constraint = user_topic_balance.topic == topic.key() @ ErrorCode::InvalidTopic,
+    )]
+    pub user_topic_balance: Account<'info, UserTopicBalance>,
+
+    /// The new AI Validation Request account (same layout as in `RequestAiValidation`)
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 8 + 1 + 2 + 8 + 32 + 32 + 2 + 8
+            + (4 + MAX_ORACLE_COMMITTEE_SIZE * (32 + 1)) + 1 + 1,
+        seeds = [
+            b"ai_request",
+            submission_topic_link.key().as_ref(),
+            requester.key().as_ref(),
+            expected_ai_request_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub ai_validation_request: Account<'info, AiValidationRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"meta_nonce", requester.key().as_ref()],
+        bump = meta_nonce.bump
+    )]
+    pub meta_nonce: Account<'info, MetaNonce>,
+
+    /// CHECK: Address checked; read by `verify_meta_tx`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account constraints for creating a session key
+#[derive(Accounts)]
+#[instruction(session_signer: Pubkey)]
+pub struct CreateSessionKey<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"session_key", user.key().as_ref(), session_signer.as_ref()],
+        bump,
+        space = 8 + 32 + 32 + 8 + 1 + 1 // Discriminator + user + session_signer + expires_at + permissions + bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    /// The user delegating to the session key
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account constraints for revoking a session key
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        mut,
+        seeds = [b"session_key", session_key.user.as_ref(), session_key.session_signer.as_ref()],
+        bump = session_key.bump,
+        has_one = user,
+        constraint = signer.key() == session_key.user
+            || signer.key() == session_key.session_signer
+            @ ErrorCode::UnauthorizedActingSigner,
+        close = user
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    /// Receives the session key's rent
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    /// The user or the session key itself
+    pub signer: Signer<'info>,
+}
+
+/// Instruction: Set a protocol mint's name, symbol and URI (authority only)
+///
+/// Metadata lives on the mint itself through Token-2022's metadata extension. The authority
+/// pays for any extra rent the metadata needs.
+#[derive(Accounts)]
+pub struct SetMintMetadata<'info> {
+    #[account(has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        constraint = [state.temp_align_mint, state.align_mint, state.temp_rep_mint, state.rep_mint]
+            .contains(&mint.key()) @ ErrorCode::TokenMintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Account constraints for onboarding a user in one instruction
+///
+/// Creates the user's profile, protocol-owned tempAlign and tempRep accounts and Align and Rep
+/// ATAs. Each is created only if missing, so a user who already ran some of the individual
+/// instructions, or whose earlier attempt failed halfway, can call this to finish.
+#[derive(Accounts)]
+pub struct OnboardUser<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, State>,
+
+    /// Signer that covers the rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The user being onboarded (read-only, unsigned)
+    pub user: SystemAccount<'info>,
+
+    /// CHECK: Created by the handler if missing, otherwise loaded as a `UserProfile`
+    #[account(mut, seeds = [b"user_profile", user.key().as_ref()], bump)]
+    pub user_profile: UncheckedAccount<'info>,
+
+    #[account(address = state.temp_align_mint @ ErrorCode::TokenMintMismatch)]
+    pub temp_align_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = state.align_mint @ ErrorCode::TokenMintMismatch)]
+    pub align_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = state.temp_rep_mint @ ErrorCode::TokenMintMismatch)]
+    pub temp_rep_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = state.rep_mint @ ErrorCode::TokenMintMismatch)]
+    pub rep_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Created by the handler if missing, with the state PDA as authority
+    #[account(mut, seeds = [b"user_temp_align", user.key().as_ref()], bump)]
+    pub user_temp_align_account: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the handler if missing, with the state PDA as authority
+    #[account(mut, seeds = [b"user_temp_rep", user.key().as_ref()], bump)]
+    pub user_temp_rep_account: UncheckedAccount<'info>,
+
+    /// CHECK: Created idempotently by the ATA program
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &align_mint.key(),
+            &token_program.key()
+        )
+    )]
+    pub user_align_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Created idempotently by the ATA program
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &rep_mint.key(),
+            &token_program.key()
+        )
+    )]
+    pub user_rep_ata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Instruction: Create the starter tempAlign faucet (authority only, `faucet` feature)
+#[derive(Accounts)]
+pub struct InitializeFaucet<'info> {
+    #[account(has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"faucet_config"],
+        bump,
+        space = 8 + 8 + 8 + 1 // Discriminator + amount + claim_interval + bump
+    )]
+    pub faucet_config: Account<'info, FaucetConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction: Update the faucet's amount and claim interval (authority only, `faucet` feature)
+#[derive(Accounts)]
+pub struct UpdateFaucet<'info> {
+    #[account(has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut, seeds = [b"faucet_config"], bump = faucet_config.bump)]
+    pub faucet_config: Account<'info, FaucetConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Instruction: Claim starter tempAlign in a topic (`faucet` feature)
+#[derive(Accounts)]
+pub struct FaucetClaim<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(seeds = [b"faucet_config"], bump = faucet_config.bump)]
+    pub faucet_config: Account<'info, FaucetConfig>,
+
+    pub user: Signer<'info>,
+
+    /// Pays for the claim record on the first claim
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(constraint = topic.is_active @ ErrorCode::TopicInactive)]
+    pub topic: Box<Account<'info, Topic>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_topic_balance", user.key().as_ref(), topic.key().as_ref()],
+        bump = user_topic_balance.bump
+    )]
+    pub user_topic_balance: Box<Account<'info, UserTopicBalance>>,
+
+    /// CHECK: Created by the handler on the user's first claim, otherwise loaded as a
+    /// `FaucetClaimRecord`
+    #[account(
+        mut,
+        seeds = [b"faucet_claim", user.key().as_ref()],
+        bump
+    )]
+    pub faucet_claim: UncheckedAccount<'info>,
+
+    #[account(mut, address = state.temp_align_mint @ ErrorCode::TokenMintMismatch)]
+    pub temp_align_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"user_temp_align", user.key().as_ref()],
+        bump,
+        token::mint = temp_align_mint,
+        token::authority = state
+    )]
+    pub user_temp_align_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction: Update the lifetime `admin_mint` cap for one mint
+///
+/// 1) Updates one entry of `admin_mint_caps` in the `State` account.
+/// 2) Requires the authority to sign.
+#[derive(Accounts)]
+pub struct UpdateAdminMintCap<'info> {
+    #[account(mut, has_one = authority)]
+    pub state: Account<'info, State>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Instruction: Mint protocol tokens to a user (authority only, capped per mint)
+///
+/// The mint is checked against the requested `TokenType` in the handler. Temporary tokens must
+/// go to the recipient's protocol-owned temp account and need their `UserTopicBalance`.
+#[derive(Accounts)]
+pub struct AdminMint<'info> {
+    #[account(mut, has_one = authority)]
+    pub state: Box<Account<'info, State>>,
+
+    pub authority: Signer<'info>,
+
+    /// The user credited with the tokens
+    pub recipient: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, token::mint = mint)]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The recipient's balance in the credited topic, required for temporary tokens
+    #[account(mut)]
+    pub user_topic_balance: Option<Box<Account<'info, UserTopicBalance>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
-// --- END OF NEW CONTEXTS ---
@@ -5,6 +5,57 @@ pub const MAX_TOPIC_NAME_LENGTH: usize = 64;
 pub const MAX_TOPIC_DESCRIPTION_LENGTH: usize = 256;
 pub const MAX_DATA_REFERENCE_LENGTH: usize = 128; // For IPFS/Arweave hashes or transaction references
 
+// Maximum lengths for mint metadata, matching Metaplex's limits so wallets display it alike
+pub const MAX_TOKEN_NAME_LENGTH: usize = 32;
+pub const MAX_TOKEN_SYMBOL_LENGTH: usize = 10;
+pub const MAX_TOKEN_URI_LENGTH: usize = 200;
+
+// Maximum length of the reason recorded with each `admin_mint`
+pub const MAX_ADMIN_MINT_REASON_LENGTH: usize = 128;
+
+// Maximum number of delegations a single vote can draw on
+pub const MAX_DELEGATIONS_PER_VOTE: usize = 8;
+
+// Maximum number of oracles in the oracle committee
+pub const MAX_ORACLE_COMMITTEE_SIZE: usize = 10;
+
+// Denominator for basis-point configuration values
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Topic role bits stored in `TopicRole.roles`
+pub const TOPIC_ROLE_MODERATOR: u8 = 1 << 0; // may update topic settings and voting phases
+pub const TOPIC_ROLE_PHASE_MANAGER: u8 = 1 << 1; // may set voting phases
+pub const TOPIC_ROLE_ORACLE: u8 = 1 << 2; // may submit AI votes for the topic
+pub const TOPIC_ROLE_ALL: u8 = TOPIC_ROLE_MODERATOR | TOPIC_ROLE_PHASE_MANAGER | TOPIC_ROLE_ORACLE;
+
+// Default emission epoch length for topic budgets (24 hours)
+pub const DEFAULT_EMISSION_EPOCH_DURATION: u64 = 24 * 60 * 60;
+
+// Time after which an unanswered AI validation request can be expired and refunded (24 hours)
+pub const AI_REQUEST_TIMEOUT: u64 = 24 * 60 * 60;
+
+// Default weight of AI voting power relative to human voting power (1:1)
+pub const DEFAULT_AI_WEIGHT_BPS: u16 = 10_000;
+
+// Default window length for per-user submission rate limits (24 hours)
+pub const DEFAULT_SUBMISSION_WINDOW_DURATION: u64 = 24 * 60 * 60;
+
+// Default Rep minted per tempRep converted (1:1)
+pub const DEFAULT_REP_MINT_RATIO_BPS: u16 = 10_000;
+
+// Domain separator prefixed to every meta-transaction payload a user signs
+pub const META_TX_DOMAIN: &[u8] = b"ALIGN_META_TX";
+
+// Session key permission bits stored in `SessionKey.permissions`
+pub const SESSION_PERMISSION_SUBMIT: u8 = 1 << 0; // may submit data to topics
+pub const SESSION_PERMISSION_COMMIT_VOTE: u8 = 1 << 1; // may commit votes
+pub const SESSION_PERMISSION_REVEAL_VOTE: u8 = 1 << 2; // may reveal votes
+pub const SESSION_PERMISSION_ALL: u8 =
+    SESSION_PERMISSION_SUBMIT | SESSION_PERMISSION_COMMIT_VOTE | SESSION_PERMISSION_REVEAL_VOTE;
+
+// Longest a session key can stay valid (7 days)
+pub const MAX_SESSION_DURATION: u64 = 7 * 24 * 60 * 60;
+
 /// Global state account for this protocol
 #[account]
 pub struct State {
@@ -40,6 +91,46 @@ pub struct State {
 
     /// Default duration for reveal phase in seconds (24 hours)
     pub default_reveal_phase_duration: u64,
+
+    /// Protocol Rep emitted into each link's validator reward pool at finalization
+    pub validator_reward_emission: u64,
+
+    /// Lamports paid from the `FeeVault` to the signer of each successful
+    /// `finalize_submission` / `finalize_vote` (per vote for `finalize_votes_batch`)
+    pub finalization_reward: u64,
+
+    /// Revealed voting power (yes + no) a link needs before finalizing it or its votes pays
+    /// `finalization_reward`, so a single cheap reveal cannot farm the fee vault
+    pub finalization_reward_min_voting_power: u64,
+
+    /// Commission (in basis points) validators keep from delegators' vote rewards
+    pub delegation_commission_bps: u16,
+
+    /// When true, AI votes require threshold attestations from the `OracleCommittee`
+    /// and `oracle_pubkey` alone can no longer decide them
+    pub oracle_committee_active: bool,
+
+    /// Lifetime caps on `admin_mint`, indexed by `TokenType` (0 disables admin minting)
+    pub admin_mint_caps: [u64; 4],
+
+    /// Total minted through `admin_mint` so far, indexed by `TokenType`
+    pub admin_minted: [u64; 4],
+}
+
+/// One of the four protocol token mints, as selected by admin instructions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenType {
+    /// `temp_align_mint`
+    TempAlign,
+
+    /// `align_mint`
+    Align,
+
+    /// `temp_rep_mint`
+    TempRep,
+
+    /// `rep_mint`
+    Rep,
 }
 
 /// Each submission entry
@@ -54,6 +145,10 @@ pub struct Submission {
     /// Arbitrary string to store data reference (IPFS hash, Arweave ID, etc.)
     pub data_reference: String,
 
+    /// tempAlign actually minted for the submission, after any emission budget scaling.
+    /// This is what `finalize_submission` converts to Align on acceptance.
+    pub temp_align_minted: u64,
+
     /// Bump seed for the submission PDA
     pub bump: u8,
 }
@@ -89,12 +184,21 @@ pub struct UserProfile {
     pub user_align_ata: Pubkey,
     pub user_rep_ata: Pubkey,
 
+    /// Number of this user's submissions accepted across all topics
+    pub accepted_submission_count: u64,
+
     // REMOVE pub permanent_rep_amount: u64,
     /// Bump seed for the user profile PDA
     pub bump: u8,
     // REMOVE pub topic_tokens: Vec<TopicTokenPair>, - Done
 }
 
+impl UserProfile {
+    /// Account size, shared by every path that creates a profile: discriminator + user +
+    /// submission count + four token account keys + accepted submission count + bump
+    pub const SPACE: usize = 8 + 32 + 8 + (32 * 4) + 8 + 1;
+}
+
 /// Account to store user's token balances for a specific topic
 #[account]
 pub struct UserTopicBalance {
@@ -113,6 +217,25 @@ pub struct UserTopicBalance {
     /// Amount of temporary reputation tokens locked in active votes for this topic
     pub locked_temp_rep_amount: u64,
 
+    /// Total permanent Rep this user has earned by voting in this topic
+    pub rep_earned: u64,
+
+    /// Start timestamp of the user's current submission rate-limit window
+    pub submission_window_start: u64,
+
+    /// Submissions made in the current rate-limit window
+    pub window_submission_count: u64,
+
+    /// Submissions made in this topic that are not finalized yet
+    pub pending_submission_count: u64,
+
+    /// Number of AI validation requests this user has made in this topic (next request index)
+    pub user_ai_request_count: u64,
+
+    /// Part of `temp_align_amount` that came from the faucet or `admin_mint` and cannot be
+    /// converted to Align
+    pub unconvertible_temp_align_amount: u64,
+
     /// Bump seed for the PDA
     pub bump: u8,
 }
@@ -141,6 +264,59 @@ pub struct Topic {
     /// Whether the topic is active and accepting submissions
     pub is_active: bool,
 
+    /// Override for `State.tokens_to_mint` in this topic (None = use the global value)
+    pub tokens_to_mint: Option<u64>,
+
+    /// Rep minted per tempRep converted in `finalize_vote`, in basis points
+    /// (None = `DEFAULT_REP_MINT_RATIO_BPS`, i.e. 1:1)
+    pub rep_mint_ratio_bps: Option<u16>,
+
+    /// Maximum tempAlign minted by submissions per emission epoch (None = unlimited)
+    pub emission_budget_per_epoch: Option<u64>,
+
+    /// Length of an emission epoch in seconds
+    pub emission_epoch_duration: u64,
+
+    /// Start timestamp of the current emission epoch
+    pub emission_epoch_start: u64,
+
+    /// tempAlign minted by submissions in the current emission epoch
+    pub emission_epoch_minted: u64,
+
+    /// Minimum permanent Rep balance required to participate (0 = no requirement)
+    pub min_rep_balance: u64,
+
+    /// Minimum number of accepted submissions (across all topics) required to participate
+    pub min_accepted_submissions: u64,
+
+    /// Topic in which participants must have earned Rep (None = no requirement)
+    pub parent_topic: Option<Pubkey>,
+
+    /// Minimum Rep earned in `parent_topic` required to participate
+    pub min_parent_topic_rep: u64,
+
+    /// Maximum submissions per user per rate-limit window (None = unlimited)
+    pub max_submissions_per_window: Option<u64>,
+
+    /// Length of a user's submission rate-limit window in seconds
+    pub submission_window_duration: u64,
+
+    /// Maximum unfinalized submissions per user (None = unlimited)
+    pub max_pending_submissions: Option<u64>,
+
+    /// Weight applied to AI voting power at finalization, in basis points (10000 = same as human power)
+    pub ai_weight_bps: u16,
+
+    /// Whether AI voting power is further scaled by each verdict's `confidence_bps` at finalization
+    pub ai_confidence_scaling: bool,
+
+    /// Oracle that answers this topic's AI requests instead of `State.oracle_pubkey` (None = use the global oracle)
+    pub oracle_pubkey: Option<Pubkey>,
+
+    /// When true, this topic's AI votes require threshold attestations from its own
+    /// `OracleCommittee` (seeds `[b"oracle_committee", topic]`) instead of any oracle key
+    pub oracle_committee_active: bool,
+
     /// Bump seed for the topic PDA
     pub bump: u8,
 }
@@ -181,6 +357,21 @@ pub struct SubmissionTopicLink {
     /// Total number of revealed votes
     pub total_revealed_votes: u64,
 
+    /// Total tempRep revealed on the Yes side
+    pub yes_temp_rep: u64,
+
+    /// Total tempRep revealed on the No side
+    pub no_temp_rep: u64,
+
+    /// Rep reward pool shared by correct validators (losing tempRep + emission, set at finalization)
+    pub reward_pool: u64,
+
+    /// Portion of the reward pool already paid out to correct validators
+    pub reward_pool_claimed: u64,
+
+    /// Number of completed AI validation requests, all of which `finalize_submission` must count
+    pub completed_ai_requests: u64,
+
     /// Bump seed for the link PDA
     pub bump: u8,
 }
@@ -223,10 +414,48 @@ pub struct VoteCommit {
     /// The amount of permanent REP tokens committed to this vote
     pub perm_rep_amount: u64,
 
+    /// Total delegated tempRep drawn into this vote (sum of `delegations`)
+    pub delegated_temp_rep_amount: u64,
+
+    /// The delegations this vote drew on, in the order they were passed at commit
+    pub delegations: Vec<DelegatedStake>,
+
     /// Bump seed for the vote commit PDA
     pub bump: u8,
 }
 
+/// A delegation drawn into a vote and the amount locked from it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DelegatedStake {
+    /// The Delegation PDA the stake was drawn from
+    pub delegation: Pubkey,
+
+    /// The amount of delegated tempRep locked in the vote
+    pub amount: u64,
+}
+
+/// Delegation of a user's topic-specific tempRep to a validator
+#[account]
+pub struct Delegation {
+    /// The user delegating their tempRep
+    pub delegator: Pubkey,
+
+    /// The validator allowed to vote with the delegated tempRep
+    pub validator: Pubkey,
+
+    /// The topic the delegated tempRep belongs to
+    pub topic: Pubkey,
+
+    /// Delegated tempRep available for the validator to vote with
+    pub amount: u64,
+
+    /// Delegated tempRep locked in the validator's active votes
+    pub locked_amount: u64,
+
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
 /// Status of an AI Validation Request
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum AiValidationStatus {
@@ -236,8 +465,10 @@ pub enum AiValidationStatus {
     Processing,
     /// AI result received and vote submitted on-chain
     Completed,
-    /// Off-chain processing failed
+    /// Off-chain processing failed (stake refunded)
     Failed,
+    /// The oracle did not respond within `AI_REQUEST_TIMEOUT` (stake refunded)
+    Expired,
 }
 
 /// Account to track an AI validation request for a specific submission within a topic
@@ -257,8 +488,155 @@ pub struct AiValidationRequest {
     pub ai_decision: Option<VoteChoice>,
     /// The AI's voting power derived from temp_rep_staked (populated upon completion)
     pub ai_voting_power: u64,
-    /// The index (from link counter) used for this request's PDA
+    /// Hash of the model identifier that produced the verdict (zeroed until completed)
+    pub model_hash: [u8; 32],
+    /// Hash of the model's rationale, stored off-chain (zeroed until completed)
+    pub rationale_hash: [u8; 32],
+    /// The model's confidence in its verdict, in basis points
+    pub confidence_bps: u16,
+    /// The index (from the requester's `user_ai_request_count`) used for this request's PDA
     pub request_index: u64,
+    /// Decisions submitted by oracle committee members so far
+    pub attestations: Vec<OracleAttestation>,
+    /// True once committee members have submitted conflicting decisions
+    pub disputed: bool,
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
+/// A single oracle committee member's decision on an AI validation request
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OracleAttestation {
+    /// The committee member who attested
+    pub oracle: Pubkey,
+
+    /// The member's decision
+    pub decision: VoteChoice,
+}
+
+/// Set of oracle keys that must agree before an AI vote counts
+#[account]
+pub struct OracleCommittee {
+    /// Member oracle keys
+    pub members: Vec<Pubkey>,
+
+    /// Number of matching attestations needed to complete a request
+    pub threshold: u8,
+
+    /// Bump seed for the committee PDA
+    pub bump: u8,
+}
+
+/// Topic-scoped permissions granted to a wallet by the topic creator or protocol authority
+#[account]
+pub struct TopicRole {
+    /// The topic the roles apply to
+    pub topic: Pubkey,
+
+    /// The wallet holding the roles
+    pub holder: Pubkey,
+
+    /// Bitmask of `TOPIC_ROLE_*` flags
+    pub roles: u8,
+
+    /// The signer who granted the roles
+    pub granted_by: Pubkey,
+
     /// Bump seed for the PDA
     pub bump: u8,
 }
+
+/// Program-owned lamport pool that pays finalization rewards
+///
+/// Anyone can top it up with `fund_fee_vault`; its rent-exempt minimum is never paid out.
+#[account]
+pub struct FeeVault {
+    /// Bump seed for the vault PDA
+    pub bump: u8,
+}
+
+/// Replay protection for a user's relayed (meta-transaction) instructions
+#[account]
+pub struct MetaNonce {
+    /// The user whose signed payloads this counter guards
+    pub user: Pubkey,
+
+    /// Nonce the next signed payload must carry; incremented on every relayed instruction
+    pub nonce: u64,
+
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
+/// Ephemeral key a user authorized to act for them until `expires_at`
+#[account]
+pub struct SessionKey {
+    /// The user who created the session
+    pub user: Pubkey,
+
+    /// The ephemeral key allowed to sign for the user
+    pub session_signer: Pubkey,
+
+    /// Unix timestamp after which the session can no longer be used
+    pub expires_at: u64,
+
+    /// Bitmask of `SESSION_PERMISSION_*` flags
+    pub permissions: u8,
+
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
+/// Settings for the starter tempAlign faucet
+#[account]
+pub struct FaucetConfig {
+    /// tempAlign minted per claim; zero disables the faucet
+    pub amount: u64,
+
+    /// Length in seconds of the epochs a user may claim once in; zero allows a single claim
+    /// per user ever
+    pub claim_interval: u64,
+
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
+/// A user's last faucet claim, in any topic
+#[account]
+pub struct FaucetClaimRecord {
+    pub user: Pubkey,
+
+    /// Unix timestamp of the last claim
+    pub last_claimed_at: u64,
+
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
+/// Emitted by `admin_mint` so every authority mint leaves an auditable record
+#[event]
+pub struct AdminMinted {
+    /// Which of the four mints was minted from
+    pub token_type: TokenType,
+
+    /// The user credited with the tokens
+    pub recipient: Pubkey,
+
+    /// The token account the tokens were minted into
+    pub recipient_token_account: Pubkey,
+
+    /// The topic credited for temporary tokens, `None` for permanent ones
+    pub topic: Option<Pubkey>,
+
+    /// Amount minted
+    pub amount: u64,
+
+    /// Total minted through `admin_mint` for this mint after this call
+    pub total_minted: u64,
+
+    /// Free-form justification supplied by the authority
+    pub reason: String,
+
+    /// Unix timestamp of the mint
+    pub timestamp: i64,
+}
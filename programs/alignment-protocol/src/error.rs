@@ -144,4 +144,170 @@ pub enum ErrorCode {
     // State Mismatch Errors (Start: 2024)
     #[msg("AI request index mismatch. State may have changed.")]
     StateMismatch,
+
+    // --- Delegation Errors ---
+    #[msg("Users cannot delegate tempRep to themselves.")]
+    SelfDelegationNotAllowed,
+
+    #[msg("The delegation does not belong to this validator.")]
+    DelegationValidatorMismatch,
+
+    #[msg("The delegation does not belong to this topic.")]
+    DelegationTopicMismatch,
+
+    #[msg("Too many delegations drawn into a single vote.")]
+    TooManyDelegations,
+
+    #[msg("The delegation accounts passed do not match the delegations recorded on the vote.")]
+    DelegationAccountsMismatch,
+
+    #[msg("Insufficient unlocked delegated tempRep.")]
+    InsufficientDelegatedBalance,
+
+    #[msg("Commission must not exceed 10000 basis points.")]
+    InvalidCommission,
+
+    // --- Topic Role Errors ---
+    #[msg("Topic roles must be a non-empty combination of known role flags.")]
+    InvalidTopicRole,
+
+    // --- Emission Budget Errors ---
+    #[msg("Emission epoch duration must be greater than zero.")]
+    InvalidEmissionEpochDuration,
+
+    // --- Topic Eligibility Errors ---
+    #[msg("Permanent Rep balance is below the topic's minimum.")]
+    InsufficientRepForTopic,
+
+    #[msg("Not enough accepted submissions to participate in this topic.")]
+    InsufficientAcceptedSubmissions,
+
+    #[msg("Not enough Rep earned in the topic's parent topic.")]
+    InsufficientParentTopicRep,
+
+    #[msg("A topic cannot be its own parent topic.")]
+    InvalidParentTopic,
+
+    // --- Submission Rate Limit Errors ---
+    #[msg("Submission limit for the current window reached in this topic.")]
+    SubmissionRateLimitExceeded,
+
+    #[msg("Too many pending submissions in this topic; wait for some to be finalized.")]
+    TooManyPendingSubmissions,
+
+    #[msg("Submission window duration must be greater than zero.")]
+    InvalidSubmissionWindow,
+
+    // --- AI Weighting Errors ---
+    #[msg("AI weight cannot exceed 10000 basis points.")]
+    InvalidAiWeight,
+
+    #[msg("AI confidence cannot exceed 10000 basis points.")]
+    InvalidAiConfidence,
+
+    #[msg("Completed AI requests passed do not match the link's completed request count.")]
+    AiRequestAccountsMismatch,
+
+    #[msg("The AI validation request has not timed out yet.")]
+    AiRequestNotExpired,
+
+    // --- Oracle Committee Errors ---
+    #[msg("Committee threshold must be between 1 and the number of members.")]
+    InvalidCommitteeThreshold,
+
+    #[msg("Too many oracle committee members.")]
+    TooManyCommitteeMembers,
+
+    #[msg("Oracle committee members must be unique.")]
+    DuplicateCommitteeMember,
+
+    #[msg("The signer is not a member of the oracle committee.")]
+    NotCommitteeMember,
+
+    #[msg("This oracle has already attested to the AI validation request.")]
+    DuplicateAttestation,
+
+    #[msg("The oracle committee is not the one assigned to this topic.")]
+    InvalidOracleCommittee,
+
+    // --- Batch Finalization Errors ---
+    #[msg("Remaining accounts do not form complete vote finalization groups.")]
+    VoteBatchAccountsMismatch,
+
+    #[msg("Vote has not been revealed")]
+    VoteNotRevealed,
+
+    // --- Finalization Reward Errors ---
+    #[msg("Fee vault deposit must be greater than zero.")]
+    ZeroFeeVaultDeposit,
+
+    // --- Meta-Transaction Errors ---
+    #[msg("Meta-transaction nonce does not match the user's next nonce.")]
+    InvalidMetaNonce,
+
+    #[msg("No ed25519 signature by the user over the meta-transaction payload was found.")]
+    MetaTxSignatureMissing,
+
+    // --- Session Key Errors ---
+    #[msg("Session expiry must be in the future and within the maximum session duration.")]
+    InvalidSessionExpiry,
+
+    #[msg("Session permissions must be a non-empty set of known permission bits.")]
+    InvalidSessionPermissions,
+
+    #[msg("The session key has expired.")]
+    SessionExpired,
+
+    #[msg("The session key does not allow this instruction.")]
+    SessionPermissionDenied,
+
+    #[msg("The signer is neither the user nor one of their session keys.")]
+    UnauthorizedActingSigner,
+
+    // --- Mint Metadata Errors ---
+    #[msg("Token name, symbol or URI exceeds maximum length.")]
+    TokenMetadataTooLong,
+
+    #[msg("Token name and symbol cannot be empty.")]
+    EmptyTokenMetadata,
+
+    // --- Faucet Errors ---
+    #[msg("The faucet is not enabled in this build of the program.")]
+    FaucetNotEnabled,
+
+    #[msg("The faucet is disabled.")]
+    FaucetDisabled,
+
+    #[msg("The faucet has already been claimed in the current epoch.")]
+    FaucetAlreadyClaimed,
+
+    // --- Admin Mint Errors ---
+    #[msg("Admin mint amount must be greater than zero.")]
+    ZeroAdminMintAmount,
+
+    #[msg("Admin mint reason cannot be empty.")]
+    EmptyAdminMintReason,
+
+    #[msg("Admin mint reason exceeds maximum length.")]
+    AdminMintReasonTooLong,
+
+    #[msg("Admin mint would exceed the lifetime cap for this token.")]
+    AdminMintCapExceeded,
+
+    #[msg("Admin mint cap cannot be lowered below the amount already minted.")]
+    AdminMintCapBelowMinted,
+
+    #[msg("Minting temporary tokens requires the recipient's UserTopicBalance.")]
+    MissingUserTopicBalance,
+
+    // --- Topic Reward Override Errors ---
+    #[msg("A topic's tokens_to_mint override cannot exceed the global tokens_to_mint.")]
+    TopicTokensToMintTooHigh,
+
+    #[msg("Rep mint ratio cannot exceed 10000 basis points.")]
+    InvalidRepMintRatio,
+
+    // --- Meta-Transaction Expiry Errors ---
+    #[msg("The meta-transaction payload has expired.")]
+    MetaTxExpired,
 }
@@ -1,4 +1,290 @@
+use crate::data::{
+    SessionKey, State, Topic, TopicRole, UserTopicBalance, BPS_DENOMINATOR, META_TX_DOMAIN,
+    TOPIC_ROLE_MODERATOR,
+};
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+use anchor_lang::system_program;
+use sha2::{Digest, Sha256};
+
 /// Calculates the square root of a number for quadratic voting power
 pub fn calculate_quadratic_voting_power(amount: u64) -> u64 {
     (amount as f64).sqrt() as u64
 }
+
+/// Converts a tempRep amount to Rep using a basis-point mint ratio
+///
+/// Rounds down and saturates at `u64::MAX` for ratios above 1:1.
+pub fn calculate_rep_from_temp_rep(temp_rep_amount: u64, rep_mint_ratio_bps: u16) -> u64 {
+    let rep = (temp_rep_amount as u128) * (rep_mint_ratio_bps as u128) / (BPS_DENOMINATOR as u128);
+    u64::try_from(rep).unwrap_or(u64::MAX)
+}
+
+/// Returns the start of the emission epoch containing `now`
+///
+/// Epochs are aligned to `epoch_start`, so a topic that sees no submissions for
+/// several epochs skips straight to the current one.
+pub fn calculate_current_epoch_start(epoch_start: u64, epoch_duration: u64, now: u64) -> u64 {
+    if epoch_duration == 0 || now < epoch_start {
+        return epoch_start;
+    }
+    let elapsed_epochs = (now - epoch_start) / epoch_duration;
+    epoch_start.saturating_add(elapsed_epochs.saturating_mul(epoch_duration))
+}
+
+/// Calculates a validator's pro rata share of a link's reward pool
+///
+/// The share is proportional to the validator's voting power relative to the total
+/// voting power of the winning side. Rounds down; dust stays in the pool.
+pub fn calculate_reward_share(reward_pool: u64, voting_power: u64, winning_power: u64) -> u64 {
+    if winning_power == 0 {
+        return 0;
+    }
+    ((reward_pool as u128) * (voting_power as u128) / (winning_power as u128)) as u64
+}
+
+/// Returns true if `topic_role` grants `holder` any of the `roles` bits on `topic`
+pub fn has_topic_role(
+    topic_role: Option<&TopicRole>,
+    topic: &Pubkey,
+    holder: &Pubkey,
+    roles: u8,
+) -> bool {
+    match topic_role {
+        Some(role) => role.topic == *topic && role.holder == *holder && role.roles & roles != 0,
+        None => false,
+    }
+}
+
+/// Checks that `signer` is the protocol authority, the topic creator or a topic moderator
+pub fn require_topic_admin(
+    state: &State,
+    topic: &Account<Topic>,
+    topic_role: Option<&TopicRole>,
+    signer: &Pubkey,
+) -> Result<()> {
+    require!(
+        *signer == state.authority
+            || *signer == topic.creator
+            || has_topic_role(topic_role, &topic.key(), signer, TOPIC_ROLE_MODERATOR),
+        ErrorCode::InvalidAuthority
+    );
+    Ok(())
+}
+
+/// Checks that `signer` may act for `user`
+///
+/// The user can always act for themselves. Anyone else needs an unexpired session key the
+/// user created for them that grants `permission`.
+pub fn require_user_or_session(
+    user: &Pubkey,
+    signer: &Pubkey,
+    session_key: Option<&SessionKey>,
+    permission: u8,
+    now: u64,
+) -> Result<()> {
+    if signer == user {
+        return Ok(());
+    }
+    let session = match session_key {
+        Some(session) if session.user == *user && session.session_signer == *signer => session,
+        _ => return err!(ErrorCode::UnauthorizedActingSigner),
+    };
+    require!(now <= session.expires_at, ErrorCode::SessionExpired);
+    require!(
+        session.permissions & permission != 0,
+        ErrorCode::SessionPermissionDenied
+    );
+    Ok(())
+}
+
+/// Checks a user against a topic's participation rules
+///
+/// A missing parent-topic balance counts as zero Rep earned there.
+pub fn check_topic_eligibility(
+    topic: &Topic,
+    user: &Pubkey,
+    rep_balance: u64,
+    accepted_submissions: u64,
+    parent_topic_balance: Option<&UserTopicBalance>,
+) -> Result<()> {
+    require!(
+        rep_balance >= topic.min_rep_balance,
+        ErrorCode::InsufficientRepForTopic
+    );
+    require!(
+        accepted_submissions >= topic.min_accepted_submissions,
+        ErrorCode::InsufficientAcceptedSubmissions
+    );
+
+    if let Some(parent_topic) = topic.parent_topic {
+        let parent_rep = match parent_topic_balance {
+            Some(balance) => {
+                require_keys_eq!(balance.user, *user, ErrorCode::UserAccountMismatch);
+                require_keys_eq!(balance.topic, parent_topic, ErrorCode::InvalidTopic);
+                balance.rep_earned
+            }
+            None => 0,
+        };
+        require!(
+            parent_rep >= topic.min_parent_topic_rep,
+            ErrorCode::InsufficientParentTopicRep
+        );
+    }
+
+    Ok(())
+}
+
+/// Computes the digest a user signs to authorize a relayed instruction
+///
+/// `sha256("ALIGN_META_TX" || instruction_data || scope || remaining_accounts || nonce ||
+/// expires_at)`, where `instruction_data` is the base instruction's discriminator followed by
+/// its borsh args, `scope` is the account the action targets, so a payload cannot be
+/// redirected elsewhere, and `remaining_accounts` is the borsh-style u32 count followed by the
+/// ordered keys of the instruction's remaining accounts, so a relayer cannot swap in accounts
+/// (e.g. delegations) the user did not choose.
+///
+/// Replay is prevented by the per-user nonce and staleness by `expires_at`. The digest does
+/// not cover a recent blockhash: a program cannot read the blockhash its transaction was built
+/// against, so the binding could not be checked on-chain.
+pub fn meta_tx_digest(
+    instruction_data: &[u8],
+    scope: &Pubkey,
+    remaining_accounts: &[Pubkey],
+    nonce: u64,
+    expires_at: u64,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(META_TX_DOMAIN);
+    hasher.update(instruction_data);
+    hasher.update(scope.as_ref());
+    hasher.update((remaining_accounts.len() as u32).to_le_bytes());
+    for account in remaining_accounts {
+        hasher.update(account.as_ref());
+    }
+    hasher.update(nonce.to_le_bytes());
+    hasher.update(expires_at.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Checks that the transaction carries an ed25519 signature by `signer` over `message`
+///
+/// Scans the instructions sysvar for an ed25519 program instruction whose signature, public
+/// key and message all live in its own data. The runtime verifies ed25519 instructions before
+/// the program runs, so a matching entry proves the signature.
+pub fn verify_meta_tx(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    // Layout: [num_signatures: u8, padding: u8] then 7 u16 offsets per signature
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    // Instruction index meaning "this instruction's own data"
+    const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        let data = &ix.data;
+        let num_signatures = data.first().copied().unwrap_or(0) as usize;
+        for i in 0..num_signatures {
+            let start = OFFSETS_START + i * OFFSETS_LEN;
+            let offsets = match data.get(start..start + OFFSETS_LEN) {
+                Some(offsets) => offsets,
+                None => break,
+            };
+            let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+            // signature_instruction_index, public_key_instruction_index, message_instruction_index
+            if read(2) != CURRENT_INSTRUCTION
+                || read(6) != CURRENT_INSTRUCTION
+                || read(12) != CURRENT_INSTRUCTION
+            {
+                continue;
+            }
+            let public_key_offset = read(4) as usize;
+            let message_offset = read(8) as usize;
+            let message_size = read(10) as usize;
+            let public_key = data.get(public_key_offset..public_key_offset + 32);
+            let signed_message = data.get(message_offset..message_offset + message_size);
+            if public_key == Some(signer.as_ref()) && signed_message == Some(message) {
+                return Ok(());
+            }
+        }
+    }
+
+    err!(ErrorCode::MetaTxSignatureMissing)
+}
+
+/// Creates a PDA-addressed account owned by `owner`, as Anchor's `init` does
+///
+/// Works even if someone already sent lamports to the address: the shortfall is topped up and
+/// the account allocated and assigned instead of created.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[seeds],
+            ),
+            rent.minimum_balance(space),
+            space as u64,
+            owner,
+        );
+    }
+
+    let required_lamports = rent
+        .minimum_balance(space)
+        .max(1)
+        .saturating_sub(current_lamports);
+    if required_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[seeds],
+        ),
+        owner,
+    )
+}
@@ -1,24 +1,64 @@
-use crate::contexts::{RequestAiValidation, SubmitAiVote};
-use crate::data::{AiValidationStatus, SubmissionStatus, VoteChoice};
+use crate::contexts::{
+    ExpireAiRequest, MarkAiFailed, MarkAiProcessing, RequestAiValidation, SubmitAiVote,
+};
+use crate::data::{
+    AiValidationRequest, AiValidationStatus, State, Submission, SubmissionStatus,
+    SubmissionTopicLink, Topic, TopicRole, UserTopicBalance, VoteChoice, AI_REQUEST_TIMEOUT,
+    BPS_DENOMINATOR, TOPIC_ROLE_ORACLE,
+};
 use crate::error::ErrorCode;
-use crate::helpers::calculate_quadratic_voting_power; // Use existing helper
+use crate::helpers::{calculate_quadratic_voting_power, has_topic_role}; // Use existing helper
 use anchor_lang::prelude::*;
 
 pub fn request_ai_validation(
     ctx: Context<RequestAiValidation>,
     temp_rep_to_stake: u64,         // Amount of tempRep user commits
     expected_ai_request_index: u64, // <-- Add expected index argument
+) -> Result<()> {
+    open_ai_request(
+        AiRequestOpening {
+            requester: ctx.accounts.requester.key(),
+            submission: &ctx.accounts.submission,
+            link: &ctx.accounts.submission_topic_link,
+            user_balance: &mut ctx.accounts.user_topic_balance,
+            ai_request: &mut ctx.accounts.ai_validation_request,
+            bump: ctx.bumps.ai_validation_request,
+        },
+        temp_rep_to_stake,
+        expected_ai_request_index,
+    )
+}
+
+/// Accounts needed to open an AI validation request, whoever pays for it
+pub(crate) struct AiRequestOpening<'a, 'info> {
+    pub requester: Pubkey,
+    pub submission: &'a Account<'info, Submission>,
+    pub link: &'a Account<'info, SubmissionTopicLink>,
+    pub user_balance: &'a mut Account<'info, UserTopicBalance>,
+    pub ai_request: &'a mut Account<'info, AiValidationRequest>,
+    pub bump: u8,
+}
+
+/// Validate and record an AI validation request, spending the requester's tempRep
+pub(crate) fn open_ai_request(
+    opening: AiRequestOpening,
+    temp_rep_to_stake: u64,
+    expected_ai_request_index: u64,
 ) -> Result<()> {
     let clock = Clock::get()?;
     let current_timestamp_u64 = clock.unix_timestamp as u64; // Cast to u64
-    let requester = &ctx.accounts.requester;
-    let submission = &ctx.accounts.submission;
-    let link = &mut ctx.accounts.submission_topic_link;
-    let user_balance = &mut ctx.accounts.user_topic_balance;
-    let ai_request = &mut ctx.accounts.ai_validation_request;
+    let AiRequestOpening {
+        requester,
+        submission,
+        link,
+        user_balance,
+        ai_request,
+        bump,
+    } = opening;
 
     // --- State Check ---
-    let current_ai_request_index = link.total_committed_votes;
+    // Requests are indexed per (link, requester) by the requester's own counter in this topic
+    let current_ai_request_index = user_balance.user_ai_request_count;
     require_eq!(
         current_ai_request_index,
         expected_ai_request_index,
@@ -30,7 +70,7 @@ pub fn request_ai_validation(
     // 1. Requester must be the original contributor of the submission
     require_keys_eq!(
         submission.contributor,
-        requester.key(),
+        requester,
         ErrorCode::NotSubmissionContributor // Keeping specific error
     );
 
@@ -52,11 +92,7 @@ pub fn request_ai_validation(
         ErrorCode::RevealPhaseEnded                     // Using existing error
     );
 
-    // 5. Ensure an AI validation hasn't already been requested/completed for this link
-    // (The `init` constraint on ai_validation_request account in the context handles this -
-    // if the account already exists from a previous request, the transaction will fail)
-
-    // 6. Basic sanity check for stake amount
+    // 5. Basic sanity check for stake amount
     require!(temp_rep_to_stake > 0, ErrorCode::ZeroVoteAmount); // Using existing error
 
     // Logic:
@@ -72,26 +108,31 @@ pub fn request_ai_validation(
 
     // 2. Initialize the AiValidationRequest account
     ai_request.submission_topic_link = link.key();
-    ai_request.requester = requester.key();
+    ai_request.requester = requester;
     ai_request.temp_rep_staked = temp_rep_to_stake;
     ai_request.request_timestamp = current_timestamp_u64;
     ai_request.status = AiValidationStatus::Pending;
     ai_request.ai_decision = None;
     ai_request.ai_voting_power = 0;
+    ai_request.model_hash = [0; 32];
+    ai_request.rationale_hash = [0; 32];
+    ai_request.confidence_bps = 0;
     ai_request.request_index = index_for_this_request;
-    ai_request.bump = ctx.bumps.ai_validation_request;
+    ai_request.attestations = Vec::new();
+    ai_request.disputed = false;
+    ai_request.bump = bump;
 
-    // IMPORTANT: Increment the counter on the link *after* successful request init
+    // IMPORTANT: Increment the requester's counter *after* successful request init
     // and *after* reading the value for request_index.
-    link.total_committed_votes = link
-        .total_committed_votes
+    user_balance.user_ai_request_count = user_balance
+        .user_ai_request_count
         .checked_add(1)
         .ok_or(ErrorCode::Overflow)?;
 
     msg!(
         "AI Validation requested for link {} by user {}. Staked/Spent {} tempRep. Request index: {}",
         link.key(),
-        requester.key(),
+        requester,
         temp_rep_to_stake,
         index_for_this_request
     );
@@ -101,8 +142,11 @@ pub fn request_ai_validation(
 
 pub fn submit_ai_vote(
     ctx: Context<SubmitAiVote>,
-    _ai_request_index: u64,  // Renamed to silence unused variable warning
-    ai_decision: VoteChoice, // The decision from the AI (Yes/No)
+    _ai_request_index: u64,   // Renamed to silence unused variable warning
+    ai_decision: VoteChoice,  // The decision from the AI (Yes/No)
+    model_hash: [u8; 32],     // Hash of the model identifier
+    rationale_hash: [u8; 32], // Hash of the rationale stored off-chain
+    confidence_bps: u16,      // Model confidence in basis points
 ) -> Result<()> {
     let clock = Clock::get()?;
     let current_timestamp_u64 = clock.unix_timestamp as u64; // Cast to u64
@@ -112,16 +156,28 @@ pub fn submit_ai_vote(
     let link = &mut ctx.accounts.submission_topic_link;
 
     // Validation Checks:
-    // 1. Signer must be the authorized Oracle stored in the global state
-    require_keys_eq!(
-        oracle.key(),
-        state.oracle_pubkey,
+    // 1. Signer must be the authorized Oracle stored in the global state,
+    //    or hold the oracle role on the link's topic
+    require!(
+        is_authorized_oracle(
+            state,
+            &ctx.accounts.topic,
+            ctx.accounts.topic_role.as_deref(),
+            &oracle.key()
+        ),
         ErrorCode::UnauthorizedOracle // Keeping specific error
     );
 
-    // 2. AI Request must be in Pending state (waiting for oracle processing)
+    // 1b. The requester is the submission's contributor and may not judge their own work
     require!(
-        ai_request.status == AiValidationStatus::Pending,
+        oracle.key() != ai_request.requester,
+        ErrorCode::SelfVotingNotAllowed
+    );
+
+    // 2. AI Request must be waiting for the oracle (Pending or Processing)
+    require!(
+        ai_request.status == AiValidationStatus::Pending
+            || ai_request.status == AiValidationStatus::Processing,
         ErrorCode::InvalidAiRequestStatus // Keeping specific error
     );
 
@@ -138,31 +194,38 @@ pub fn submit_ai_vote(
         ErrorCode::RevealPhaseEnded                     // Using existing error
     );
 
-    // Logic:
-    // 1. Calculate quadratic voting power from the tempRep staked by the user
+    // 5. Confidence is a fraction of certainty
+    require!(
+        confidence_bps as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidAiConfidence
+    );
+
+    // Record provenance so auditors can trace which model and prompt produced the verdict
+    ai_request.model_hash = model_hash;
+    ai_request.rationale_hash = rationale_hash;
+    ai_request.confidence_bps = confidence_bps;
+
+    complete_ai_request(ai_request, link, ai_decision)
+}
+
+/// Records an AI verdict on a request and counts it on the link
+///
+/// The verdict is stored on the request only. The link's vote tallies stay human-only
+/// until `finalize_submission` applies the topic's AI weight, so validators revealing
+/// afterwards cannot see the AI's verdict reflected in the tally.
+pub(crate) fn complete_ai_request(
+    ai_request: &mut AiValidationRequest,
+    link: &mut Account<SubmissionTopicLink>,
+    ai_decision: VoteChoice,
+) -> Result<()> {
+    // Calculate quadratic voting power from the tempRep staked by the user
     let voting_power = calculate_quadratic_voting_power(ai_request.temp_rep_staked);
 
-    // 2. Update SubmissionTopicLink vote counts with AI's power
-    match ai_decision {
-        VoteChoice::Yes => {
-            link.yes_voting_power = link
-                .yes_voting_power
-                .checked_add(voting_power)
-                .ok_or(ErrorCode::Overflow)?; // Using existing error
-            msg!("AI voted Yes with power {}", voting_power);
-        }
-        VoteChoice::No => {
-            link.no_voting_power = link
-                .no_voting_power
-                .checked_add(voting_power)
-                .ok_or(ErrorCode::Overflow)?; // Using existing error
-            msg!("AI voted No with power {}", voting_power);
-        }
-    }
-    // Note: We are not incrementing total_committed_votes or total_revealed_votes
-    // as the AI doesn't follow the commit/reveal scheme. Its power is added directly.
+    link.completed_ai_requests = link
+        .completed_ai_requests
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
 
-    // 3. Update AiValidationRequest status and details
     ai_request.status = AiValidationStatus::Completed;
     ai_request.ai_decision = Some(ai_decision);
     ai_request.ai_voting_power = voting_power;
@@ -176,3 +239,120 @@ pub fn submit_ai_vote(
 
     Ok(())
 }
+
+/// Returns true if `oracle` may decide AI requests in `topic` on its own
+///
+/// A committee that applies to the topic means only attestations count, even for holders of
+/// the topic's oracle role. Otherwise role holders are trusted, and a topic oracle key
+/// replaces the global one. Topics without an assignment fall back to the global oracle.
+fn is_authorized_oracle(
+    state: &State,
+    topic: &Account<Topic>,
+    topic_role: Option<&TopicRole>,
+    oracle: &Pubkey,
+) -> bool {
+    let committee_applies = topic.oracle_committee_active
+        || (topic.oracle_pubkey.is_none() && state.oracle_committee_active);
+    if committee_applies {
+        return false;
+    }
+    if has_topic_role(topic_role, &topic.key(), oracle, TOPIC_ROLE_ORACLE) {
+        return true;
+    }
+    match topic.oracle_pubkey {
+        Some(topic_oracle) => *oracle == topic_oracle,
+        None => *oracle == state.oracle_pubkey,
+    }
+}
+
+/// Returns the request's tempRep stake to the requester's available topic balance
+fn refund_ai_stake(
+    ai_request: &mut AiValidationRequest,
+    user_balance: &mut UserTopicBalance,
+    status: AiValidationStatus,
+) -> Result<()> {
+    require!(
+        ai_request.status == AiValidationStatus::Pending
+            || ai_request.status == AiValidationStatus::Processing,
+        ErrorCode::InvalidAiRequestStatus
+    );
+
+    user_balance.temp_rep_amount = user_balance
+        .temp_rep_amount
+        .checked_add(ai_request.temp_rep_staked)
+        .ok_or(ErrorCode::Overflow)?;
+    ai_request.status = status;
+
+    msg!(
+        "AI request {} marked {:?}. Refunded {} tempRep to {}",
+        ai_request.request_index,
+        ai_request.status,
+        ai_request.temp_rep_staked,
+        ai_request.requester
+    );
+    Ok(())
+}
+
+pub fn mark_ai_processing(ctx: Context<MarkAiProcessing>) -> Result<()> {
+    let ai_request = &mut ctx.accounts.ai_validation_request;
+
+    require!(
+        is_authorized_oracle(
+            &ctx.accounts.state,
+            &ctx.accounts.topic,
+            ctx.accounts.topic_role.as_deref(),
+            &ctx.accounts.oracle.key(),
+        ),
+        ErrorCode::UnauthorizedOracle
+    );
+    require!(
+        ai_request.status == AiValidationStatus::Pending,
+        ErrorCode::InvalidAiRequestStatus
+    );
+
+    ai_request.status = AiValidationStatus::Processing;
+
+    msg!(
+        "AI request {} for link {} is being processed",
+        ai_request.request_index,
+        ai_request.submission_topic_link
+    );
+    Ok(())
+}
+
+pub fn mark_ai_failed(ctx: Context<MarkAiFailed>) -> Result<()> {
+    require!(
+        is_authorized_oracle(
+            &ctx.accounts.state,
+            &ctx.accounts.topic,
+            ctx.accounts.topic_role.as_deref(),
+            &ctx.accounts.oracle.key(),
+        ),
+        ErrorCode::UnauthorizedOracle
+    );
+
+    refund_ai_stake(
+        &mut ctx.accounts.ai_validation_request,
+        &mut ctx.accounts.user_topic_balance,
+        AiValidationStatus::Failed,
+    )
+}
+
+pub fn expire_ai_request(ctx: Context<ExpireAiRequest>) -> Result<()> {
+    let current_timestamp_u64 = Clock::get()?.unix_timestamp as u64;
+    let expires_at = ctx
+        .accounts
+        .ai_validation_request
+        .request_timestamp
+        .saturating_add(AI_REQUEST_TIMEOUT);
+    require!(
+        current_timestamp_u64 >= expires_at,
+        ErrorCode::AiRequestNotExpired
+    );
+
+    refund_ai_stake(
+        &mut ctx.accounts.ai_validation_request,
+        &mut ctx.accounts.user_topic_balance,
+        AiValidationStatus::Expired,
+    )
+}
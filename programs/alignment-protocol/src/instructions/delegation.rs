@@ -0,0 +1,133 @@
+use crate::contexts::{CreateDelegation, ModifyDelegation};
+use crate::data::Delegation;
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Create a delegation of the delegator's topic-specific tempRep to a validator
+///
+/// The delegated amount moves out of the delegator's available `temp_rep_amount`
+/// and into the Delegation PDA. The tokens themselves stay in the delegator's
+/// protocol-owned tempRep account until a vote drawing on them is finalized.
+pub fn create_delegation(ctx: Context<CreateDelegation>, amount: u64) -> Result<()> {
+    if ctx.accounts.delegator.key() == ctx.accounts.validator.key() {
+        return Err(ErrorCode::SelfDelegationNotAllowed.into());
+    }
+
+    let delegation = &mut ctx.accounts.delegation;
+    delegation.delegator = ctx.accounts.delegator.key();
+    delegation.validator = ctx.accounts.validator.key();
+    delegation.topic = ctx.accounts.topic.key();
+    delegation.amount = 0;
+    delegation.locked_amount = 0;
+    delegation.bump = ctx.bumps.delegation;
+
+    move_into_delegation(
+        &mut ctx.accounts.user_topic_balance.temp_rep_amount,
+        delegation,
+        amount,
+    )?;
+
+    msg!(
+        "Delegated {} tempRep in topic {} from {} to validator {}",
+        amount,
+        ctx.accounts.topic.key(),
+        delegation.delegator,
+        delegation.validator
+    );
+    Ok(())
+}
+
+/// Add more of the delegator's available tempRep to an existing delegation
+pub fn delegate_temp_rep(ctx: Context<ModifyDelegation>, amount: u64) -> Result<()> {
+    let delegation = &mut ctx.accounts.delegation;
+    require!(
+        delegation.topic == ctx.accounts.topic.key(),
+        ErrorCode::DelegationTopicMismatch
+    );
+
+    move_into_delegation(
+        &mut ctx.accounts.user_topic_balance.temp_rep_amount,
+        delegation,
+        amount,
+    )?;
+
+    msg!(
+        "Delegated {} more tempRep to validator {} (available delegated: {}, locked: {})",
+        amount,
+        delegation.validator,
+        delegation.amount,
+        delegation.locked_amount
+    );
+    Ok(())
+}
+
+/// Return unlocked delegated tempRep to the delegator's available balance
+///
+/// Amounts locked in the validator's pending votes cannot be withdrawn until
+/// those votes are finalized.
+pub fn undelegate_temp_rep(ctx: Context<ModifyDelegation>, amount: u64) -> Result<()> {
+    let delegation = &mut ctx.accounts.delegation;
+    require!(
+        delegation.topic == ctx.accounts.topic.key(),
+        ErrorCode::DelegationTopicMismatch
+    );
+
+    if amount == 0 {
+        return Err(ErrorCode::ZeroStakeAmount.into());
+    }
+    if delegation.amount < amount {
+        msg!(
+            "Insufficient unlocked delegated tempRep. Requested: {}, Available: {}",
+            amount,
+            delegation.amount
+        );
+        return Err(ErrorCode::InsufficientDelegatedBalance.into());
+    }
+
+    delegation.amount = delegation
+        .amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    let user_topic_balance = &mut ctx.accounts.user_topic_balance;
+    user_topic_balance.temp_rep_amount = user_topic_balance
+        .temp_rep_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    msg!(
+        "Withdrew {} delegated tempRep from validator {} (available delegated: {}, locked: {})",
+        amount,
+        delegation.validator,
+        delegation.amount,
+        delegation.locked_amount
+    );
+    Ok(())
+}
+
+fn move_into_delegation(
+    available_temp_rep: &mut u64,
+    delegation: &mut Delegation,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Err(ErrorCode::ZeroStakeAmount.into());
+    }
+    if *available_temp_rep < amount {
+        msg!(
+            "Insufficient tempRep to delegate. Requested: {}, Available: {}",
+            amount,
+            available_temp_rep
+        );
+        return Err(ErrorCode::InsufficientTempRepBalance.into());
+    }
+
+    *available_temp_rep = available_temp_rep
+        .checked_sub(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    delegation.amount = delegation
+        .amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
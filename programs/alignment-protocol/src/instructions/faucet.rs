@@ -0,0 +1,115 @@
+use crate::contexts::{FaucetClaim, InitializeFaucet, UpdateFaucet};
+use crate::data::FaucetClaimRecord;
+use crate::error::ErrorCode;
+use crate::helpers::create_pda_account;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, MintTo};
+
+/// Create the faucet with its per-claim amount and claim interval
+pub fn initialize_faucet(
+    ctx: Context<InitializeFaucet>,
+    amount: u64,
+    claim_interval: u64,
+) -> Result<()> {
+    let faucet_config = &mut ctx.accounts.faucet_config;
+    faucet_config.amount = amount;
+    faucet_config.claim_interval = claim_interval;
+    faucet_config.bump = ctx.bumps.faucet_config;
+
+    msg!(
+        "Initialized faucet: {} tempAlign per claim, claim interval {} seconds",
+        amount,
+        claim_interval
+    );
+    Ok(())
+}
+
+/// Update the faucet's per-claim amount and claim interval
+pub fn update_faucet(ctx: Context<UpdateFaucet>, amount: u64, claim_interval: u64) -> Result<()> {
+    let faucet_config = &mut ctx.accounts.faucet_config;
+    faucet_config.amount = amount;
+    faucet_config.claim_interval = claim_interval;
+
+    msg!(
+        "Updated faucet: {} tempAlign per claim, claim interval {} seconds",
+        amount,
+        claim_interval
+    );
+    Ok(())
+}
+
+/// Mint starter tempAlign into the user's protocol-owned account for a topic
+///
+/// Each user may claim once, or once per `claim_interval` epoch when an interval is set,
+/// whichever topic they claim in. Epochs are aligned to multiples of the interval since the
+/// Unix epoch. Faucet tempAlign can be staked but is never converted to Align.
+pub fn faucet_claim(ctx: Context<FaucetClaim>) -> Result<()> {
+    let amount = ctx.accounts.faucet_config.amount;
+    let claim_interval = ctx.accounts.faucet_config.claim_interval;
+    require!(amount > 0, ErrorCode::FaucetDisabled);
+
+    let user = ctx.accounts.user.key();
+    let topic = ctx.accounts.topic.key();
+    let now = Clock::get()?.unix_timestamp as u64;
+    let claim_info = ctx.accounts.faucet_claim.to_account_info();
+
+    let mut claim = if claim_info.data_is_empty() {
+        create_pda_account(
+            &ctx.accounts.payer.to_account_info(),
+            &claim_info,
+            8 + 32 + 8 + 1, // Discriminator + user + last_claimed_at + bump
+            ctx.program_id,
+            &[b"faucet_claim", user.as_ref(), &[ctx.bumps.faucet_claim]],
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        FaucetClaimRecord {
+            user,
+            last_claimed_at: 0,
+            bump: ctx.bumps.faucet_claim,
+        }
+    } else {
+        let claim = FaucetClaimRecord::try_deserialize(&mut &claim_info.try_borrow_data()?[..])?;
+        // A repeat claim is only allowed once a new epoch has started
+        require!(
+            claim_interval > 0 && now / claim_interval > claim.last_claimed_at / claim_interval,
+            ErrorCode::FaucetAlreadyClaimed
+        );
+        claim
+    };
+    claim.last_claimed_at = now;
+    claim.try_serialize(&mut &mut claim_info.try_borrow_mut_data()?[..])?;
+
+    let state_bump = ctx.accounts.state.bump;
+    let seeds = &[b"state".as_ref(), &[state_bump]];
+    let signer = &[&seeds[..]];
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.temp_align_mint.to_account_info(),
+                to: ctx.accounts.user_temp_align_account.to_account_info(),
+                authority: ctx.accounts.state.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    let user_topic_balance = &mut ctx.accounts.user_topic_balance;
+    user_topic_balance.temp_align_amount = user_topic_balance
+        .temp_align_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    user_topic_balance.unconvertible_temp_align_amount = user_topic_balance
+        .unconvertible_temp_align_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    msg!(
+        "Faucet minted {} tempAlign to user {} for topic {}",
+        amount,
+        user,
+        topic
+    );
+    Ok(())
+}
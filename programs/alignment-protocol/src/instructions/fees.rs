@@ -0,0 +1,98 @@
+use crate::contexts::{FundFeeVault, InitializeFeeVault};
+use crate::data::{FeeVault, SubmissionTopicLink};
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+/// Create the fee vault that pays finalization rewards
+pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+    ctx.accounts.fee_vault.bump = ctx.bumps.fee_vault;
+    msg!("Initialized fee vault {}", ctx.accounts.fee_vault.key());
+    Ok(())
+}
+
+/// Deposit lamports into the fee vault
+pub fn fund_fee_vault(ctx: Context<FundFeeVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::ZeroFeeVaultDeposit);
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_ctx, amount)?;
+
+    msg!(
+        "Deposited {} lamports into the fee vault (balance {})",
+        amount,
+        ctx.accounts.fee_vault.to_account_info().lamports()
+    );
+    Ok(())
+}
+
+/// Pay `count` finalization rewards on `link` from the fee vault to `recipient`
+///
+/// Finalization never fails over the reward: nothing is paid when no vault is passed
+/// or the vault cannot cover it without dipping below its rent-exempt minimum. Callers
+/// pay only after a link or vote has moved to its finalized state, which can happen
+/// once, so no finalization is rewarded twice. Links nobody revealed a vote on, or whose
+/// revealed voting power is below `min_voting_power`, earn nothing: contributors cannot
+/// vote on their own submissions, but a sybil validator could, so this stops either
+/// draining the vault by finalizing self-made links nobody meaningfully reviewed.
+pub(crate) fn pay_finalization_reward<'info>(
+    reward: u64,
+    min_voting_power: u64,
+    count: u64,
+    link: &SubmissionTopicLink,
+    fee_vault: Option<&Account<'info, FeeVault>>,
+    recipient: &AccountInfo<'info>,
+) -> Result<()> {
+    let fee_vault = match fee_vault {
+        Some(fee_vault) => fee_vault.to_account_info(),
+        None => return Ok(()),
+    };
+    if link.total_revealed_votes == 0 {
+        msg!("No votes were revealed on this link, skipping finalization reward");
+        return Ok(());
+    }
+    let revealed_voting_power = link.yes_voting_power.saturating_add(link.no_voting_power);
+    if revealed_voting_power < min_voting_power {
+        msg!(
+            "Revealed voting power {} is below the minimum {}, skipping finalization reward",
+            revealed_voting_power,
+            min_voting_power
+        );
+        return Ok(());
+    }
+    let amount = reward.checked_mul(count).ok_or(ErrorCode::Overflow)?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(fee_vault.data_len());
+    let available = fee_vault.lamports().saturating_sub(rent_exempt_minimum);
+    if available < amount {
+        msg!(
+            "Fee vault holds {} spare lamports, skipping finalization reward of {}",
+            available,
+            amount
+        );
+        return Ok(());
+    }
+
+    // The vault is program-owned, so its lamports can be moved without a CPI
+    **fee_vault.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    msg!(
+        "Paid finalization reward of {} lamports to {}",
+        amount,
+        recipient.key()
+    );
+    Ok(())
+}
@@ -1,8 +1,27 @@
 use crate::contexts::{
     InitializeAlignMint, InitializeRepMint, InitializeState, InitializeTempAlignMint,
-    InitializeTempRepMint, UpdateTokensToMint,
+    InitializeTempRepMint, SetMintMetadata, UpdateAdminMintCap, UpdateDelegationCommission,
+    UpdateFinalizationReward, UpdateTokensToMint, UpdateValidatorRewardEmission,
 };
+use crate::data::{
+    TokenType, BPS_DENOMINATOR, MAX_TOKEN_NAME_LENGTH, MAX_TOKEN_SYMBOL_LENGTH,
+    MAX_TOKEN_URI_LENGTH,
+};
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount, Transfer};
+use anchor_spl::token_interface::{
+    self, find_mint_account_size, metadata_pointer_initialize, non_transferable_mint_initialize,
+    permanent_delegate_initialize,
+    spl_token_2022::{
+        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        state::Mint as MintState,
+    },
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+    token_metadata_initialize, token_metadata_update_field, InitializeMint2,
+    MetadataPointerInitialize, NonTransferableMintInitialize, PermanentDelegateInitialize,
+    TokenMetadataInitialize, TokenMetadataUpdateField,
+};
 
 pub fn initialize_state(ctx: Context<InitializeState>, oracle_pubkey: Pubkey) -> Result<()> {
     let state_acc = &mut ctx.accounts.state;
@@ -13,6 +32,13 @@ pub fn initialize_state(ctx: Context<InitializeState>, oracle_pubkey: Pubkey) ->
     state_acc.bump = ctx.bumps.state;
     state_acc.topic_count = 0;
     state_acc.tokens_to_mint = 0;
+    state_acc.validator_reward_emission = 0;
+    state_acc.finalization_reward = 0;
+    state_acc.finalization_reward_min_voting_power = 0;
+    state_acc.delegation_commission_bps = 0;
+    state_acc.oracle_committee_active = false;
+    state_acc.admin_mint_caps = [0; 4];
+    state_acc.admin_minted = [0; 4];
 
     // Set default voting phase durations (24 hours each by default)
     state_acc.default_commit_phase_duration = 24 * 60 * 60; // 24 hours in seconds
@@ -39,7 +65,90 @@ pub fn initialize_state(ctx: Context<InitializeState>, oracle_pubkey: Pubkey) ->
     Ok(())
 }
 
+/// Create a soulbound Token-2022 mint at a PDA
+///
+/// NonTransferable stops holders moving tokens out of their accounts, and the state PDA is
+/// PermanentDelegate so the protocol can burn from any holder. The state PDA is also mint and
+/// freeze authority, as for the other mints, and the mint's MetadataPointer points at itself
+/// so `set_mint_metadata` can store metadata on it. Anchor's `init` cannot add
+/// NonTransferable, so the account is created and initialised here.
+fn create_soulbound_mint<'info>(
+    mint: &AccountInfo<'info>,
+    mint_seeds: &[&[u8]],
+    state: &Pubkey,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let space = find_mint_account_size(Some(&vec![
+        ExtensionType::NonTransferable,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::MetadataPointer,
+    ]))?;
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount {
+                from: payer.clone(),
+                to: mint.clone(),
+            },
+            &[mint_seeds],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        token_program.key,
+    )?;
+
+    // Extensions must be initialised before the mint itself
+    non_transferable_mint_initialize(CpiContext::new(
+        token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        },
+    ))?;
+    permanent_delegate_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            PermanentDelegateInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        state,
+    )?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(*state),
+        Some(mint.key()),
+    )?;
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program.clone(),
+            InitializeMint2 { mint: mint.clone() },
+        ),
+        0,
+        state,
+        Some(state),
+    )
+}
+
 pub fn initialize_temp_align_mint(ctx: Context<InitializeTempAlignMint>) -> Result<()> {
+    create_soulbound_mint(
+        &ctx.accounts.temp_align_mint,
+        &[b"temp_align_mint", &[ctx.bumps.temp_align_mint]],
+        &ctx.accounts.state.key(),
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
     let state_acc = &mut ctx.accounts.state;
 
     // Store the temp_align_mint address
@@ -65,6 +174,15 @@ pub fn initialize_align_mint(ctx: Context<InitializeAlignMint>) -> Result<()> {
 }
 
 pub fn initialize_temp_rep_mint(ctx: Context<InitializeTempRepMint>) -> Result<()> {
+    create_soulbound_mint(
+        &ctx.accounts.temp_rep_mint,
+        &[b"temp_rep_mint", &[ctx.bumps.temp_rep_mint]],
+        &ctx.accounts.state.key(),
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
     let state_acc = &mut ctx.accounts.state;
 
     // Store the temp_rep_mint address
@@ -76,6 +194,15 @@ pub fn initialize_temp_rep_mint(ctx: Context<InitializeTempRepMint>) -> Result<(
 }
 
 pub fn initialize_rep_mint(ctx: Context<InitializeRepMint>) -> Result<()> {
+    create_soulbound_mint(
+        &ctx.accounts.rep_mint,
+        &[b"rep_mint", &[ctx.bumps.rep_mint]],
+        &ctx.accounts.state.key(),
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    )?;
+
     let state_acc = &mut ctx.accounts.state;
 
     // Store the rep_mint address
@@ -100,3 +227,214 @@ pub fn update_tokens_to_mint(
     );
     Ok(())
 }
+
+pub fn update_validator_reward_emission(
+    ctx: Context<UpdateValidatorRewardEmission>,
+    new_validator_reward_emission: u64,
+) -> Result<()> {
+    let state_acc = &mut ctx.accounts.state;
+    let previous_emission = state_acc.validator_reward_emission;
+    state_acc.validator_reward_emission = new_validator_reward_emission;
+    msg!(
+        "Updated validator_reward_emission from {} to {}",
+        previous_emission,
+        new_validator_reward_emission
+    );
+    Ok(())
+}
+
+pub fn update_delegation_commission(
+    ctx: Context<UpdateDelegationCommission>,
+    new_delegation_commission_bps: u16,
+) -> Result<()> {
+    require!(
+        new_delegation_commission_bps as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidCommission
+    );
+
+    let state_acc = &mut ctx.accounts.state;
+    let previous_commission = state_acc.delegation_commission_bps;
+    state_acc.delegation_commission_bps = new_delegation_commission_bps;
+    msg!(
+        "Updated delegation_commission_bps from {} to {}",
+        previous_commission,
+        new_delegation_commission_bps
+    );
+    Ok(())
+}
+
+/// Set the finalization reward along with the revealed voting power a link needs to earn it
+///
+/// Both are set together: a reward without a voting power floor lets anyone with a sliver of
+/// tempRep reveal one vote per self-made link and collect it.
+pub fn update_finalization_reward(
+    ctx: Context<UpdateFinalizationReward>,
+    new_finalization_reward: u64,
+    new_min_voting_power: u64,
+) -> Result<()> {
+    let state_acc = &mut ctx.accounts.state;
+    let previous_reward = state_acc.finalization_reward;
+    let previous_min_voting_power = state_acc.finalization_reward_min_voting_power;
+    state_acc.finalization_reward = new_finalization_reward;
+    state_acc.finalization_reward_min_voting_power = new_min_voting_power;
+    msg!(
+        "Updated finalization_reward from {} to {} lamports (min voting power {} to {})",
+        previous_reward,
+        new_finalization_reward,
+        previous_min_voting_power,
+        new_min_voting_power
+    );
+    Ok(())
+}
+
+/// Set the lifetime `admin_mint` cap for one mint
+///
+/// The cap bounds the total ever minted through `admin_mint`, so it cannot drop below what has
+/// already been minted.
+pub fn update_admin_mint_cap(
+    ctx: Context<UpdateAdminMintCap>,
+    token_type: TokenType,
+    new_cap: u64,
+) -> Result<()> {
+    let state_acc = &mut ctx.accounts.state;
+    let index = token_type as usize;
+    require!(
+        new_cap >= state_acc.admin_minted[index],
+        ErrorCode::AdminMintCapBelowMinted
+    );
+
+    let previous_cap = state_acc.admin_mint_caps[index];
+    state_acc.admin_mint_caps[index] = new_cap;
+    msg!(
+        "Updated {:?} admin mint cap from {} to {}",
+        token_type,
+        previous_cap,
+        new_cap
+    );
+    Ok(())
+}
+
+/// Set a protocol mint's name, symbol and URI
+///
+/// The first call initialises the mint's metadata extension, with the state PDA as update
+/// authority; later calls overwrite the three fields. Token-2022 reallocates the mint to fit,
+/// so the authority first tops it up to the new rent-exempt minimum.
+pub fn set_mint_metadata(
+    ctx: Context<SetMintMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    require!(
+        !name.is_empty() && !symbol.is_empty(),
+        ErrorCode::EmptyTokenMetadata
+    );
+    require!(
+        name.len() <= MAX_TOKEN_NAME_LENGTH
+            && symbol.len() <= MAX_TOKEN_SYMBOL_LENGTH
+            && uri.len() <= MAX_TOKEN_URI_LENGTH,
+        ErrorCode::TokenMetadataTooLong
+    );
+
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let state_info = ctx.accounts.state.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    let (current, data_len) = {
+        let data = mint_info.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+        (
+            mint_state
+                .get_variable_len_extension::<TokenMetadata>()
+                .ok(),
+            data.len(),
+        )
+    };
+    let mut metadata = current.clone().unwrap_or(TokenMetadata {
+        update_authority: Some(state_info.key())
+            .try_into()
+            .map_err(|_| ErrorCode::InvalidAuthority)?,
+        mint: mint_info.key(),
+        ..Default::default()
+    });
+    metadata.name = name.clone();
+    metadata.symbol = symbol.clone();
+    metadata.uri = uri.clone();
+
+    let new_tlv_len = metadata.tlv_size_of()?;
+    let old_tlv_len = match &current {
+        Some(current) => current.tlv_size_of()?,
+        None => 0,
+    };
+    let new_len = data_len
+        .checked_sub(old_tlv_len)
+        .and_then(|len| len.checked_add(new_tlv_len))
+        .ok_or(ErrorCode::Overflow)?;
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(mint_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: mint_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    let state_bump = ctx.accounts.state.bump;
+    let state_seeds: &[&[u8]] = &[b"state", &[state_bump]];
+    match current {
+        None => token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataInitialize {
+                    token_program_id: token_program.clone(),
+                    metadata: mint_info.clone(),
+                    update_authority: state_info.clone(),
+                    mint_authority: state_info.clone(),
+                    mint: mint_info.clone(),
+                },
+                &[state_seeds],
+            ),
+            name,
+            symbol,
+            uri,
+        )?,
+        Some(_) => {
+            for (field, value) in [
+                (Field::Name, name),
+                (Field::Symbol, symbol),
+                (Field::Uri, uri),
+            ] {
+                token_metadata_update_field(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        TokenMetadataUpdateField {
+                            token_program_id: token_program.clone(),
+                            metadata: mint_info.clone(),
+                            update_authority: state_info.clone(),
+                        },
+                        &[state_seeds],
+                    ),
+                    field,
+                    value,
+                )?;
+            }
+        }
+    }
+
+    msg!(
+        "Set metadata on mint {}: {} ({}) {}",
+        mint_info.key(),
+        metadata.name,
+        metadata.symbol,
+        metadata.uri
+    );
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use crate::contexts::{
+    CommitVoteMeta, InitializeMetaNonce, RequestAiValidationMeta, RevealVoteMeta,
+    StakeTopicSpecificTokensMeta, SubmitDataToTopicMeta,
+};
+use crate::data::{MetaNonce, VoteChoice};
+use crate::error::ErrorCode;
+use crate::helpers::{meta_tx_digest, verify_meta_tx};
+use crate::instruction;
+use crate::instructions::ai::{open_ai_request, AiRequestOpening};
+use crate::instructions::submission::record_submission;
+use crate::instructions::tokens::record_stake;
+use crate::instructions::votes::{record_vote_commit, record_vote_reveal};
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+
+/// Create the nonce PDA a user's relayed instructions are counted in
+pub fn initialize_meta_nonce(ctx: Context<InitializeMetaNonce>) -> Result<()> {
+    let meta_nonce = &mut ctx.accounts.meta_nonce;
+    meta_nonce.user = ctx.accounts.user.key();
+    meta_nonce.nonce = 0;
+    meta_nonce.bump = ctx.bumps.meta_nonce;
+
+    msg!("Initialized meta-transaction nonce for {}", meta_nonce.user);
+    Ok(())
+}
+
+/// Check the user's signature over an unexpired relayed payload and consume its nonce
+///
+/// `base_instruction` is the instruction the user would otherwise have sent themselves, so
+/// the signed payload carries its discriminator and borsh args. The keys of
+/// `remaining_accounts` are signed in order too, whether or not the instruction reads them.
+fn authorize_meta_tx<I: InstructionData>(
+    meta_nonce: &mut Account<MetaNonce>,
+    instructions_sysvar: &AccountInfo,
+    base_instruction: I,
+    scope: Pubkey,
+    remaining_accounts: &[AccountInfo],
+    payload_nonce: u64,
+    expires_at: u64,
+) -> Result<()> {
+    require_eq!(payload_nonce, meta_nonce.nonce, ErrorCode::InvalidMetaNonce);
+    require!(
+        Clock::get()?.unix_timestamp as u64 <= expires_at,
+        ErrorCode::MetaTxExpired
+    );
+
+    let remaining_keys: Vec<Pubkey> = remaining_accounts.iter().map(|a| a.key()).collect();
+    let digest = meta_tx_digest(
+        &base_instruction.data(),
+        &scope,
+        &remaining_keys,
+        payload_nonce,
+        expires_at,
+    );
+    verify_meta_tx(instructions_sysvar, &meta_nonce.user, &digest)?;
+
+    meta_nonce.nonce = payload_nonce.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    msg!(
+        "Relayed instruction authorized by {} (nonce {})",
+        meta_nonce.user,
+        payload_nonce
+    );
+    Ok(())
+}
+
+/// `submit_data_to_topic` signed by the contributor and sent by a relayer; scoped to the topic
+pub fn submit_data_to_topic_meta<'info>(
+    ctx: Context<'_, '_, '_, 'info, SubmitDataToTopicMeta<'info>>,
+    data_reference: String,
+    current_submission_index: u64,
+    payload_nonce: u64,
+    expires_at: u64,
+) -> Result<()> {
+    let Context {
+        program_id,
+        accounts,
+        remaining_accounts,
+        bumps,
+        ..
+    } = ctx;
+    authorize_meta_tx(
+        &mut accounts.meta_nonce,
+        &accounts.instructions,
+        instruction::SubmitDataToTopic {
+            data_reference: data_reference.clone(),
+            current_submission_index,
+        },
+        accounts.inner.topic.key(),
+        remaining_accounts,
+        payload_nonce,
+        expires_at,
+    )?;
+    record_submission(
+        Context::new(
+            program_id,
+            &mut accounts.inner,
+            remaining_accounts,
+            bumps.inner,
+        ),
+        data_reference,
+        current_submission_index,
+    )
+}
+
+/// `stake_topic_specific_tokens` signed by the user and sent by a relayer; scoped to the topic
+pub fn stake_topic_specific_tokens_meta<'info>(
+    ctx: Context<'_, '_, '_, 'info, StakeTopicSpecificTokensMeta<'info>>,
+    amount: u64,
+    payload_nonce: u64,
+    expires_at: u64,
+) -> Result<()> {
+    let Context {
+        program_id,
+        accounts,
+        remaining_accounts,
+        bumps,
+        ..
+    } = ctx;
+    authorize_meta_tx(
+        &mut accounts.meta_nonce,
+        &accounts.instructions,
+        instruction::StakeTopicSpecificTokens { amount },
+        accounts.inner.topic.key(),
+        remaining_accounts,
+        payload_nonce,
+        expires_at,
+    )?;
+    record_stake(
+        Context::new(
+            program_id,
+            &mut accounts.inner,
+            remaining_accounts,
+            bumps.inner,
+        ),
+        amount,
+    )
+}
+
+/// `commit_vote` signed by the validator and sent by a relayer; scoped to the
+/// submission-topic link
+pub fn commit_vote_meta<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CommitVoteMeta<'info>>,
+    vote_hash: [u8; 32],
+    temp_rep_amount: u64,
+    perm_rep_amount: u64,
+    delegation_amounts: Vec<u64>,
+    payload_nonce: u64,
+    expires_at: u64,
+) -> Result<()> {
+    let Context {
+        program_id,
+        accounts,
+        remaining_accounts,
+        bumps,
+        ..
+    } = ctx;
+    authorize_meta_tx(
+        &mut accounts.meta_nonce,
+        &accounts.instructions,
+        instruction::CommitVote {
+            vote_hash,
+            temp_rep_amount,
+            perm_rep_amount,
+            delegation_amounts: delegation_amounts.clone(),
+        },
+        accounts.inner.submission_topic_link.key(),
+        remaining_accounts,
+        payload_nonce,
+        expires_at,
+    )?;
+    record_vote_commit(
+        Context::new(
+            program_id,
+            &mut accounts.inner,
+            remaining_accounts,
+            bumps.inner,
+        ),
+        vote_hash,
+        temp_rep_amount,
+        perm_rep_amount,
+        delegation_amounts,
+    )
+}
+
+/// `reveal_vote` signed by the validator and sent by a relayer; scoped to the
+/// submission-topic link
+pub fn reveal_vote_meta<'info>(
+    ctx: Context<'_, '_, '_, 'info, RevealVoteMeta<'info>>,
+    vote_choice: VoteChoice,
+    nonce: String,
+    payload_nonce: u64,
+    expires_at: u64,
+) -> Result<()> {
+    let Context {
+        program_id,
+        accounts,
+        remaining_accounts,
+        bumps,
+        ..
+    } = ctx;
+    authorize_meta_tx(
+        &mut accounts.meta_nonce,
+        &accounts.instructions,
+        instruction::RevealVote {
+            vote_choice,
+            nonce: nonce.clone(),
+        },
+        accounts.inner.submission_topic_link.key(),
+        remaining_accounts,
+        payload_nonce,
+        expires_at,
+    )?;
+    record_vote_reveal(
+        Context::new(
+            program_id,
+            &mut accounts.inner,
+            remaining_accounts,
+            bumps.inner,
+        ),
+        vote_choice,
+        nonce,
+    )
+}
+
+/// `request_ai_validation` signed by the contributor and sent by a relayer; scoped to the
+/// submission-topic link
+pub fn request_ai_validation_meta(
+    ctx: Context<RequestAiValidationMeta>,
+    temp_rep_to_stake: u64,
+    expected_ai_request_index: u64,
+    payload_nonce: u64,
+    expires_at: u64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    authorize_meta_tx(
+        &mut accounts.meta_nonce,
+        &accounts.instructions,
+        instruction::RequestAiValidation {
+            temp_rep_to_stake,
+            expected_ai_request_index,
+        },
+        accounts.submission_topic_link.key(),
+        ctx.remaining_accounts,
+        payload_nonce,
+        expires_at,
+    )?;
+    open_ai_request(
+        AiRequestOpening {
+            requester: accounts.requester.key(),
+            submission: &accounts.submission,
+            link: &accounts.submission_topic_link,
+            user_balance: &mut accounts.user_topic_balance,
+            ai_request: &mut accounts.ai_validation_request,
+            bump: ctx.bumps.ai_validation_request,
+        },
+        temp_rep_to_stake,
+        expected_ai_request_index,
+    )
+}
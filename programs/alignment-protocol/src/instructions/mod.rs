@@ -1,5 +1,12 @@
 pub mod ai;
+pub mod delegation;
+#[cfg(feature = "faucet")]
+pub mod faucet;
+pub mod fees;
 pub mod initialize;
+pub mod meta;
+pub mod oracle;
+pub mod session;
 pub mod submission;
 pub mod tokens;
 pub mod topics;
@@ -7,7 +14,14 @@ pub mod users;
 pub mod votes;
 
 pub use ai::*;
+pub use delegation::*;
+#[cfg(feature = "faucet")]
+pub use faucet::*;
+pub use fees::*;
 pub use initialize::*;
+pub use meta::*;
+pub use oracle::*;
+pub use session::*;
 pub use submission::*;
 pub use tokens::*;
 pub use topics::*;
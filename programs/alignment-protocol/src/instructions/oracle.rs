@@ -0,0 +1,208 @@
+use crate::contexts::{
+    InitializeOracleCommittee, InitializeTopicOracleCommittee, SubmitOracleAttestation,
+    UpdateOracleCommittee, UpdateTopicOracleCommittee,
+};
+use crate::data::{
+    AiValidationStatus, OracleAttestation, OracleCommittee, VoteChoice, BPS_DENOMINATOR,
+    MAX_ORACLE_COMMITTEE_SIZE,
+};
+use crate::error::ErrorCode;
+use crate::instructions::ai::complete_ai_request;
+use anchor_lang::prelude::*;
+
+/// Validates and stores a committee's members and threshold
+fn set_committee(
+    committee: &mut OracleCommittee,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        members.len() <= MAX_ORACLE_COMMITTEE_SIZE,
+        ErrorCode::TooManyCommitteeMembers
+    );
+    require!(
+        threshold >= 1 && threshold as usize <= members.len(),
+        ErrorCode::InvalidCommitteeThreshold
+    );
+    for (i, member) in members.iter().enumerate() {
+        require!(
+            !members[..i].contains(member),
+            ErrorCode::DuplicateCommitteeMember
+        );
+    }
+
+    committee.members = members;
+    committee.threshold = threshold;
+
+    msg!(
+        "Oracle committee: {} members, threshold {}",
+        committee.members.len(),
+        committee.threshold
+    );
+    Ok(())
+}
+
+pub fn initialize_oracle_committee(
+    ctx: Context<InitializeOracleCommittee>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let committee = &mut ctx.accounts.oracle_committee;
+    committee.bump = ctx.bumps.oracle_committee;
+    set_committee(committee, members, threshold)?;
+
+    // From now on the single global oracle can no longer decide AI votes by itself
+    ctx.accounts.state.oracle_committee_active = true;
+    msg!("Oracle committee activated");
+    Ok(())
+}
+
+pub fn update_oracle_committee(
+    ctx: Context<UpdateOracleCommittee>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    set_committee(&mut ctx.accounts.oracle_committee, members, threshold)
+}
+
+pub fn initialize_topic_oracle_committee(
+    ctx: Context<InitializeTopicOracleCommittee>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let committee = &mut ctx.accounts.oracle_committee;
+    committee.bump = ctx.bumps.oracle_committee;
+    set_committee(committee, members, threshold)?;
+
+    // The topic's AI votes now need attestations from its own committee
+    let topic = &mut ctx.accounts.topic;
+    topic.oracle_committee_active = true;
+    msg!("Oracle committee activated for topic {}", topic.key());
+    Ok(())
+}
+
+pub fn update_topic_oracle_committee(
+    ctx: Context<UpdateTopicOracleCommittee>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+    active: bool,
+) -> Result<()> {
+    set_committee(&mut ctx.accounts.oracle_committee, members, threshold)?;
+
+    let topic = &mut ctx.accounts.topic;
+    topic.oracle_committee_active = active;
+    msg!(
+        "Oracle committee for topic {} active: {}",
+        topic.key(),
+        topic.oracle_committee_active
+    );
+    Ok(())
+}
+
+pub fn submit_oracle_attestation(
+    ctx: Context<SubmitOracleAttestation>,
+    _ai_request_index: u64,
+    decision: VoteChoice,
+) -> Result<()> {
+    let current_timestamp_u64 = Clock::get()?.unix_timestamp as u64;
+    let oracle = ctx.accounts.oracle.key();
+    let committee = &ctx.accounts.oracle_committee;
+    let topic = &ctx.accounts.topic;
+    let ai_request = &mut ctx.accounts.ai_validation_request;
+    let link = &mut ctx.accounts.submission_topic_link;
+
+    // Topics with their own committee only accept it. Topics with their own oracle key
+    // are not decided by the global committee.
+    let expected_committee = if topic.oracle_committee_active {
+        Pubkey::create_program_address(
+            &[
+                b"oracle_committee".as_ref(),
+                topic.key().as_ref(),
+                &[committee.bump],
+            ],
+            ctx.program_id,
+        )
+    } else {
+        require!(
+            topic.oracle_pubkey.is_none(),
+            ErrorCode::InvalidOracleCommittee
+        );
+        Pubkey::create_program_address(
+            &[b"oracle_committee".as_ref(), &[committee.bump]],
+            ctx.program_id,
+        )
+    }
+    .map_err(|_| ErrorCode::InvalidOracleCommittee)?;
+    require_keys_eq!(
+        committee.key(),
+        expected_committee,
+        ErrorCode::InvalidOracleCommittee
+    );
+
+    require!(
+        committee.members.contains(&oracle),
+        ErrorCode::NotCommitteeMember
+    );
+    // Requests are opened by the submission's contributor, who may not judge their own work
+    require!(
+        oracle != ai_request.requester,
+        ErrorCode::SelfVotingNotAllowed
+    );
+    require!(
+        ai_request.status == AiValidationStatus::Pending
+            || ai_request.status == AiValidationStatus::Processing,
+        ErrorCode::InvalidAiRequestStatus
+    );
+    require!(
+        current_timestamp_u64 <= link.reveal_phase_end,
+        ErrorCode::RevealPhaseEnded
+    );
+    require!(
+        !ai_request.attestations.iter().any(|a| a.oracle == oracle),
+        ErrorCode::DuplicateAttestation
+    );
+    // Members removed from the committee may have left attestations behind
+    require!(
+        ai_request.attestations.len() < MAX_ORACLE_COMMITTEE_SIZE,
+        ErrorCode::TooManyCommitteeMembers
+    );
+
+    // Record the attestation and flag disagreement between members
+    if ai_request
+        .attestations
+        .iter()
+        .any(|a| a.decision != decision)
+    {
+        ai_request.disputed = true;
+        msg!(
+            "Oracle committee disagreement on AI request {}",
+            ai_request.request_index
+        );
+    }
+    ai_request
+        .attestations
+        .push(OracleAttestation { oracle, decision });
+
+    // Only attestations from current members count towards the threshold
+    let matching = ai_request
+        .attestations
+        .iter()
+        .filter(|a| a.decision == decision && committee.members.contains(&a.oracle))
+        .count();
+    msg!(
+        "Oracle {} attested {:?} on AI request {} ({}/{} matching)",
+        oracle,
+        decision,
+        ai_request.request_index,
+        matching,
+        committee.threshold
+    );
+
+    if matching >= committee.threshold as usize {
+        // Attestations carry no model provenance; reaching the threshold counts as full confidence
+        ai_request.confidence_bps = BPS_DENOMINATOR as u16;
+        complete_ai_request(ai_request, link, decision)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::contexts::{CreateSessionKey, RevokeSessionKey};
+use crate::data::{SessionKey, MAX_SESSION_DURATION, SESSION_PERMISSION_ALL};
+use crate::error::ErrorCode;
+use crate::helpers::require_user_or_session;
+use anchor_lang::prelude::*;
+
+/// Let `session_signer` act for the user until `expires_at` in the instructions
+/// `permissions` allows
+pub fn create_session_key(
+    ctx: Context<CreateSessionKey>,
+    session_signer: Pubkey,
+    expires_at: u64,
+    permissions: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        expires_at > now && expires_at <= now.saturating_add(MAX_SESSION_DURATION),
+        ErrorCode::InvalidSessionExpiry
+    );
+    require!(
+        permissions != 0 && permissions & !SESSION_PERMISSION_ALL == 0,
+        ErrorCode::InvalidSessionPermissions
+    );
+
+    let session_key = &mut ctx.accounts.session_key;
+    session_key.user = ctx.accounts.user.key();
+    session_key.session_signer = session_signer;
+    session_key.expires_at = expires_at;
+    session_key.permissions = permissions;
+    session_key.bump = ctx.bumps.session_key;
+
+    msg!(
+        "Session key {} created for {} until {} (permissions {:#05b})",
+        session_signer,
+        session_key.user,
+        expires_at,
+        permissions
+    );
+    Ok(())
+}
+
+/// Close a session key, returning its rent to the user
+pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+    msg!(
+        "Session key {} revoked for {}",
+        ctx.accounts.session_key.session_signer,
+        ctx.accounts.session_key.user
+    );
+    Ok(())
+}
+
+/// Checks that `acting_signer` is the user or holds one of their session keys granting
+/// `permission`
+pub(crate) fn authorize_session(
+    user: &Pubkey,
+    acting_signer: &Pubkey,
+    session_key: &Option<Account<SessionKey>>,
+    permission: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    require_user_or_session(user, acting_signer, session_key.as_deref(), permission, now)
+}
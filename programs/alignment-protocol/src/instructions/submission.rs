@@ -1,16 +1,41 @@
 use crate::contexts::{FinalizeSubmission, LinkSubmissionToTopic, SubmitDataToTopic};
-use crate::data::{SubmissionStatus, MAX_DATA_REFERENCE_LENGTH};
+use crate::data::{
+    AiValidationRequest, AiValidationStatus, SubmissionStatus, VoteChoice, BPS_DENOMINATOR,
+    MAX_DATA_REFERENCE_LENGTH, SESSION_PERMISSION_SUBMIT,
+};
 use crate::error::ErrorCode;
+use crate::helpers::{
+    calculate_current_epoch_start, calculate_reward_share, check_topic_eligibility,
+};
+use crate::instructions::fees::pay_finalization_reward;
+use crate::instructions::session::authorize_session;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, MintTo};
+use anchor_spl::token_interface::{self, Burn, MintTo};
 
 // Removed legacy submit_data function
 
-/// Submit data to a specific topic, earn tempAlign, and update UserTopicBalance
+/// Submit data to a specific topic, signed by the contributor or one of their session keys
 pub fn submit_data_to_topic(
     ctx: Context<SubmitDataToTopic>,
     data_reference: String,
     current_submission_index: u64,
+) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.contributor.key(),
+        &ctx.accounts.acting_signer.key(),
+        &ctx.accounts.session_key,
+        SESSION_PERMISSION_SUBMIT,
+    )?;
+    record_submission(ctx, data_reference, current_submission_index)
+}
+
+/// Create the submission, earn tempAlign, and update UserTopicBalance
+///
+/// Callers must have authorized the contributor already.
+pub(crate) fn record_submission(
+    ctx: Context<SubmitDataToTopic>,
+    data_reference: String,
+    current_submission_index: u64,
 ) -> Result<()> {
     // Validate inputs
     if data_reference.len() > MAX_DATA_REFERENCE_LENGTH {
@@ -40,6 +65,51 @@ pub fn submit_data_to_topic(
         return Err(ErrorCode::TopicInactive.into());
     }
 
+    // Enforce the topic's participation rules
+    check_topic_eligibility(
+        topic,
+        &ctx.accounts.contributor.key(),
+        ctx.accounts
+            .contributor_rep_ata
+            .as_ref()
+            .map_or(0, |ata| ata.amount),
+        contributor_profile.accepted_submission_count,
+        ctx.accounts
+            .parent_topic_balance
+            .as_ref()
+            .map(|balance| &***balance),
+    )?;
+
+    // Enforce the topic's per-user rate limits
+    let now = clock.unix_timestamp as u64;
+    let window_end = user_topic_balance
+        .submission_window_start
+        .saturating_add(topic.submission_window_duration);
+    if now >= window_end {
+        user_topic_balance.submission_window_start = now;
+        user_topic_balance.window_submission_count = 0;
+    }
+    if let Some(max_per_window) = topic.max_submissions_per_window {
+        require!(
+            user_topic_balance.window_submission_count < max_per_window,
+            ErrorCode::SubmissionRateLimitExceeded
+        );
+    }
+    if let Some(max_pending) = topic.max_pending_submissions {
+        require!(
+            user_topic_balance.pending_submission_count < max_pending,
+            ErrorCode::TooManyPendingSubmissions
+        );
+    }
+    user_topic_balance.window_submission_count = user_topic_balance
+        .window_submission_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+    user_topic_balance.pending_submission_count = user_topic_balance
+        .pending_submission_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
     // --- Initialize Submission Account ---
     submission.contributor = ctx.accounts.contributor.key();
     submission.timestamp = clock.unix_timestamp as u64;
@@ -66,10 +136,48 @@ pub fn submit_data_to_topic(
     submission_topic_link.no_voting_power = 0;
     submission_topic_link.total_committed_votes = 0; // Correct field name
     submission_topic_link.total_revealed_votes = 0; // Correct field name
+    submission_topic_link.yes_temp_rep = 0;
+    submission_topic_link.no_temp_rep = 0;
+    submission_topic_link.reward_pool = 0;
+    submission_topic_link.reward_pool_claimed = 0;
+    submission_topic_link.completed_ai_requests = 0;
     submission_topic_link.bump = ctx.bumps.submission_topic_link;
 
     // --- Mint Temporary Alignment Tokens ---
-    let tokens_to_mint = state.tokens_to_mint; // Ensure correct field name used if changed from tokens_to_mint_per_submission
+    // Topic override takes precedence over the global default
+    let base_tokens_to_mint = topic.tokens_to_mint.unwrap_or(state.tokens_to_mint);
+
+    // Refill the topic's emission budget if a new epoch has started
+    let current_epoch_start = calculate_current_epoch_start(
+        topic.emission_epoch_start,
+        topic.emission_epoch_duration,
+        now,
+    );
+    if current_epoch_start != topic.emission_epoch_start {
+        topic.emission_epoch_start = current_epoch_start;
+        topic.emission_epoch_minted = 0;
+    }
+
+    // Scale the reward down to what is left of the budget (zero once exhausted)
+    let tokens_to_mint = match topic.emission_budget_per_epoch {
+        Some(budget) => {
+            let remaining = budget.saturating_sub(topic.emission_epoch_minted);
+            if remaining < base_tokens_to_mint {
+                msg!(
+                    "Topic emission budget limits reward to {} of {} tempAlign this epoch",
+                    remaining,
+                    base_tokens_to_mint
+                );
+            }
+            std::cmp::min(base_tokens_to_mint, remaining)
+        }
+        None => base_tokens_to_mint,
+    };
+    topic.emission_epoch_minted = topic
+        .emission_epoch_minted
+        .checked_add(tokens_to_mint)
+        .ok_or(ErrorCode::Overflow)?;
+    submission.temp_align_minted = tokens_to_mint;
     if tokens_to_mint > 0 {
         let state_bump = state.bump;
         let seeds = &[b"state".as_ref(), &[state_bump]];
@@ -87,7 +195,7 @@ pub fn submit_data_to_topic(
             },
             signer,
         );
-        token::mint_to(mint_to_ctx, tokens_to_mint)?;
+        token_interface::mint_to(mint_to_ctx, tokens_to_mint)?;
     }
 
     // --- Update UserTopicBalance ---
@@ -155,6 +263,11 @@ pub fn link_submission_to_topic(ctx: Context<LinkSubmissionToTopic>) -> Result<(
     link.no_voting_power = 0;
     link.total_committed_votes = 0;
     link.total_revealed_votes = 0;
+    link.yes_temp_rep = 0;
+    link.no_temp_rep = 0;
+    link.reward_pool = 0;
+    link.reward_pool_claimed = 0;
+    link.completed_ai_requests = 0;
 
     // Increment the topic's submission count
     let topic = &mut ctx.accounts.topic;
@@ -178,28 +291,114 @@ pub fn link_submission_to_topic(ctx: Context<LinkSubmissionToTopic>) -> Result<(
     Ok(())
 }
 
-pub fn finalize_submission(ctx: Context<FinalizeSubmission>) -> Result<()> {
+pub fn finalize_submission<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeSubmission<'info>>,
+) -> Result<()> {
+    // Tally AI voting power from every completed AI request on the link, passed as
+    // remaining accounts. Requiring all of them stops a caller from cherry-picking verdicts.
+    let link_key = ctx.accounts.submission_topic_link.key();
+    require!(
+        ctx.remaining_accounts.len() as u64
+            == ctx.accounts.submission_topic_link.completed_ai_requests,
+        ErrorCode::AiRequestAccountsMismatch
+    );
+    let confidence_scaling = ctx.accounts.topic.ai_confidence_scaling;
+    let mut ai_yes_power: u64 = 0;
+    let mut ai_no_power: u64 = 0;
+    for (i, request_info) in ctx.remaining_accounts.iter().enumerate() {
+        if ctx.remaining_accounts[..i]
+            .iter()
+            .any(|seen| seen.key() == request_info.key())
+        {
+            return Err(ErrorCode::AiRequestAccountsMismatch.into());
+        }
+        let ai_request: Account<'info, AiValidationRequest> = Account::try_from(request_info)?;
+        require_keys_eq!(
+            ai_request.submission_topic_link,
+            link_key,
+            ErrorCode::MismatchedAiRequestLink
+        );
+        require!(
+            ai_request.status == AiValidationStatus::Completed,
+            ErrorCode::InvalidAiRequestStatus
+        );
+        let power = if confidence_scaling {
+            calculate_reward_share(
+                ai_request.ai_voting_power,
+                ai_request.confidence_bps as u64,
+                BPS_DENOMINATOR,
+            )
+        } else {
+            ai_request.ai_voting_power
+        };
+        match ai_request.ai_decision {
+            Some(VoteChoice::Yes) => {
+                ai_yes_power = ai_yes_power.checked_add(power).ok_or(ErrorCode::Overflow)?
+            }
+            Some(VoteChoice::No) => {
+                ai_no_power = ai_no_power.checked_add(power).ok_or(ErrorCode::Overflow)?
+            }
+            None => return Err(ErrorCode::InvalidAiRequestStatus.into()),
+        }
+    }
+    let ai_weight_bps = ctx.accounts.topic.ai_weight_bps as u64;
+    let weighted_ai_yes = calculate_reward_share(ai_yes_power, ai_weight_bps, BPS_DENOMINATOR);
+    let weighted_ai_no = calculate_reward_share(ai_no_power, ai_weight_bps, BPS_DENOMINATOR);
+
     let link = &mut ctx.accounts.submission_topic_link;
 
-    // Determine if the submission is accepted or rejected
-    let is_accepted = link.yes_voting_power > link.no_voting_power;
+    // Free up a pending slot for the contributor. Saturating because links created by
+    // `link_submission_to_topic` were never counted.
+    let user_topic_balance = &mut ctx.accounts.user_topic_balance;
+    user_topic_balance.pending_submission_count = user_topic_balance
+        .pending_submission_count
+        .saturating_sub(1);
+
+    // Determine if the submission is accepted or rejected (human power plus weighted AI power)
+    let total_yes_power = link
+        .yes_voting_power
+        .checked_add(weighted_ai_yes)
+        .ok_or(ErrorCode::Overflow)?;
+    let total_no_power = link
+        .no_voting_power
+        .checked_add(weighted_ai_no)
+        .ok_or(ErrorCode::Overflow)?;
+    let is_accepted = total_yes_power > total_no_power;
 
     // Update submission status in the link
     if is_accepted {
         link.status = SubmissionStatus::Accepted;
 
+        let contributor_profile = &mut ctx.accounts.contributor_profile;
+        contributor_profile.accepted_submission_count = contributor_profile
+            .accepted_submission_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
         // --- Token Conversion Logic ---
-        // Get the amount of tempAlign potentially eligible for conversion from UserTopicBalance
-        let topic_align_balance = ctx.accounts.user_topic_balance.temp_align_amount;
+        // Get the amount of tempAlign potentially eligible for conversion from UserTopicBalance.
+        // Faucet and admin-minted tempAlign is never converted.
+        let topic_align_balance = ctx
+            .accounts
+            .user_topic_balance
+            .temp_align_amount
+            .saturating_sub(
+                ctx.accounts
+                    .user_topic_balance
+                    .unconvertible_temp_align_amount,
+            );
 
         // Determine conversion amount - use the balance from UserTopicBalance
-        // Cap it at the max mintable amount (state.tokens_to_mint) if needed.
-        let conversion_amount =
-            std::cmp::min(ctx.accounts.state.tokens_to_mint, topic_align_balance);
+        // Cap it at what the submission actually minted, which the emission budget may have
+        // scaled below the topic's tokens_to_mint.
+        let conversion_amount = std::cmp::min(
+            ctx.accounts.submission.temp_align_minted,
+            topic_align_balance,
+        );
 
         // Check if there are any tokens to convert
         if conversion_amount == 0 {
-            // No tokens were earned in this topic (or state.tokens_to_mint is 0), so nothing to convert.
+            // No tokens were earned in this topic (or none were minted), so nothing to convert.
             msg!(
                 "Submission accepted, but no tempAlign tokens found in UserTopicBalance for topic {} to convert.",
                 ctx.accounts.topic.key() // Use topic key for logging
@@ -230,7 +429,7 @@ pub fn finalize_submission(ctx: Context<FinalizeSubmission>) -> Result<()> {
             )
             .with_signer(signer); // Sign with state PDA signer
 
-            token::burn(burn_cpi_ctx, conversion_amount)?;
+            token_interface::burn(burn_cpi_ctx, conversion_amount)?;
 
             // 2. Mint permanent Align tokens to contributor's user-owned ATA
             // (Minting logic uses state PDA signer)
@@ -244,7 +443,7 @@ pub fn finalize_submission(ctx: Context<FinalizeSubmission>) -> Result<()> {
             )
             .with_signer(signer); // Sign with state PDA signer
 
-            token::mint_to(mint_cpi_ctx, conversion_amount)?;
+            token_interface::mint_to(mint_cpi_ctx, conversion_amount)?;
 
             // Update the contributor's UserTopicBalance by reducing tempAlign amount
             let user_topic_balance = &mut ctx.accounts.user_topic_balance;
@@ -271,6 +470,25 @@ pub fn finalize_submission(ctx: Context<FinalizeSubmission>) -> Result<()> {
         msg!("Submission rejected. No token conversion performed.");
     }
 
+    // --- Validator Reward Pool ---
+    // The tempRep staked by the losing side is burned in finalize_vote; that amount plus the
+    // protocol emission is shared by correct validators pro rata to their voting power.
+    // The emission is only added when a validator revealed on the winning side to claim it.
+    let (losing_temp_rep, winning_voting_power) = if link.status == SubmissionStatus::Accepted {
+        (link.no_temp_rep, link.yes_voting_power)
+    } else {
+        (link.yes_temp_rep, link.no_voting_power)
+    };
+    let emission = if winning_voting_power > 0 {
+        ctx.accounts.state.validator_reward_emission
+    } else {
+        0
+    };
+    link.reward_pool = losing_temp_rep
+        .checked_add(emission)
+        .ok_or(ErrorCode::Overflow)?;
+    link.reward_pool_claimed = 0;
+
     // Log the voting results (applies to both accepted/rejected)
     msg!(
         "Finalized submission {} in topic '{}' with status: {:?}",
@@ -279,10 +497,22 @@ pub fn finalize_submission(ctx: Context<FinalizeSubmission>) -> Result<()> {
         link.status
     );
     msg!(
-        "Final vote tally: {} YES vs {} NO",
+        "Final vote tally: {} YES vs {} NO (human {} / {}, weighted AI {} / {})",
+        total_yes_power,
+        total_no_power,
         link.yes_voting_power,
-        link.no_voting_power
+        link.no_voting_power,
+        weighted_ai_yes,
+        weighted_ai_no
     );
-
-    Ok(())
+    msg!("Validator reward pool: {} Rep", link.reward_pool);
+
+    pay_finalization_reward(
+        ctx.accounts.state.finalization_reward,
+        ctx.accounts.state.finalization_reward_min_voting_power,
+        1,
+        &ctx.accounts.submission_topic_link,
+        ctx.accounts.fee_vault.as_ref(),
+        &ctx.accounts.authority.to_account_info(),
+    )
 }
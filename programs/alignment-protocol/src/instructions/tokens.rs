@@ -1,11 +1,13 @@
 use crate::contexts::{
-    CreateUserAta, CreateUserTempAlignAccount, CreateUserTempRepAccount, StakeTopicSpecificTokens,
+    AdminMint, CreateUserAta, CreateUserTempAlignAccount, CreateUserTempRepAccount,
+    StakeTopicSpecificTokens,
 };
+use crate::data::{AdminMinted, TokenType, MAX_ADMIN_MINT_REASON_LENGTH};
 use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::{create, Create},
-    token::{self, Burn, MintTo},
+    token_interface::{self, Burn, MintTo},
 };
 
 pub fn create_user_ata(ctx: Context<CreateUserAta>) -> Result<()> {
@@ -118,13 +120,24 @@ pub fn create_user_temp_rep_account(ctx: Context<CreateUserTempRepAccount>) -> R
 
 // Removed legacy stake_alignment_tokens function
 
-/// Stakes tempAlign tokens for a specific topic to earn topic-specific tempRep
-/// Burns from the protocol-owned tempAlign PDA and Mints to the protocol-owned tempRep PDA.
-/// Updates the balances tracked in the UserTopicBalance account.
+/// Stakes tempAlign tokens for a specific topic, signed by the user
 pub fn stake_topic_specific_tokens(
     ctx: Context<StakeTopicSpecificTokens>,
     amount: u64,
 ) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.acting_signer.key(),
+        ctx.accounts.user.key(),
+        ErrorCode::UnauthorizedActingSigner
+    );
+    record_stake(ctx, amount)
+}
+
+/// Stakes tempAlign tokens for a specific topic to earn topic-specific tempRep
+/// Burns from the protocol-owned tempAlign PDA and Mints to the protocol-owned tempRep PDA.
+/// Updates the balances tracked in the UserTopicBalance account.
+/// Callers must have authorized the user already.
+pub(crate) fn record_stake(ctx: Context<StakeTopicSpecificTokens>, amount: u64) -> Result<()> {
     // Validate the stake amount
     if amount == 0 {
         // Use existing error code from error.rs
@@ -168,7 +181,7 @@ pub fn stake_topic_specific_tokens(
         },
         signer, // Provide state PDA seeds as signer
     );
-    token::burn(burn_cpi_ctx, amount)?;
+    token_interface::burn(burn_cpi_ctx, amount)?;
 
     // 4. Mint temporary reputation tokens into the protocol-owned PDA
     let mint_cpi_ctx = CpiContext::new_with_signer(
@@ -180,7 +193,7 @@ pub fn stake_topic_specific_tokens(
         },
         signer, // Provide state PDA seeds as signer
     );
-    token::mint_to(mint_cpi_ctx, amount)?;
+    token_interface::mint_to(mint_cpi_ctx, amount)?;
 
     // 5. Update the topic-specific balances in the UserTopicBalance account
     // Decrease tempAlign for this topic
@@ -188,6 +201,10 @@ pub fn stake_topic_specific_tokens(
         .temp_align_amount
         .checked_sub(amount)
         .ok_or(ErrorCode::Overflow)?;
+    // Faucet and admin-minted tempAlign is staked first, so what remains stays convertible
+    user_topic_balance.unconvertible_temp_align_amount = user_topic_balance
+        .unconvertible_temp_align_amount
+        .saturating_sub(amount);
 
     // Increase tempRep for this topic
     user_topic_balance.temp_rep_amount = user_topic_balance
@@ -210,3 +227,133 @@ pub fn stake_topic_specific_tokens(
 
     Ok(())
 }
+
+/// Mint protocol tokens to a user on the authority's behalf
+///
+/// Each mint has a lifetime cap in `State`. Temporary tokens land in the recipient's
+/// protocol-owned temp account and are credited to their `UserTopicBalance`; permanent tokens
+/// may go to any account the recipient owns. Every call emits `AdminMinted` with the reason.
+pub fn admin_mint(
+    ctx: Context<AdminMint>,
+    token_type: TokenType,
+    amount: u64,
+    reason: String,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::ZeroAdminMintAmount);
+    require!(!reason.trim().is_empty(), ErrorCode::EmptyAdminMintReason);
+    require!(
+        reason.len() <= MAX_ADMIN_MINT_REASON_LENGTH,
+        ErrorCode::AdminMintReasonTooLong
+    );
+
+    let state = &ctx.accounts.state;
+    let expected_mint = match token_type {
+        TokenType::TempAlign => state.temp_align_mint,
+        TokenType::Align => state.align_mint,
+        TokenType::TempRep => state.temp_rep_mint,
+        TokenType::Rep => state.rep_mint,
+    };
+    require!(
+        ctx.accounts.mint.key() == expected_mint,
+        ErrorCode::TokenMintMismatch
+    );
+
+    let index = token_type as usize;
+    let total_minted = state.admin_minted[index]
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        total_minted <= state.admin_mint_caps[index],
+        ErrorCode::AdminMintCapExceeded
+    );
+
+    let recipient = ctx.accounts.recipient.key();
+    let recipient_token_account = ctx.accounts.recipient_token_account.key();
+    let temp_account_seed: Option<&[u8]> = match token_type {
+        TokenType::TempAlign => Some(b"user_temp_align"),
+        TokenType::TempRep => Some(b"user_temp_rep"),
+        TokenType::Align | TokenType::Rep => None,
+    };
+
+    let topic = if let Some(seed) = temp_account_seed {
+        // Temporary tokens only count inside the protocol-owned temp account and a topic balance
+        let (expected_account, _) =
+            Pubkey::find_program_address(&[seed, recipient.as_ref()], ctx.program_id);
+        require!(
+            recipient_token_account == expected_account,
+            ErrorCode::InvalidTokenAccount
+        );
+
+        let user_topic_balance = ctx
+            .accounts
+            .user_topic_balance
+            .as_deref_mut()
+            .ok_or(ErrorCode::MissingUserTopicBalance)?;
+        require!(
+            user_topic_balance.user == recipient,
+            ErrorCode::UserAccountMismatch
+        );
+        if token_type == TokenType::TempAlign {
+            user_topic_balance.temp_align_amount = user_topic_balance
+                .temp_align_amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::Overflow)?;
+            // Like faucet tempAlign, it was not earned by a submission, so it never converts
+            user_topic_balance.unconvertible_temp_align_amount = user_topic_balance
+                .unconvertible_temp_align_amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::Overflow)?;
+        } else {
+            user_topic_balance.temp_rep_amount = user_topic_balance
+                .temp_rep_amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        Some(user_topic_balance.topic)
+    } else {
+        require!(
+            ctx.accounts.recipient_token_account.owner == recipient,
+            ErrorCode::InvalidTokenAccount
+        );
+        None
+    };
+
+    let state_bump = ctx.accounts.state.bump;
+    let seeds = &[b"state".as_ref(), &[state_bump]];
+    let signer = &[&seeds[..]];
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.state.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.state.admin_minted[index] = total_minted;
+
+    msg!(
+        "Admin minted {} {:?} to {} ({} of cap {}): {}",
+        amount,
+        token_type,
+        recipient,
+        total_minted,
+        ctx.accounts.state.admin_mint_caps[index],
+        reason
+    );
+    emit!(AdminMinted {
+        token_type,
+        recipient,
+        recipient_token_account,
+        topic,
+        amount,
+        total_minted,
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -1,6 +1,11 @@
-use crate::contexts::CreateTopic;
-use crate::data::{MAX_TOPIC_DESCRIPTION_LENGTH, MAX_TOPIC_NAME_LENGTH};
+use crate::contexts::{CreateTopic, GrantTopicRole, RevokeTopicRole, UpdateTopic};
+use crate::data::{
+    BPS_DENOMINATOR, DEFAULT_AI_WEIGHT_BPS, DEFAULT_EMISSION_EPOCH_DURATION,
+    DEFAULT_SUBMISSION_WINDOW_DURATION, MAX_TOPIC_DESCRIPTION_LENGTH, MAX_TOPIC_NAME_LENGTH,
+    TOPIC_ROLE_ALL, TOPIC_ROLE_ORACLE,
+};
 use crate::error::ErrorCode;
+use crate::helpers::require_topic_admin;
 use anchor_lang::prelude::*;
 
 pub fn create_topic(
@@ -34,6 +39,24 @@ pub fn create_topic(
     topic.creator = creator.key();
     topic.submission_count = 0;
     topic.is_active = true;
+    topic.tokens_to_mint = None;
+    topic.rep_mint_ratio_bps = None;
+    // Unlimited until the authority or the topic's admins set a budget
+    topic.emission_budget_per_epoch = None;
+    topic.emission_epoch_duration = DEFAULT_EMISSION_EPOCH_DURATION;
+    topic.emission_epoch_start = Clock::get()?.unix_timestamp as u64;
+    topic.emission_epoch_minted = 0;
+    topic.min_rep_balance = 0;
+    topic.min_accepted_submissions = 0;
+    topic.parent_topic = None;
+    topic.min_parent_topic_rep = 0;
+    topic.max_submissions_per_window = None;
+    topic.submission_window_duration = DEFAULT_SUBMISSION_WINDOW_DURATION;
+    topic.max_pending_submissions = None;
+    topic.ai_weight_bps = DEFAULT_AI_WEIGHT_BPS;
+    topic.ai_confidence_scaling = false;
+    topic.oracle_pubkey = None;
+    topic.oracle_committee_active = false;
     topic.bump = ctx.bumps.topic;
 
     // Set the commit and reveal phase durations - use provided values or defaults from state
@@ -62,24 +85,44 @@ pub fn create_topic(
     Ok(())
 }
 
-/// Update mutable fields of an existing topic (phase durations, activity flag).
-/// The signer must be either the protocol authority (state.authority) or the
-/// original topic creator (topic.authority).
+/// Update mutable fields of an existing topic (phase durations, activity flag,
+/// reward overrides). The signer must be the protocol authority (state.authority),
+/// the original topic creator (topic.creator) or a topic moderator.
+///
+/// The reward overrides take a nested option: `None` leaves the field unchanged,
+/// `Some(None)` clears the override so the global default applies again. Only the
+/// protocol authority may change them, and they can lower but never raise rewards:
+/// `tokens_to_mint` is capped at the global value and `rep_mint_ratio_bps` at 1:1.
 pub fn update_topic(
-    ctx: Context<crate::contexts::UpdateTopic>,
+    ctx: Context<UpdateTopic>,
     commit_phase_duration: Option<u64>,
     reveal_phase_duration: Option<u64>,
     is_active: Option<bool>,
+    tokens_to_mint: Option<Option<u64>>,
+    rep_mint_ratio_bps: Option<Option<u16>>,
 ) -> Result<()> {
     let state = &ctx.accounts.state;
     let topic = &mut ctx.accounts.topic;
     let signer = ctx.accounts.authority.key();
 
-    // Authorisation check
-    require!(
-        signer == state.authority || signer == topic.creator,
-        ErrorCode::InvalidAuthority
-    );
+    require_topic_admin(state, topic, ctx.accounts.topic_role.as_deref(), &signer)?;
+
+    // Topics are permissionless, so reward overrides would otherwise let anyone mint at will
+    if tokens_to_mint.is_some() || rep_mint_ratio_bps.is_some() {
+        require!(signer == state.authority, ErrorCode::InvalidAuthority);
+    }
+    if let Some(Some(new_tokens_to_mint)) = tokens_to_mint {
+        require!(
+            new_tokens_to_mint <= state.tokens_to_mint,
+            ErrorCode::TopicTokensToMintTooHigh
+        );
+    }
+    if let Some(Some(new_ratio)) = rep_mint_ratio_bps {
+        require!(
+            new_ratio as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidRepMintRatio
+        );
+    }
 
     // Apply updates if provided
     if let Some(new_commit) = commit_phase_duration {
@@ -91,6 +134,12 @@ pub fn update_topic(
     if let Some(active) = is_active {
         topic.is_active = active;
     }
+    if let Some(new_tokens_to_mint) = tokens_to_mint {
+        topic.tokens_to_mint = new_tokens_to_mint;
+    }
+    if let Some(new_ratio) = rep_mint_ratio_bps {
+        topic.rep_mint_ratio_bps = new_ratio;
+    }
 
     msg!("Updated topic {} by {}", topic.key(), signer);
     msg!(
@@ -99,6 +148,265 @@ pub fn update_topic(
         topic.reveal_phase_duration,
         topic.is_active
     );
+    msg!(
+        "tokens_to_mint = {:?} (global {}), rep_mint_ratio_bps = {:?}",
+        topic.tokens_to_mint,
+        state.tokens_to_mint,
+        topic.rep_mint_ratio_bps
+    );
+
+    Ok(())
+}
+
+/// Set a topic's per-epoch tempAlign emission budget.
+/// The signer must be the protocol authority, the topic creator or a topic moderator,
+/// but only the authority may raise the budget, remove it or shorten the epoch.
+///
+/// `emission_budget_per_epoch = None` removes the cap. `emission_epoch_duration = None`
+/// keeps the current epoch length. Tokens already minted in the current epoch still
+/// count against the new budget.
+pub fn update_topic_emission_budget(
+    ctx: Context<UpdateTopic>,
+    emission_budget_per_epoch: Option<u64>,
+    emission_epoch_duration: Option<u64>,
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let topic = &mut ctx.accounts.topic;
+    let signer = ctx.accounts.authority.key();
+
+    require_topic_admin(state, topic, ctx.accounts.topic_role.as_deref(), &signer)?;
+
+    // Creators and moderators may only tighten the budget of their own topic
+    if signer != state.authority {
+        let lowers_budget = match (emission_budget_per_epoch, topic.emission_budget_per_epoch) {
+            (Some(new_budget), Some(current)) => new_budget <= current,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        let keeps_epoch_length =
+            emission_epoch_duration.unwrap_or(u64::MAX) >= topic.emission_epoch_duration;
+        require!(
+            lowers_budget && keeps_epoch_length,
+            ErrorCode::InvalidAuthority
+        );
+    }
+
+    if let Some(new_duration) = emission_epoch_duration {
+        require!(new_duration > 0, ErrorCode::InvalidEmissionEpochDuration);
+        topic.emission_epoch_duration = new_duration;
+    }
+    topic.emission_budget_per_epoch = emission_budget_per_epoch;
+
+    msg!(
+        "Updated emission budget for topic {} by {}",
+        topic.key(),
+        signer
+    );
+    msg!(
+        "emission_budget_per_epoch = {:?}, emission_epoch_duration = {}, minted this epoch = {}",
+        topic.emission_budget_per_epoch,
+        topic.emission_epoch_duration,
+        topic.emission_epoch_minted
+    );
 
     Ok(())
 }
+
+/// Set a topic's participation rules, enforced by `initialize_user_topic_balance`,
+/// `submit_data_to_topic` and `commit_vote`.
+/// The signer must be the protocol authority, the topic creator or a topic moderator.
+///
+/// Each argument left as `None` keeps its current value; `parent_topic = Some(None)`
+/// removes the parent-topic requirement.
+pub fn update_topic_eligibility(
+    ctx: Context<UpdateTopic>,
+    min_rep_balance: Option<u64>,
+    min_accepted_submissions: Option<u64>,
+    parent_topic: Option<Option<Pubkey>>,
+    min_parent_topic_rep: Option<u64>,
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let topic = &mut ctx.accounts.topic;
+    let signer = ctx.accounts.authority.key();
+
+    require_topic_admin(state, topic, ctx.accounts.topic_role.as_deref(), &signer)?;
+
+    if let Some(parent) = parent_topic {
+        require!(parent != Some(topic.key()), ErrorCode::InvalidParentTopic);
+        topic.parent_topic = parent;
+    }
+    if let Some(min_rep) = min_rep_balance {
+        topic.min_rep_balance = min_rep;
+    }
+    if let Some(min_accepted) = min_accepted_submissions {
+        topic.min_accepted_submissions = min_accepted;
+    }
+    if let Some(min_parent_rep) = min_parent_topic_rep {
+        topic.min_parent_topic_rep = min_parent_rep;
+    }
+
+    msg!(
+        "Updated eligibility rules for topic {} by {}",
+        topic.key(),
+        signer
+    );
+    msg!(
+        "min_rep_balance = {}, min_accepted_submissions = {}, parent_topic = {:?}, min_parent_topic_rep = {}",
+        topic.min_rep_balance,
+        topic.min_accepted_submissions,
+        topic.parent_topic,
+        topic.min_parent_topic_rep
+    );
+
+    Ok(())
+}
+
+/// Set how much AI voting power counts in `finalize_submission`, in basis points of the
+/// raw AI power (0 = AI verdicts are ignored, 10000 = same weight as human voting power).
+/// With `ai_confidence_scaling`, each verdict's power is also scaled by its `confidence_bps`.
+/// The signer must be the protocol authority, the topic creator or a topic moderator.
+pub fn update_topic_ai_weight(
+    ctx: Context<UpdateTopic>,
+    ai_weight_bps: u16,
+    ai_confidence_scaling: bool,
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let topic = &mut ctx.accounts.topic;
+    let signer = ctx.accounts.authority.key();
+
+    require_topic_admin(state, topic, ctx.accounts.topic_role.as_deref(), &signer)?;
+
+    require!(
+        ai_weight_bps as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidAiWeight
+    );
+    topic.ai_weight_bps = ai_weight_bps;
+    topic.ai_confidence_scaling = ai_confidence_scaling;
+
+    msg!(
+        "Updated AI weight for topic {} by {}: {} bps (confidence scaling: {})",
+        topic.key(),
+        signer,
+        topic.ai_weight_bps,
+        topic.ai_confidence_scaling
+    );
+
+    Ok(())
+}
+
+/// Assign the oracle that answers the topic's AI requests (None = fall back to the global
+/// oracle). Only the protocol authority may assign oracles: a creator-chosen oracle could
+/// settle AI requests on the creator's own submissions.
+pub fn update_topic_oracle(ctx: Context<UpdateTopic>, oracle_pubkey: Option<Pubkey>) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let topic = &mut ctx.accounts.topic;
+    let signer = ctx.accounts.authority.key();
+
+    require!(signer == state.authority, ErrorCode::InvalidAuthority);
+
+    topic.oracle_pubkey = oracle_pubkey;
+
+    msg!(
+        "Updated oracle for topic {} by {}: {:?}",
+        topic.key(),
+        signer,
+        topic.oracle_pubkey
+    );
+
+    Ok(())
+}
+
+/// Set a topic's per-user submission rate limits.
+/// The signer must be the protocol authority, the topic creator or a topic moderator.
+///
+/// `max_submissions_per_window = None` and `max_pending_submissions = None` remove
+/// the respective limit. `submission_window_duration = None` keeps the current window length.
+pub fn update_topic_rate_limits(
+    ctx: Context<UpdateTopic>,
+    max_submissions_per_window: Option<u64>,
+    submission_window_duration: Option<u64>,
+    max_pending_submissions: Option<u64>,
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let topic = &mut ctx.accounts.topic;
+    let signer = ctx.accounts.authority.key();
+
+    require_topic_admin(state, topic, ctx.accounts.topic_role.as_deref(), &signer)?;
+
+    if let Some(new_duration) = submission_window_duration {
+        require!(new_duration > 0, ErrorCode::InvalidSubmissionWindow);
+        topic.submission_window_duration = new_duration;
+    }
+    topic.max_submissions_per_window = max_submissions_per_window;
+    topic.max_pending_submissions = max_pending_submissions;
+
+    msg!(
+        "Updated submission rate limits for topic {} by {}",
+        topic.key(),
+        signer
+    );
+    msg!(
+        "max_submissions_per_window = {:?}, submission_window_duration = {}, max_pending_submissions = {:?}",
+        topic.max_submissions_per_window,
+        topic.submission_window_duration,
+        topic.max_pending_submissions
+    );
+
+    Ok(())
+}
+
+/// Grant topic-scoped roles (`TOPIC_ROLE_*` bitmask) to a wallet.
+/// Only the protocol authority or the topic creator may grant roles, and only the
+/// authority may grant `TOPIC_ROLE_ORACLE`.
+pub fn grant_topic_role(ctx: Context<GrantTopicRole>, roles: u8) -> Result<()> {
+    let signer = ctx.accounts.authority.key();
+    require!(
+        signer == ctx.accounts.state.authority || signer == ctx.accounts.topic.creator,
+        ErrorCode::InvalidAuthority
+    );
+    require!(
+        roles != 0 && roles & !TOPIC_ROLE_ALL == 0,
+        ErrorCode::InvalidTopicRole
+    );
+    if roles & TOPIC_ROLE_ORACLE != 0 {
+        require!(
+            signer == ctx.accounts.state.authority,
+            ErrorCode::InvalidAuthority
+        );
+    }
+
+    let topic_role = &mut ctx.accounts.topic_role;
+    topic_role.topic = ctx.accounts.topic.key();
+    topic_role.holder = ctx.accounts.holder.key();
+    topic_role.roles = roles;
+    topic_role.granted_by = signer;
+    topic_role.bump = ctx.bumps.topic_role;
+
+    msg!(
+        "Granted roles {:#04b} on topic {} to {} (by {})",
+        roles,
+        topic_role.topic,
+        topic_role.holder,
+        signer
+    );
+    Ok(())
+}
+
+/// Revoke all of a wallet's topic-scoped roles by closing its TopicRole account.
+/// Only the protocol authority or the topic creator may revoke roles.
+pub fn revoke_topic_role(ctx: Context<RevokeTopicRole>) -> Result<()> {
+    let signer = ctx.accounts.authority.key();
+    require!(
+        signer == ctx.accounts.state.authority || signer == ctx.accounts.topic.creator,
+        ErrorCode::InvalidAuthority
+    );
+
+    msg!(
+        "Revoked roles {:#04b} on topic {} from {} (by {})",
+        ctx.accounts.topic_role.roles,
+        ctx.accounts.topic.key(),
+        ctx.accounts.topic_role.holder,
+        signer
+    );
+    Ok(())
+}
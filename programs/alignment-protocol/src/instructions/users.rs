@@ -1,5 +1,17 @@
-use crate::contexts::{CreateUserProfile, InitializeUserTopicBalance};
+use crate::contexts::{CreateUserProfile, InitializeUserTopicBalance, OnboardUser};
+use crate::data::UserProfile;
+use crate::error::ErrorCode;
+use crate::helpers::{check_topic_eligibility, create_pda_account};
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{create_idempotent, Create};
+use anchor_spl::token_interface::{
+    self,
+    spl_token_2022::{
+        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        state::{Account as TokenAccountState, Mint as MintState},
+    },
+    InitializeAccount3,
+};
 
 // Re-export other user-related instructions if moved here
 // pub use crate::instructions::topics::create_user_profile; // Example
@@ -12,6 +24,7 @@ pub fn create_user_profile(ctx: Context<CreateUserProfile>) -> Result<()> {
     user_profile.user_temp_rep_account = Pubkey::default();
     user_profile.user_align_ata = Pubkey::default();
     user_profile.user_rep_ata = Pubkey::default();
+    user_profile.accepted_submission_count = 0;
     user_profile.bump = ctx.bumps.user_profile;
 
     msg!("Created user profile for {}", ctx.accounts.user.key());
@@ -19,12 +32,30 @@ pub fn create_user_profile(ctx: Context<CreateUserProfile>) -> Result<()> {
 }
 
 pub fn initialize_user_topic_balance(ctx: Context<InitializeUserTopicBalance>) -> Result<()> {
+    // Enforce the topic's participation rules
+    check_topic_eligibility(
+        &ctx.accounts.topic,
+        &ctx.accounts.user.key(),
+        ctx.accounts
+            .user_rep_ata
+            .as_ref()
+            .map_or(0, |ata| ata.amount),
+        ctx.accounts.user_profile.accepted_submission_count,
+        ctx.accounts.parent_topic_balance.as_deref(),
+    )?;
+
     let user_topic_balance = &mut ctx.accounts.user_topic_balance;
     user_topic_balance.user = ctx.accounts.user.key();
     user_topic_balance.topic = ctx.accounts.topic.key();
     user_topic_balance.temp_align_amount = 0;
     user_topic_balance.temp_rep_amount = 0;
     user_topic_balance.locked_temp_rep_amount = 0;
+    user_topic_balance.rep_earned = 0;
+    user_topic_balance.submission_window_start = 0;
+    user_topic_balance.window_submission_count = 0;
+    user_topic_balance.pending_submission_count = 0;
+    user_topic_balance.user_ai_request_count = 0;
+    user_topic_balance.unconvertible_temp_align_amount = 0;
     user_topic_balance.bump = ctx.bumps.user_topic_balance;
 
     msg!(
@@ -35,4 +66,120 @@ pub fn initialize_user_topic_balance(ctx: Context<InitializeUserTopicBalance>) -
     Ok(())
 }
 
-// Make sure to declare this new instruction in lib.rs and mod.rs
+/// Creates everything a new user needs, skipping whatever already exists
+///
+/// Equivalent to `create_user_profile`, `create_user_temp_align_account`,
+/// `create_user_temp_rep_account` and `create_user_ata` for Align and Rep, but in one
+/// instruction so the user is never left half set up.
+pub fn onboard_user(ctx: Context<OnboardUser>) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let profile_info = ctx.accounts.user_profile.to_account_info();
+
+    let mut profile = if profile_info.data_is_empty() {
+        create_pda_account(
+            &payer,
+            &profile_info,
+            UserProfile::SPACE,
+            ctx.program_id,
+            &[b"user_profile", user.as_ref(), &[ctx.bumps.user_profile]],
+            &system_program,
+        )?;
+        msg!("Created user profile for {}", user);
+        UserProfile {
+            user,
+            user_submission_count: 0,
+            user_temp_align_account: Pubkey::default(),
+            user_temp_rep_account: Pubkey::default(),
+            user_align_ata: Pubkey::default(),
+            user_rep_ata: Pubkey::default(),
+            accepted_submission_count: 0,
+            bump: ctx.bumps.user_profile,
+        }
+    } else {
+        require_keys_eq!(
+            *profile_info.owner,
+            *ctx.program_id,
+            ErrorCode::InvalidUserProfile
+        );
+        let profile = UserProfile::try_deserialize(&mut &profile_info.try_borrow_data()?[..])?;
+        require_keys_eq!(profile.user, user, ErrorCode::UserAccountMismatch);
+        profile
+    };
+
+    for (token_account, mint, seed, bump) in [
+        (
+            &ctx.accounts.user_temp_align_account,
+            &ctx.accounts.temp_align_mint,
+            b"user_temp_align".as_ref(),
+            ctx.bumps.user_temp_align_account,
+        ),
+        (
+            &ctx.accounts.user_temp_rep_account,
+            &ctx.accounts.temp_rep_mint,
+            b"user_temp_rep".as_ref(),
+            ctx.bumps.user_temp_rep_account,
+        ),
+    ] {
+        if !token_account.data_is_empty() {
+            continue;
+        }
+        let mint_info = mint.to_account_info();
+        let space = {
+            let mint_data = mint_info.try_borrow_data()?;
+            let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+            let required_extensions = ExtensionType::get_required_init_account_extensions(
+                &mint_state.get_extension_types()?,
+            );
+            ExtensionType::try_calculate_account_len::<TokenAccountState>(&required_extensions)?
+        };
+        create_pda_account(
+            &payer,
+            &token_account.to_account_info(),
+            space,
+            &ctx.accounts.token_program.key(),
+            &[seed, user.as_ref(), &[bump]],
+            &system_program,
+        )?;
+        token_interface::initialize_account3(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            InitializeAccount3 {
+                account: token_account.to_account_info(),
+                mint: mint_info,
+                authority: ctx.accounts.state.to_account_info(),
+            },
+        ))?;
+        msg!(
+            "Created protocol-owned token account {} for user {}",
+            token_account.key(),
+            user
+        );
+    }
+
+    for (ata, mint) in [
+        (&ctx.accounts.user_align_ata, &ctx.accounts.align_mint),
+        (&ctx.accounts.user_rep_ata, &ctx.accounts.rep_mint),
+    ] {
+        create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: payer.clone(),
+                associated_token: ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+                mint: mint.to_account_info(),
+                system_program: system_program.clone(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+    }
+
+    profile.user_temp_align_account = ctx.accounts.user_temp_align_account.key();
+    profile.user_temp_rep_account = ctx.accounts.user_temp_rep_account.key();
+    profile.user_align_ata = ctx.accounts.user_align_ata.key();
+    profile.user_rep_ata = ctx.accounts.user_rep_ata.key();
+    profile.try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])?;
+
+    msg!("Onboarded user {}", user);
+    Ok(())
+}
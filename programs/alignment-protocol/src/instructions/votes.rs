@@ -1,16 +1,62 @@
-use crate::contexts::{CommitVote, FinalizeVote, RevealVote, SetVotingPhases};
-use crate::data::{SubmissionStatus, VoteChoice};
+use crate::contexts::{
+    CommitVote, FinalizeVote, FinalizeVotesBatch, RevealVote, SetVotingPhases, SettleUnrevealedVote,
+};
+use crate::data::{
+    DelegatedStake, Delegation, State, SubmissionStatus, SubmissionTopicLink, Topic,
+    UserTopicBalance, VoteChoice, VoteCommit, BPS_DENOMINATOR, DEFAULT_REP_MINT_RATIO_BPS,
+    MAX_DELEGATIONS_PER_VOTE, SESSION_PERMISSION_COMMIT_VOTE, SESSION_PERMISSION_REVEAL_VOTE,
+    TOPIC_ROLE_MODERATOR, TOPIC_ROLE_PHASE_MANAGER,
+};
 use crate::error::ErrorCode;
-use crate::helpers::calculate_quadratic_voting_power;
+use crate::helpers::{
+    calculate_quadratic_voting_power, calculate_rep_from_temp_rep, calculate_reward_share,
+    check_topic_eligibility, has_topic_role,
+};
+use crate::instructions::fees::pay_finalization_reward;
+use crate::instructions::session::authorize_session;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, MintTo};
+use anchor_spl::token_interface::{self, Burn, MintTo, TokenAccount};
 use sha2::{Digest, Sha256};
 
-pub fn commit_vote(
-    ctx: Context<CommitVote>,
+/// Remaining accounts settling one delegation: the delegation, the delegator's tempRep
+/// account, their Rep ATA and their topic balance
+const DELEGATION_SETTLEMENT_ACCOUNTS: usize = 4;
+
+/// Commit a vote, signed by the validator or one of their session keys
+pub fn commit_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CommitVote<'info>>,
     vote_hash: [u8; 32],
     temp_rep_amount: u64,
     perm_rep_amount: u64,
+    delegation_amounts: Vec<u64>,
+) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.validator.key(),
+        &ctx.accounts.acting_signer.key(),
+        &ctx.accounts.session_key,
+        SESSION_PERMISSION_COMMIT_VOTE,
+    )?;
+    record_vote_commit(
+        ctx,
+        vote_hash,
+        temp_rep_amount,
+        perm_rep_amount,
+        delegation_amounts,
+    )
+}
+
+/// Record a vote commitment, drawing on any delegations passed as remaining accounts
+///
+/// Each remaining account must be a `Delegation` to this validator in this topic.
+/// `delegation_amounts` holds the tempRep to lock from each delegation, in the same order;
+/// when empty, the full unlocked amount of each delegation is locked into the vote. Callers
+/// must have authorized the validator already.
+pub(crate) fn record_vote_commit<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CommitVote<'info>>,
+    vote_hash: [u8; 32],
+    temp_rep_amount: u64,
+    perm_rep_amount: u64,
+    delegation_amounts: Vec<u64>,
 ) -> Result<()> {
     // Get current time to validate voting window
     let current_time = Clock::get()?.unix_timestamp as u64;
@@ -25,8 +71,94 @@ pub fn commit_vote(
         return Err(ErrorCode::CommitPhaseEnded.into());
     }
 
+    // Enforce the topic's participation rules
+    check_topic_eligibility(
+        &ctx.accounts.topic,
+        &ctx.accounts.validator.key(),
+        ctx.accounts.validator_rep_ata.amount,
+        ctx.accounts.user_profile.accepted_submission_count,
+        ctx.accounts.parent_topic_balance.as_deref(),
+    )?;
+
+    // Draw on delegated tempRep passed in as remaining accounts
+    if ctx.remaining_accounts.len() > MAX_DELEGATIONS_PER_VOTE {
+        return Err(ErrorCode::TooManyDelegations.into());
+    }
+    if !delegation_amounts.is_empty() && delegation_amounts.len() != ctx.remaining_accounts.len() {
+        return Err(ErrorCode::DelegationAccountsMismatch.into());
+    }
+    let mut delegations: Vec<DelegatedStake> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut delegated_temp_rep_amount: u64 = 0;
+    for (i, delegation_info) in ctx.remaining_accounts.iter().enumerate() {
+        let mut delegation: Account<'info, Delegation> = Account::try_from(delegation_info)?;
+        require!(
+            delegation.validator == ctx.accounts.validator.key(),
+            ErrorCode::DelegationValidatorMismatch
+        );
+        require!(
+            delegation.topic == ctx.accounts.topic.key(),
+            ErrorCode::DelegationTopicMismatch
+        );
+        if delegation.delegator == ctx.accounts.submission.contributor {
+            msg!(
+                "Self-voting is not allowed: delegators cannot back votes on their own submissions"
+            );
+            return Err(ErrorCode::SelfVotingNotAllowed.into());
+        }
+        if delegations
+            .iter()
+            .any(|stake| stake.delegation == delegation_info.key())
+        {
+            return Err(ErrorCode::DelegationAccountsMismatch.into());
+        }
+
+        let amount = delegation_amounts
+            .get(i)
+            .copied()
+            .unwrap_or(delegation.amount);
+        if amount == 0 {
+            msg!(
+                "Delegation {} has no unlocked tempRep to draw; skipping",
+                delegation_info.key()
+            );
+            continue;
+        }
+        if delegation.amount < amount {
+            msg!(
+                "Insufficient unlocked delegated tempRep in {}. Requested: {}, Available: {}",
+                delegation_info.key(),
+                amount,
+                delegation.amount
+            );
+            return Err(ErrorCode::InsufficientDelegatedBalance.into());
+        }
+
+        delegation.amount = delegation
+            .amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        delegation.locked_amount = delegation
+            .locked_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        delegation.exit(ctx.program_id)?;
+
+        delegated_temp_rep_amount = delegated_temp_rep_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        delegations.push(DelegatedStake {
+            delegation: delegation_info.key(),
+            amount,
+        });
+        msg!(
+            "Locked {} delegated tempRep from delegator {}",
+            amount,
+            delegation.delegator
+        );
+    }
+
     // Validate vote amounts
-    if temp_rep_amount == 0 && perm_rep_amount == 0 {
+    if temp_rep_amount == 0 && perm_rep_amount == 0 && delegated_temp_rep_amount == 0 {
         return Err(ErrorCode::ZeroVoteAmount.into());
     }
 
@@ -122,6 +254,8 @@ pub fn commit_vote(
     vote_commit.commit_timestamp = current_time;
     vote_commit.temp_rep_amount = temp_rep_amount;
     vote_commit.perm_rep_amount = perm_rep_amount;
+    vote_commit.delegated_temp_rep_amount = delegated_temp_rep_amount;
+    vote_commit.delegations = delegations;
     vote_commit.bump = ctx.bumps.vote_commit;
 
     // Increment the submission-topic link's committed votes counter
@@ -146,11 +280,14 @@ pub fn commit_vote(
         // This message will effectively not show for perm_rep_amount in MVP due to earlier constraint
         msg!("Permanent REP committed: {}", perm_rep_amount);
     }
+    if delegated_temp_rep_amount > 0 {
+        msg!("Delegated tempRep committed: {}", delegated_temp_rep_amount);
+    }
     // For overall context, let's log total effective amount for this commit
     // Even if perm_rep_amount is 0, this helps in logs
     msg!(
         "Total effective vote amount considered for this commit: {}",
-        temp_rep_amount + perm_rep_amount
+        temp_rep_amount + perm_rep_amount + delegated_temp_rep_amount
     );
     msg!(
         "Total committed votes for link now: {}",
@@ -160,7 +297,25 @@ pub fn commit_vote(
     Ok(())
 }
 
+/// Reveal a committed vote, signed by the validator or one of their session keys
 pub fn reveal_vote(ctx: Context<RevealVote>, vote_choice: VoteChoice, nonce: String) -> Result<()> {
+    authorize_session(
+        &ctx.accounts.validator.key(),
+        &ctx.accounts.acting_signer.key(),
+        &ctx.accounts.session_key,
+        SESSION_PERMISSION_REVEAL_VOTE,
+    )?;
+    record_vote_reveal(ctx, vote_choice, nonce)
+}
+
+/// Check a revealed vote against its commitment and tally it
+///
+/// Callers must have authorized the validator already.
+pub(crate) fn record_vote_reveal(
+    ctx: Context<RevealVote>,
+    vote_choice: VoteChoice,
+    nonce: String,
+) -> Result<()> {
     // Get current time to validate voting window
     let current_time = Clock::get()?.unix_timestamp as u64;
     let link = &ctx.accounts.submission_topic_link;
@@ -178,6 +333,11 @@ pub fn reveal_vote(ctx: Context<RevealVote>, vote_choice: VoteChoice, nonce: Str
     // Reconstruct the hash from the reveal data and verify it matches the commit
     let vote_commit = &mut ctx.accounts.vote_commit;
 
+    // A vote released by `settle_unrevealed_vote` cannot be revealed later
+    if vote_commit.finalized {
+        return Err(ErrorCode::VoteAlreadyFinalized.into());
+    }
+
     // Create the pre-image for the hash
     // Format: validator pubkey + submission_topic_link pubkey + vote choice (0 for Yes, 1 for No) + nonce
     let mut hasher = Sha256::new();
@@ -198,14 +358,17 @@ pub fn reveal_vote(ctx: Context<RevealVote>, vote_choice: VoteChoice, nonce: Str
     vote_commit.vote_choice = Some(vote_choice);
 
     // Calculate voting power (quadratic)
-    // Sum temp_rep_amount and perm_rep_amount for total voting power base
-    let total_vote_amount = vote_commit
+    // Sum own and delegated tempRep plus perm_rep_amount for total voting power base
+    let vote_temp_rep = vote_commit
         .temp_rep_amount
+        .checked_add(vote_commit.delegated_temp_rep_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    let total_vote_amount = vote_temp_rep
         .checked_add(vote_commit.perm_rep_amount)
         .ok_or(ErrorCode::Overflow)?;
     let voting_power = calculate_quadratic_voting_power(total_vote_amount);
 
-    // Add the voting power to the appropriate counter
+    // Add the voting power (and the tempRep backing it) to the appropriate side
     let link = &mut ctx.accounts.submission_topic_link;
     match vote_choice {
         VoteChoice::Yes => {
@@ -213,12 +376,20 @@ pub fn reveal_vote(ctx: Context<RevealVote>, vote_choice: VoteChoice, nonce: Str
                 .yes_voting_power
                 .checked_add(voting_power)
                 .ok_or(ErrorCode::Overflow)?;
+            link.yes_temp_rep = link
+                .yes_temp_rep
+                .checked_add(vote_temp_rep)
+                .ok_or(ErrorCode::Overflow)?;
         }
         VoteChoice::No => {
             link.no_voting_power = link
                 .no_voting_power
                 .checked_add(voting_power)
                 .ok_or(ErrorCode::Overflow)?;
+            link.no_temp_rep = link
+                .no_temp_rep
+                .checked_add(vote_temp_rep)
+                .ok_or(ErrorCode::Overflow)?;
         }
     }
 
@@ -238,18 +409,258 @@ pub fn reveal_vote(ctx: Context<RevealVote>, vote_choice: VoteChoice, nonce: Str
     Ok(())
 }
 
-pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
+/// Finalize a revealed vote, settling the validator's and any delegators' tempRep
+///
+/// For each delegation recorded on the vote, the remaining accounts must contain, in order:
+/// the `Delegation` PDA, the delegator's protocol-owned tempRep account, the delegator's
+/// Rep ATA and the delegator's `UserTopicBalance` for the topic. Delegators receive their
+/// pro rata share of the vote's Rep reward, and it counts towards their `rep_earned` in the
+/// topic. The validator keeps the protocol's delegation commission on the part of that share
+/// above the delegator's own converted principal.
+pub fn finalize_vote<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeVote<'info>>) -> Result<()> {
     // Check if the vote has already been finalized
     if ctx.accounts.vote_commit.finalized {
         return Err(ErrorCode::VoteAlreadyFinalized.into());
     }
 
+    let accounts = ctx.accounts;
+    settle_vote(
+        VoteSettlement {
+            state: &accounts.state,
+            link: &mut accounts.submission_topic_link,
+            topic: &accounts.topic,
+            vote_commit: &mut accounts.vote_commit,
+            user_topic_balance: &mut accounts.user_topic_balance,
+            validator_temp_rep_account: &accounts.validator_temp_rep_account,
+            validator_rep_ata: accounts.validator_rep_ata.to_account_info(),
+            temp_rep_mint: accounts.temp_rep_mint.to_account_info(),
+            rep_mint: accounts.rep_mint.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+            program_id: ctx.program_id,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    pay_finalization_reward(
+        accounts.state.finalization_reward,
+        accounts.state.finalization_reward_min_voting_power,
+        1,
+        &accounts.submission_topic_link,
+        accounts.fee_vault.as_ref(),
+        &accounts.authority.to_account_info(),
+    )
+}
+
+/// Finalize several revealed votes on one finalized link
+///
+/// Remaining accounts are read group by group: (vote_commit, user_topic_balance,
+/// validator_temp_rep_account, validator_rep_ata), then one (delegation, delegator tempRep
+/// account, delegator Rep ATA, delegator topic balance) group per delegation recorded on the
+/// vote commit. The checks
+/// `FinalizeVote` makes through account constraints are made here by hand.
+pub fn finalize_votes_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeVotesBatch<'info>>,
+) -> Result<()> {
+    const GROUP_LEN: usize = 4;
+    let remaining = ctx.remaining_accounts;
+    require!(!remaining.is_empty(), ErrorCode::VoteBatchAccountsMismatch);
+
+    let accounts = ctx.accounts;
+    let link_key = accounts.submission_topic_link.key();
+    let topic_key = accounts.topic.key();
+    let mut cursor = 0;
+    let mut finalized = 0u64;
+
+    while cursor < remaining.len() {
+        require!(
+            remaining.len() - cursor >= GROUP_LEN,
+            ErrorCode::VoteBatchAccountsMismatch
+        );
+        let group = &remaining[cursor..cursor + GROUP_LEN];
+
+        // The vote must be a revealed, unfinalized vote on this link. A vote listed twice
+        // fails here, as the first settlement is written back before the next group loads.
+        let mut vote_commit: Account<'info, VoteCommit> = Account::try_from(&group[0])?;
+        require_keys_eq!(
+            vote_commit.submission_topic_link,
+            link_key,
+            ErrorCode::VoteBatchAccountsMismatch
+        );
+        require!(vote_commit.revealed, ErrorCode::VoteNotRevealed);
+        require!(!vote_commit.finalized, ErrorCode::VoteAlreadyFinalized);
+        let validator = vote_commit.validator;
+
+        let mut user_topic_balance: Account<'info, UserTopicBalance> =
+            Account::try_from(&group[1])?;
+        require_keys_eq!(
+            user_topic_balance.user,
+            validator,
+            ErrorCode::UserAccountMismatch
+        );
+        require_keys_eq!(user_topic_balance.topic, topic_key, ErrorCode::InvalidTopic);
+
+        let (expected_temp_rep_account, _) =
+            Pubkey::find_program_address(&[b"user_temp_rep", validator.as_ref()], ctx.program_id);
+        let validator_temp_rep_account: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(&group[2])?;
+        require!(
+            group[2].key() == expected_temp_rep_account
+                && validator_temp_rep_account.mint == accounts.state.temp_rep_mint
+                && validator_temp_rep_account.owner == accounts.state.key(),
+            ErrorCode::InvalidTokenAccount
+        );
+
+        let validator_rep_ata: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(&group[3])?;
+        require!(
+            validator_rep_ata.mint == accounts.state.rep_mint
+                && validator_rep_ata.owner == validator,
+            ErrorCode::InvalidTokenAccount
+        );
+
+        let delegation_end =
+            cursor + GROUP_LEN + vote_commit.delegations.len() * DELEGATION_SETTLEMENT_ACCOUNTS;
+        require!(
+            delegation_end <= remaining.len(),
+            ErrorCode::VoteBatchAccountsMismatch
+        );
+
+        settle_vote(
+            VoteSettlement {
+                state: &accounts.state,
+                link: &mut accounts.submission_topic_link,
+                topic: &accounts.topic,
+                vote_commit: &mut vote_commit,
+                user_topic_balance: &mut user_topic_balance,
+                validator_temp_rep_account: &validator_temp_rep_account,
+                validator_rep_ata: group[3].clone(),
+                temp_rep_mint: accounts.temp_rep_mint.to_account_info(),
+                rep_mint: accounts.rep_mint.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                program_id: ctx.program_id,
+            },
+            &remaining[cursor + GROUP_LEN..delegation_end],
+        )?;
+
+        // Accounts loaded from remaining accounts are not written back automatically
+        vote_commit.exit(ctx.program_id)?;
+        user_topic_balance.exit(ctx.program_id)?;
+
+        finalized += 1;
+        cursor = delegation_end;
+    }
+
+    msg!("Finalized {} votes on link {}", finalized, link_key);
+
+    pay_finalization_reward(
+        accounts.state.finalization_reward,
+        accounts.state.finalization_reward_min_voting_power,
+        finalized,
+        &accounts.submission_topic_link,
+        accounts.fee_vault.as_ref(),
+        &accounts.authority.to_account_info(),
+    )
+}
+
+/// Release the tempRep locked in a vote that was never revealed
+///
+/// Permissionless once the link's reveal phase has ended. The vote never counted, so nothing
+/// is burned or minted: the validator's own tempRep returns to their available topic balance
+/// and each delegation drawn at commit gets its locked amount back. Remaining accounts are the
+/// `Delegation` PDAs recorded on the vote, in order.
+pub fn settle_unrevealed_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleUnrevealedVote<'info>>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    if current_time <= ctx.accounts.submission_topic_link.reveal_phase_end {
+        return Err(ErrorCode::RevealPhaseNotEnded.into());
+    }
+
+    let vote_commit = &mut ctx.accounts.vote_commit;
+    require!(!vote_commit.revealed, ErrorCode::VoteAlreadyRevealed);
+    require!(!vote_commit.finalized, ErrorCode::VoteAlreadyFinalized);
+    if ctx.remaining_accounts.len() != vote_commit.delegations.len() {
+        return Err(ErrorCode::DelegationAccountsMismatch.into());
+    }
+
+    for (stake, delegation_info) in vote_commit
+        .delegations
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+    {
+        if delegation_info.key() != stake.delegation {
+            return Err(ErrorCode::DelegationAccountsMismatch.into());
+        }
+        let mut delegation: Account<'info, Delegation> = Account::try_from(delegation_info)?;
+        delegation.locked_amount = delegation
+            .locked_amount
+            .checked_sub(stake.amount)
+            .ok_or(ErrorCode::Overflow)?;
+        delegation.amount = delegation
+            .amount
+            .checked_add(stake.amount)
+            .ok_or(ErrorCode::Overflow)?;
+        delegation.exit(ctx.program_id)?;
+        msg!(
+            "Released {} delegated tempRep back to delegator {}",
+            stake.amount,
+            delegation.delegator
+        );
+    }
+
+    let temp_rep_amount = vote_commit.temp_rep_amount;
+    if temp_rep_amount > 0 {
+        let user_topic_balance = &mut ctx.accounts.user_topic_balance;
+        user_topic_balance.locked_temp_rep_amount = user_topic_balance
+            .locked_temp_rep_amount
+            .checked_sub(temp_rep_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        user_topic_balance.temp_rep_amount = user_topic_balance
+            .temp_rep_amount
+            .checked_add(temp_rep_amount)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+
+    vote_commit.finalized = true;
+
+    msg!(
+        "Released unrevealed vote by validator {}: {} own and {} delegated tempRep unlocked",
+        vote_commit.validator,
+        temp_rep_amount,
+        vote_commit.delegated_temp_rep_amount
+    );
+    Ok(())
+}
+
+/// Accounts needed to settle one revealed vote on a finalized link
+struct VoteSettlement<'a, 'info> {
+    state: &'a Account<'info, State>,
+    link: &'a mut Account<'info, SubmissionTopicLink>,
+    topic: &'a Account<'info, Topic>,
+    vote_commit: &'a mut Account<'info, VoteCommit>,
+    user_topic_balance: &'a mut Account<'info, UserTopicBalance>,
+    validator_temp_rep_account: &'a InterfaceAccount<'info, TokenAccount>,
+    validator_rep_ata: AccountInfo<'info>,
+    temp_rep_mint: AccountInfo<'info>,
+    rep_mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    program_id: &'a Pubkey,
+}
+
+/// Converts or burns a revealed vote's tempRep according to the link's outcome, settles
+/// its delegations and marks it finalized
+///
+/// `delegation_accounts` holds a (delegation, delegator tempRep account, delegator Rep ATA)
+/// triple for each delegation drawn at commit. The caller checks the vote is not finalized.
+fn settle_vote<'info>(
+    s: VoteSettlement<'_, 'info>,
+    delegation_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
     // Get the consensus outcome (accepted/rejected)
-    let consensus_is_yes = ctx.accounts.submission_topic_link.status == SubmissionStatus::Accepted;
+    let consensus_is_yes = s.link.status == SubmissionStatus::Accepted;
 
     // Get the validator's vote choice
-    let vote_choice = ctx
-        .accounts
+    let vote_choice = s
         .vote_commit
         .vote_choice
         .ok_or(ErrorCode::InvalidVoteHash)?;
@@ -258,104 +669,268 @@ pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
     // Check if the validator voted with the consensus
     let voted_with_consensus = (consensus_is_yes && voted_yes) || (!consensus_is_yes && !voted_yes);
 
+    let vote_amount = s.vote_commit.temp_rep_amount;
+    let delegated_amount = s.vote_commit.delegated_temp_rep_amount;
+    let delegations = s.vote_commit.delegations.clone();
+    let total_temp_rep = vote_amount
+        .checked_add(delegated_amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // Every delegation drawn at commit must be passed back for settlement
+    if delegation_accounts.len() != delegations.len() * DELEGATION_SETTLEMENT_ACCOUNTS {
+        msg!(
+            "Expected {} remaining accounts for {} delegations, got {}",
+            delegations.len() * DELEGATION_SETTLEMENT_ACCOUNTS,
+            delegations.len(),
+            delegation_accounts.len()
+        );
+        return Err(ErrorCode::DelegationAccountsMismatch.into());
+    }
+
     // Only process token conversions/burns if temporary reputation was used
-    if ctx.accounts.vote_commit.temp_rep_amount > 0 {
-        let vote_amount = ctx.accounts.vote_commit.temp_rep_amount; // Use temp_rep_amount here
+    if total_temp_rep > 0 {
+        // Total Rep paid out for this vote: principal plus a share of the link's reward pool
+        // pro rata to voting power. Zero if the validator voted against consensus.
+        let mut rep_reward: u64 = 0;
+        let rep_mint_ratio_bps = s
+            .topic
+            .rep_mint_ratio_bps
+            .unwrap_or(DEFAULT_REP_MINT_RATIO_BPS);
 
         if voted_with_consensus {
-            // Validator voted correctly - convert tempRep to permanent Rep
+            let link = &mut *s.link;
+            let voting_power = calculate_quadratic_voting_power(
+                total_temp_rep
+                    .checked_add(s.vote_commit.perm_rep_amount)
+                    .ok_or(ErrorCode::Overflow)?,
+            );
+            let winning_power = if consensus_is_yes {
+                link.yes_voting_power
+            } else {
+                link.no_voting_power
+            };
+            let remaining_pool = link
+                .reward_pool
+                .checked_sub(link.reward_pool_claimed)
+                .ok_or(ErrorCode::Overflow)?;
+            let pool_share = std::cmp::min(
+                calculate_reward_share(link.reward_pool, voting_power, winning_power),
+                remaining_pool,
+            );
+            link.reward_pool_claimed = link
+                .reward_pool_claimed
+                .checked_add(pool_share)
+                .ok_or(ErrorCode::Overflow)?;
+            rep_reward = calculate_rep_from_temp_rep(total_temp_rep, rep_mint_ratio_bps)
+                .checked_add(pool_share)
+                .ok_or(ErrorCode::Overflow)?;
+
+            msg!(
+                "Reward pool share: {} (voting power {} of {}), pool claimed {}/{}",
+                pool_share,
+                voting_power,
+                winning_power,
+                link.reward_pool_claimed,
+                link.reward_pool
+            );
+        }
 
+        // Use state PDA as the authority for burning and minting
+        let state_bump = s.state.bump;
+        let seeds = &[b"state".as_ref(), &[state_bump]];
+        let signer = &[&seeds[..]];
+
+        // 1. Burn the validator's own tempRep from their protocol-owned account
+        if vote_amount > 0 {
             // Check token balance in protocol-owned tempRep account
-            if ctx.accounts.validator_temp_rep_account.amount < vote_amount {
+            if s.validator_temp_rep_account.amount < vote_amount {
                 msg!("Error: Mismatch between locked amount and tempRep token account balance during finalization.");
                 return Err(ErrorCode::InsufficientTokenBalance.into());
             }
 
-            // 1. Burn tempRep tokens from protocol-owned account
-            // Use state PDA as the authority
-            let state_bump = ctx.accounts.state.bump;
-            let seeds = &[b"state".as_ref(), &[state_bump]];
-            let signer = &[&seeds[..]];
-
             let burn_cpi_ctx = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                s.token_program.clone(),
                 Burn {
-                    mint: ctx.accounts.temp_rep_mint.to_account_info(),
-                    from: ctx.accounts.validator_temp_rep_account.to_account_info(),
-                    authority: ctx.accounts.state.to_account_info(),
+                    mint: s.temp_rep_mint.clone(),
+                    from: s.validator_temp_rep_account.to_account_info(),
+                    authority: s.state.to_account_info(),
                 },
             )
             .with_signer(signer);
 
-            token::burn(burn_cpi_ctx, vote_amount)?;
+            token_interface::burn(burn_cpi_ctx, vote_amount)?;
+        }
 
-            // 2. Mint permanent Rep tokens to user-owned ATA
-            let state_bump = ctx.accounts.state.bump;
-            let seeds = &[b"state".as_ref(), &[state_bump]];
-            let signer = &[&seeds[..]];
+        // 2. Settle each delegation: burn the delegated tempRep and pay the delegator's
+        //    share of the reward, net of commission
+        let commission_bps = s.state.delegation_commission_bps as u64;
+        let mut paid_to_delegators: u64 = 0;
+        for (stake, accounts) in delegations
+            .iter()
+            .zip(delegation_accounts.chunks(DELEGATION_SETTLEMENT_ACCOUNTS))
+        {
+            let delegation_info = &accounts[0];
+            if delegation_info.key() != stake.delegation {
+                return Err(ErrorCode::DelegationAccountsMismatch.into());
+            }
+            let mut delegation: Account<'info, Delegation> = Account::try_from(delegation_info)?;
 
-            let mint_cpi_ctx = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.rep_mint.to_account_info(),
-                    to: ctx.accounts.validator_rep_ata.to_account_info(),
-                    authority: ctx.accounts.state.to_account_info(),
+            let (expected_temp_rep_account, _) = Pubkey::find_program_address(
+                &[b"user_temp_rep", delegation.delegator.as_ref()],
+                s.program_id,
+            );
+            let delegator_temp_rep_account: InterfaceAccount<'info, TokenAccount> =
+                InterfaceAccount::try_from(&accounts[1])?;
+            if accounts[1].key() != expected_temp_rep_account
+                || delegator_temp_rep_account.mint != s.state.temp_rep_mint
+            {
+                return Err(ErrorCode::InvalidTokenAccount.into());
+            }
+            if delegator_temp_rep_account.amount < stake.amount {
+                msg!("Error: Mismatch between delegated amount and delegator tempRep token account balance during finalization.");
+                return Err(ErrorCode::InsufficientTokenBalance.into());
+            }
+
+            let burn_cpi_ctx = CpiContext::new(
+                s.token_program.clone(),
+                Burn {
+                    mint: s.temp_rep_mint.clone(),
+                    from: accounts[1].clone(),
+                    authority: s.state.to_account_info(),
                 },
             )
             .with_signer(signer);
 
-            token::mint_to(mint_cpi_ctx, vote_amount)?;
+            token_interface::burn(burn_cpi_ctx, stake.amount)?;
 
-            msg!(
-                "Validator voted correctly! Converted {} tempRep to {} permanent Rep (minted to ATA)",
-                vote_amount,
-                vote_amount
+            delegation.locked_amount = delegation
+                .locked_amount
+                .checked_sub(stake.amount)
+                .ok_or(ErrorCode::Overflow)?;
+            delegation.exit(s.program_id)?;
+
+            let mut delegator_balance: Account<'info, UserTopicBalance> =
+                Account::try_from(&accounts[3])?;
+            require_keys_eq!(
+                delegator_balance.user,
+                delegation.delegator,
+                ErrorCode::UserAccountMismatch
+            );
+            require_keys_eq!(
+                delegator_balance.topic,
+                s.topic.key(),
+                ErrorCode::InvalidTopic
             );
-        } else {
-            // Validator voted incorrectly - burn tempRep tokens with no replacement
 
-            // Check token balance in protocol-owned tempRep account
-            if ctx.accounts.validator_temp_rep_account.amount < vote_amount {
-                msg!("Error: Mismatch between locked amount and tempRep token account balance during finalization.");
-                return Err(ErrorCode::InsufficientTokenBalance.into());
+            if rep_reward > 0 {
+                let delegator_rep_ata: InterfaceAccount<'info, TokenAccount> =
+                    InterfaceAccount::try_from(&accounts[2])?;
+                if delegator_rep_ata.mint != s.state.rep_mint
+                    || delegator_rep_ata.owner != delegation.delegator
+                {
+                    return Err(ErrorCode::InvalidTokenAccount.into());
+                }
+
+                // Commission is only taken from the Rep minted above the delegator's own
+                // converted principal
+                let gross_share = calculate_reward_share(rep_reward, stake.amount, total_temp_rep);
+                let principal_share = calculate_rep_from_temp_rep(stake.amount, rep_mint_ratio_bps);
+                let commission = calculate_reward_share(
+                    gross_share.saturating_sub(principal_share),
+                    commission_bps,
+                    BPS_DENOMINATOR,
+                );
+                let net_share = gross_share
+                    .checked_sub(commission)
+                    .ok_or(ErrorCode::Overflow)?;
+
+                if net_share > 0 {
+                    let mint_cpi_ctx = CpiContext::new(
+                        s.token_program.clone(),
+                        MintTo {
+                            mint: s.rep_mint.clone(),
+                            to: accounts[2].clone(),
+                            authority: s.state.to_account_info(),
+                        },
+                    )
+                    .with_signer(signer);
+
+                    token_interface::mint_to(mint_cpi_ctx, net_share)?;
+                }
+
+                paid_to_delegators = paid_to_delegators
+                    .checked_add(net_share)
+                    .ok_or(ErrorCode::Overflow)?;
+                delegator_balance.rep_earned = delegator_balance
+                    .rep_earned
+                    .checked_add(net_share)
+                    .ok_or(ErrorCode::Overflow)?;
+                delegator_balance.exit(s.program_id)?;
+                msg!(
+                    "Delegator {} converted {} delegated tempRep to {} Rep ({} commission)",
+                    delegation.delegator,
+                    stake.amount,
+                    net_share,
+                    commission
+                );
+            } else {
+                msg!(
+                    "Burned {} delegated tempRep from delegator {} with no replacement",
+                    stake.amount,
+                    delegation.delegator
+                );
             }
+        }
 
-            // Burn tempRep tokens from protocol-owned account
-            // Use state PDA as the authority for the protocol-owned tempRep account
-            let state_bump = ctx.accounts.state.bump;
-            let seeds = &[b"state".as_ref(), &[state_bump]];
-            let signer = &[&seeds[..]];
+        // 3. Mint the validator's share (own principal, own pool share and commission)
+        if voted_with_consensus {
+            let validator_reward = rep_reward
+                .checked_sub(paid_to_delegators)
+                .ok_or(ErrorCode::Overflow)?;
 
-            let burn_cpi_ctx = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.temp_rep_mint.to_account_info(),
-                    from: ctx.accounts.validator_temp_rep_account.to_account_info(),
-                    authority: ctx.accounts.state.to_account_info(),
-                },
-            )
-            .with_signer(signer);
+            if validator_reward > 0 {
+                let mint_cpi_ctx = CpiContext::new(
+                    s.token_program.clone(),
+                    MintTo {
+                        mint: s.rep_mint.clone(),
+                        to: s.validator_rep_ata.clone(),
+                        authority: s.state.to_account_info(),
+                    },
+                )
+                .with_signer(signer);
 
-            token::burn(burn_cpi_ctx, vote_amount)?;
+                token_interface::mint_to(mint_cpi_ctx, validator_reward)?;
+            }
+
+            let user_topic_balance = &mut *s.user_topic_balance;
+            user_topic_balance.rep_earned = user_topic_balance
+                .rep_earned
+                .checked_add(validator_reward)
+                .ok_or(ErrorCode::Overflow)?;
 
             msg!(
-                "Validator voted incorrectly. Burned {} tempRep tokens with no replacement",
-                vote_amount
+                "Validator voted correctly! Converted {} tempRep to {} permanent Rep (minted to ATA)",
+                vote_amount,
+                validator_reward
+            );
+        } else {
+            msg!(
+                "Validator voted incorrectly. Burned {} tempRep tokens with no replacement (funds the link reward pool)",
+                total_temp_rep
             );
         }
-    } else if ctx.accounts.vote_commit.perm_rep_amount > 0 {
+    } else if s.vote_commit.perm_rep_amount > 0 {
         // This branch is for votes made only with permanent REP (temp_rep_amount is 0)
         // No token conversion/burn/reward/penalty for permanent REP in MVP
         msg!(
             "Vote was made with permanent Rep tokens (amount: {}). No token conversion applied.",
-            ctx.accounts.vote_commit.perm_rep_amount
+            s.vote_commit.perm_rep_amount
         );
     }
 
     // Update the locked token balance in UserTopicBalance if tempRep was used
-    if ctx.accounts.vote_commit.temp_rep_amount > 0 {
-        let vote_amount = ctx.accounts.vote_commit.temp_rep_amount; // Use temp_rep_amount here
-        let user_topic_balance = &mut ctx.accounts.user_topic_balance;
+    if vote_amount > 0 {
+        let user_topic_balance = &mut *s.user_topic_balance;
 
         // Unlock the tokens that were committed to this vote
         user_topic_balance.locked_temp_rep_amount = user_topic_balance
@@ -366,7 +941,7 @@ pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
         msg!(
             "Unlocked {} tempRep tokens from locked pool in UserTopicBalance for topic {}",
             vote_amount,
-            ctx.accounts.topic.key()
+            s.topic.key()
         );
         msg!(
             "New locked balance in UserTopicBalance: {}",
@@ -375,13 +950,13 @@ pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
     }
 
     // Mark the vote as finalized
-    let vote_commit = &mut ctx.accounts.vote_commit;
+    let vote_commit = &mut *s.vote_commit;
     vote_commit.finalized = true;
 
     msg!(
         "Finalized vote for validator {} on submission in topic '{}'",
-        ctx.accounts.validator_profile.user,
-        ctx.accounts.topic.name
+        s.vote_commit.validator,
+        s.topic.name
     );
 
     Ok(())
@@ -389,7 +964,8 @@ pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
 
 /// Set arbitrary timestamps for a submission's voting phases for testing or administrative purposes
 ///
-/// This function allows the protocol authority to manually set timestamps for the commit and reveal phases.
+/// This function allows the protocol authority, the topic creator, or a topic moderator or
+/// phase manager to manually set timestamps for the commit and reveal phases.
 /// This is primarily intended for testing where time-based constraints are difficult to simulate,
 /// but could also be used for emergency situations in production.
 ///
@@ -405,6 +981,20 @@ pub fn set_voting_phases(
     reveal_phase_start: Option<u64>,
     reveal_phase_end: Option<u64>,
 ) -> Result<()> {
+    // Authorisation check: protocol authority, topic creator, or a moderator / phase manager
+    let signer = ctx.accounts.authority.key();
+    require!(
+        signer == ctx.accounts.state.authority
+            || signer == ctx.accounts.topic.creator
+            || has_topic_role(
+                ctx.accounts.topic_role.as_deref(),
+                &ctx.accounts.topic.key(),
+                &signer,
+                TOPIC_ROLE_MODERATOR | TOPIC_ROLE_PHASE_MANAGER
+            ),
+        ErrorCode::InvalidAuthority
+    );
+
     // Get the current time for logging purposes (though we don't use it for validation)
     let _current_time = Clock::get()?.unix_timestamp as u64;
     let link = &mut ctx.accounts.submission_topic_link;
@@ -60,6 +60,49 @@ pub mod alignment_protocol {
         instructions::initialize::update_tokens_to_mint(ctx, new_tokens_to_mint)
     }
 
+    /// Instruction handler: update the Rep emitted into each link's validator reward pool
+    pub fn update_validator_reward_emission(
+        ctx: Context<UpdateValidatorRewardEmission>,
+        new_validator_reward_emission: u64,
+    ) -> Result<()> {
+        instructions::initialize::update_validator_reward_emission(
+            ctx,
+            new_validator_reward_emission,
+        )
+    }
+
+    /// Instruction handler: update the commission validators keep from delegators' vote rewards
+    pub fn update_delegation_commission(
+        ctx: Context<UpdateDelegationCommission>,
+        new_delegation_commission_bps: u16,
+    ) -> Result<()> {
+        instructions::initialize::update_delegation_commission(ctx, new_delegation_commission_bps)
+    }
+
+    /// Instruction handler: update the lamports paid to whoever finalizes a link or vote, and
+    /// the revealed voting power a link needs before it pays them
+    pub fn update_finalization_reward(
+        ctx: Context<UpdateFinalizationReward>,
+        new_finalization_reward: u64,
+        new_min_voting_power: u64,
+    ) -> Result<()> {
+        instructions::initialize::update_finalization_reward(
+            ctx,
+            new_finalization_reward,
+            new_min_voting_power,
+        )
+    }
+
+    /// Instruction handler: create the fee vault that pays finalization rewards
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        instructions::fees::initialize_fee_vault(ctx)
+    }
+
+    /// Instruction handler: deposit lamports into the fee vault (anyone)
+    pub fn fund_fee_vault(ctx: Context<FundFeeVault>, amount: u64) -> Result<()> {
+        instructions::fees::fund_fee_vault(ctx, amount)
+    }
+
     /// Instruction handler: explicitly create user's ATA for permanent tokens (Align, Rep)
     ///
     /// This does NOT use `init_if_needed`. Instead, it does a CPI to the associated_token::create method.
@@ -111,18 +154,84 @@ pub mod alignment_protocol {
         )
     }
 
-    /// Instruction handler: Update an existing topic (durations, activity flag)
+    /// Instruction handler: Update an existing topic (durations, activity flag, reward overrides)
     pub fn update_topic(
         ctx: Context<UpdateTopic>,
         commit_phase_duration: Option<u64>,
         reveal_phase_duration: Option<u64>,
         is_active: Option<bool>,
+        tokens_to_mint: Option<Option<u64>>,
+        rep_mint_ratio_bps: Option<Option<u16>>,
     ) -> Result<()> {
         instructions::topics::update_topic(
             ctx,
             commit_phase_duration,
             reveal_phase_duration,
             is_active,
+            tokens_to_mint,
+            rep_mint_ratio_bps,
+        )
+    }
+
+    /// Instruction handler: Set a topic's per-epoch tempAlign emission budget
+    pub fn update_topic_emission_budget(
+        ctx: Context<UpdateTopic>,
+        emission_budget_per_epoch: Option<u64>,
+        emission_epoch_duration: Option<u64>,
+    ) -> Result<()> {
+        instructions::topics::update_topic_emission_budget(
+            ctx,
+            emission_budget_per_epoch,
+            emission_epoch_duration,
+        )
+    }
+
+    /// Instruction handler: Set the weight of AI voting power in a topic's finalization
+    pub fn update_topic_ai_weight(
+        ctx: Context<UpdateTopic>,
+        ai_weight_bps: u16,
+        ai_confidence_scaling: bool,
+    ) -> Result<()> {
+        instructions::topics::update_topic_ai_weight(ctx, ai_weight_bps, ai_confidence_scaling)
+    }
+
+    /// Instruction handler: Assign a topic its own oracle key (None = use the global oracle)
+    pub fn update_topic_oracle(
+        ctx: Context<UpdateTopic>,
+        oracle_pubkey: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::topics::update_topic_oracle(ctx, oracle_pubkey)
+    }
+
+    /// Instruction handler: Set a topic's per-user submission rate limits
+    pub fn update_topic_rate_limits(
+        ctx: Context<UpdateTopic>,
+        max_submissions_per_window: Option<u64>,
+        submission_window_duration: Option<u64>,
+        max_pending_submissions: Option<u64>,
+    ) -> Result<()> {
+        instructions::topics::update_topic_rate_limits(
+            ctx,
+            max_submissions_per_window,
+            submission_window_duration,
+            max_pending_submissions,
+        )
+    }
+
+    /// Instruction handler: Set a topic's participation rules (min Rep, accepted submissions, parent topic Rep)
+    pub fn update_topic_eligibility(
+        ctx: Context<UpdateTopic>,
+        min_rep_balance: Option<u64>,
+        min_accepted_submissions: Option<u64>,
+        parent_topic: Option<Option<Pubkey>>,
+        min_parent_topic_rep: Option<u64>,
+    ) -> Result<()> {
+        instructions::topics::update_topic_eligibility(
+            ctx,
+            min_rep_balance,
+            min_accepted_submissions,
+            parent_topic,
+            min_parent_topic_rep,
         )
     }
 
@@ -134,6 +243,7 @@ pub mod alignment_protocol {
     /// Instruction handler: Submit data to a specific topic
     ///
     /// This creates a submission and links it to a topic, setting up the voting phases.
+    /// The contributor signs as `acting_signer`, or passes a session key that signs for them.
     pub fn submit_data_to_topic(
         ctx: Context<SubmitDataToTopic>,
         data_reference: String,
@@ -148,7 +258,8 @@ pub mod alignment_protocol {
 
     /// Instruction handler: Stake topic-specific temporary alignment tokens
     ///
-    /// Burns tempAlign tokens from a specific topic and mints tempRep tokens for that topic
+    /// Burns tempAlign tokens from a specific topic and mints tempRep tokens for that topic.
+    /// The user signs as `acting_signer`.
     pub fn stake_topic_specific_tokens(
         ctx: Context<StakeTopicSpecificTokens>,
         amount: u64,
@@ -162,19 +273,32 @@ pub mod alignment_protocol {
     ///
     /// This creates a vote commitment without revealing the actual vote choice.
     /// The actual vote is hashed with a nonce for privacy during the commit phase.
-    pub fn commit_vote(
-        ctx: Context<CommitVote>,
+    ///
+    /// Delegations to the validator in this topic may be passed as remaining accounts;
+    /// `delegation_amounts` says how much unlocked tempRep to draw from each (all of it when
+    /// empty). The validator signs as `acting_signer`, or passes a session key that signs for
+    /// them.
+    pub fn commit_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CommitVote<'info>>,
         vote_hash: [u8; 32],
         temp_rep_amount: u64,
         perm_rep_amount: u64,
+        delegation_amounts: Vec<u64>,
     ) -> Result<()> {
-        instructions::votes::commit_vote(ctx, vote_hash, temp_rep_amount, perm_rep_amount)
+        instructions::votes::commit_vote(
+            ctx,
+            vote_hash,
+            temp_rep_amount,
+            perm_rep_amount,
+            delegation_amounts,
+        )
     }
 
     /// Instruction handler: Reveal a previously committed vote
     ///
     /// This reveals the actual vote choice and verifies it matches the previously committed hash.
     /// If valid, it adds the voter's voting power to the appropriate yes/no counter.
+    /// The validator signs as `acting_signer`, or passes a session key that signs for them.
     pub fn reveal_vote(
         ctx: Context<RevealVote>,
         vote_choice: VoteChoice,
@@ -187,20 +311,51 @@ pub mod alignment_protocol {
     ///
     /// This determines if a submission is accepted or rejected based on voting results.
     /// For accepted submissions, it converts contributor's tempAlign tokens to permanent Align tokens.
-    pub fn finalize_submission(ctx: Context<FinalizeSubmission>) -> Result<()> {
+    /// The signer earns the finalization reward when the fee vault is passed (likewise for
+    /// `finalize_vote` and, per vote, `finalize_votes_batch`).
+    pub fn finalize_submission<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeSubmission<'info>>,
+    ) -> Result<()> {
         instructions::submission::finalize_submission(ctx)
     }
 
     /// Instruction handler: Finalize a validator's vote after submission has been finalized
     ///
     /// This processes the token rewards or penalties for a validator based on their vote:
-    /// - For correct votes: Burn tempRep tokens and mint permanent Rep tokens
-    /// - For incorrect votes: Just burn tempRep tokens with no replacement
+    /// - For correct votes: Burn tempRep tokens and mint permanent Rep tokens, plus a pro rata
+    ///   share (by voting power) of the link's reward pool
+    /// - For incorrect votes: Just burn tempRep tokens with no replacement (they fund the pool)
     /// - No penalty for permanent Rep tokens used for voting
-    pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
+    /// - Delegated tempRep is settled the same way; delegators receive their pro rata share
+    ///   of the reward minus the delegation commission on the part above their own principal,
+    ///   credited to their topic `rep_earned` (passed as remaining accounts)
+    pub fn finalize_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeVote<'info>>,
+    ) -> Result<()> {
         instructions::votes::finalize_vote(ctx)
     }
 
+    /// Instruction handler: Finalize several revealed votes on one finalized link
+    ///
+    /// Permissionless, so keepers can settle every vote on a link in a few transactions.
+    /// Votes and their delegations are passed as remaining accounts (see `FinalizeVotesBatch`).
+    pub fn finalize_votes_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeVotesBatch<'info>>,
+    ) -> Result<()> {
+        instructions::votes::finalize_votes_batch(ctx)
+    }
+
+    /// Instruction handler: Release the tempRep locked in a vote that was never revealed
+    ///
+    /// Permissionless once the reveal phase has ended. The validator's own tempRep and every
+    /// delegation drawn at commit (passed as remaining accounts) are unlocked without any
+    /// burn or reward, and the vote is marked finalized.
+    pub fn settle_unrevealed_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleUnrevealedVote<'info>>,
+    ) -> Result<()> {
+        instructions::votes::settle_unrevealed_vote(ctx)
+    }
+
     /// Instruction handler: Link an existing submission to a topic
     ///
     /// This creates a new SubmissionTopicLink for an existing Submission and Topic,
@@ -212,7 +367,8 @@ pub mod alignment_protocol {
 
     /// Instruction handler: Set voting phases for a submission-topic link
     ///
-    /// This allows the protocol authority to manually set timestamps for the commit and reveal phases.
+    /// This allows the protocol authority, the topic creator, or a topic moderator or phase manager
+    /// to manually set timestamps for the commit and reveal phases.
     /// This is primarily intended for testing and administrative purposes.
     pub fn set_voting_phases(
         ctx: Context<SetVotingPhases>,
@@ -243,12 +399,305 @@ pub mod alignment_protocol {
 
     /// Instruction handler: Submit AI vote result (called by Oracle)
     ///
-    /// Allows the authorized off-chain Oracle to submit the AI's decision, adding voting power.
+    /// Allows the authorized off-chain Oracle to submit the AI's decision. Its voting power
+    /// is applied, weighted by the topic's `ai_weight_bps`, in `finalize_submission`.
+    /// The model and rationale hashes and the confidence are stored for auditing.
     pub fn submit_ai_vote(
         ctx: Context<SubmitAiVote>,
         ai_request_index: u64,
         ai_decision: VoteChoice,
+        model_hash: [u8; 32],
+        rationale_hash: [u8; 32],
+        confidence_bps: u16,
+    ) -> Result<()> {
+        instructions::ai::submit_ai_vote(
+            ctx,
+            ai_request_index,
+            ai_decision,
+            model_hash,
+            rationale_hash,
+            confidence_bps,
+        )
+    }
+
+    /// Instruction handler: Create the oracle committee (authority only)
+    ///
+    /// Once active, AI votes need `threshold` matching attestations from committee
+    /// members and `State.oracle_pubkey` can no longer decide them alone.
+    pub fn initialize_oracle_committee(
+        ctx: Context<InitializeOracleCommittee>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::oracle::initialize_oracle_committee(ctx, members, threshold)
+    }
+
+    /// Instruction handler: Replace the oracle committee's members and threshold (authority only)
+    pub fn update_oracle_committee(
+        ctx: Context<UpdateOracleCommittee>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::oracle::update_oracle_committee(ctx, members, threshold)
+    }
+
+    /// Instruction handler: Create a topic's own oracle committee
+    /// (protocol authority, topic creator or topic moderator)
+    ///
+    /// While active, the topic's AI votes need attestations from this committee instead.
+    pub fn initialize_topic_oracle_committee(
+        ctx: Context<InitializeTopicOracleCommittee>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::oracle::initialize_topic_oracle_committee(ctx, members, threshold)
+    }
+
+    /// Instruction handler: Replace a topic committee's members and threshold, or switch it
+    /// on or off (protocol authority, topic creator or topic moderator)
+    pub fn update_topic_oracle_committee(
+        ctx: Context<UpdateTopicOracleCommittee>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+        active: bool,
+    ) -> Result<()> {
+        instructions::oracle::update_topic_oracle_committee(ctx, members, threshold, active)
+    }
+
+    /// Instruction handler: Attest to an AI request's decision (called by a committee member)
+    ///
+    /// The request completes once `threshold` members submit the same decision.
+    pub fn submit_oracle_attestation(
+        ctx: Context<SubmitOracleAttestation>,
+        ai_request_index: u64,
+        decision: VoteChoice,
+    ) -> Result<()> {
+        instructions::oracle::submit_oracle_attestation(ctx, ai_request_index, decision)
+    }
+
+    /// Instruction handler: Mark an AI request as being processed (called by Oracle)
+    pub fn mark_ai_processing(ctx: Context<MarkAiProcessing>) -> Result<()> {
+        instructions::ai::mark_ai_processing(ctx)
+    }
+
+    /// Instruction handler: Mark an AI request as failed (called by Oracle)
+    ///
+    /// Refunds the requester's staked tempRep to their topic balance.
+    pub fn mark_ai_failed(ctx: Context<MarkAiFailed>) -> Result<()> {
+        instructions::ai::mark_ai_failed(ctx)
+    }
+
+    /// Instruction handler: Expire an AI request the oracle never answered
+    ///
+    /// Callable by anyone once `AI_REQUEST_TIMEOUT` has passed since the request.
+    /// Refunds the requester's staked tempRep to their topic balance.
+    pub fn expire_ai_request(ctx: Context<ExpireAiRequest>) -> Result<()> {
+        instructions::ai::expire_ai_request(ctx)
+    }
+
+    /// Instruction handler: Delegate topic-specific tempRep to a validator
+    ///
+    /// Creates the delegator's Delegation PDA for the topic and moves `amount` of their
+    /// available tempRep into it. The validator's votes in the topic can then draw on it.
+    pub fn create_delegation(ctx: Context<CreateDelegation>, amount: u64) -> Result<()> {
+        instructions::delegation::create_delegation(ctx, amount)
+    }
+
+    /// Instruction handler: Add more tempRep to an existing delegation
+    pub fn delegate_temp_rep(ctx: Context<ModifyDelegation>, amount: u64) -> Result<()> {
+        instructions::delegation::delegate_temp_rep(ctx, amount)
+    }
+
+    /// Instruction handler: Withdraw unlocked tempRep from a delegation
+    pub fn undelegate_temp_rep(ctx: Context<ModifyDelegation>, amount: u64) -> Result<()> {
+        instructions::delegation::undelegate_temp_rep(ctx, amount)
+    }
+
+    /// Instruction handler: Grant topic-scoped roles (moderator, phase manager, oracle)
+    ///
+    /// Only the protocol authority or the topic creator may grant roles.
+    pub fn grant_topic_role(ctx: Context<GrantTopicRole>, roles: u8) -> Result<()> {
+        instructions::topics::grant_topic_role(ctx, roles)
+    }
+
+    /// Instruction handler: Revoke all of a wallet's topic-scoped roles
+    pub fn revoke_topic_role(ctx: Context<RevokeTopicRole>) -> Result<()> {
+        instructions::topics::revoke_topic_role(ctx)
+    }
+
+    /// Instruction handler: Create a user's meta-transaction nonce (anyone can pay)
+    pub fn initialize_meta_nonce(ctx: Context<InitializeMetaNonce>) -> Result<()> {
+        instructions::meta::initialize_meta_nonce(ctx)
+    }
+
+    /// Instruction handler: Relayed `submit_data_to_topic`
+    ///
+    /// The meta variants let a relayer pay and send an instruction the user only signed
+    /// off-chain. The transaction must include an ed25519 verify of the user's signature over
+    /// `sha256("ALIGN_META_TX" || base instruction data || scope || payload_nonce ||
+    /// expires_at)`, `payload_nonce` must be the user's next `MetaNonce`, and the cluster
+    /// clock must not have passed `expires_at` (unix seconds).
+    pub fn submit_data_to_topic_meta<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitDataToTopicMeta<'info>>,
+        data_reference: String,
+        current_submission_index: u64,
+        payload_nonce: u64,
+        expires_at: u64,
+    ) -> Result<()> {
+        instructions::meta::submit_data_to_topic_meta(
+            ctx,
+            data_reference,
+            current_submission_index,
+            payload_nonce,
+            expires_at,
+        )
+    }
+
+    /// Instruction handler: Relayed `stake_topic_specific_tokens`
+    pub fn stake_topic_specific_tokens_meta<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeTopicSpecificTokensMeta<'info>>,
+        amount: u64,
+        payload_nonce: u64,
+        expires_at: u64,
+    ) -> Result<()> {
+        instructions::meta::stake_topic_specific_tokens_meta(ctx, amount, payload_nonce, expires_at)
+    }
+
+    /// Instruction handler: Relayed `commit_vote`
+    pub fn commit_vote_meta<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CommitVoteMeta<'info>>,
+        vote_hash: [u8; 32],
+        temp_rep_amount: u64,
+        perm_rep_amount: u64,
+        delegation_amounts: Vec<u64>,
+        payload_nonce: u64,
+        expires_at: u64,
+    ) -> Result<()> {
+        instructions::meta::commit_vote_meta(
+            ctx,
+            vote_hash,
+            temp_rep_amount,
+            perm_rep_amount,
+            delegation_amounts,
+            payload_nonce,
+            expires_at,
+        )
+    }
+
+    /// Instruction handler: Relayed `reveal_vote`
+    pub fn reveal_vote_meta<'info>(
+        ctx: Context<'_, '_, '_, 'info, RevealVoteMeta<'info>>,
+        vote_choice: VoteChoice,
+        nonce: String,
+        payload_nonce: u64,
+        expires_at: u64,
+    ) -> Result<()> {
+        instructions::meta::reveal_vote_meta(ctx, vote_choice, nonce, payload_nonce, expires_at)
+    }
+
+    /// Instruction handler: Relayed `request_ai_validation`
+    pub fn request_ai_validation_meta(
+        ctx: Context<RequestAiValidationMeta>,
+        temp_rep_to_stake: u64,
+        expected_ai_request_index: u64,
+        payload_nonce: u64,
+        expires_at: u64,
+    ) -> Result<()> {
+        instructions::meta::request_ai_validation_meta(
+            ctx,
+            temp_rep_to_stake,
+            expected_ai_request_index,
+            payload_nonce,
+            expires_at,
+        )
+    }
+
+    /// Instruction handler: Authorize an ephemeral key to act for the user until `expires_at`
+    /// (unix seconds) in the instructions allowed by the `permissions` bitmask
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        session_signer: Pubkey,
+        expires_at: u64,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::session::create_session_key(ctx, session_signer, expires_at, permissions)
+    }
+
+    /// Instruction handler: Revoke a session key (signed by the user or the session key)
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        instructions::session::revoke_session_key(ctx)
+    }
+
+    /// Instruction handler: set a protocol mint's name, symbol and URI (authority only)
+    pub fn set_mint_metadata(
+        ctx: Context<SetMintMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::initialize::set_mint_metadata(ctx, name, symbol, uri)
+    }
+
+    /// Instruction handler: create a user's profile, temp token accounts and ATAs in one call
+    pub fn onboard_user(ctx: Context<OnboardUser>) -> Result<()> {
+        instructions::users::onboard_user(ctx)
+    }
+
+    // The faucet mints tempAlign for free, so its handlers are only compiled with the `faucet`
+    // feature (devnet). `#[program]` in anchor 0.30 ignores `#[cfg]` on entry points, so
+    // other builds keep them and reject every call.
+
+    /// Instruction handler: create the starter tempAlign faucet (authority only)
+    #[cfg_attr(not(feature = "faucet"), allow(unused_variables))]
+    pub fn initialize_faucet(
+        ctx: Context<InitializeFaucet>,
+        amount: u64,
+        claim_interval: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "faucet")]
+        return instructions::faucet::initialize_faucet(ctx, amount, claim_interval);
+        #[cfg(not(feature = "faucet"))]
+        err!(crate::error::ErrorCode::FaucetNotEnabled)
+    }
+
+    /// Instruction handler: update the faucet's amount and claim interval (authority only)
+    #[cfg_attr(not(feature = "faucet"), allow(unused_variables))]
+    pub fn update_faucet(
+        ctx: Context<UpdateFaucet>,
+        amount: u64,
+        claim_interval: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "faucet")]
+        return instructions::faucet::update_faucet(ctx, amount, claim_interval);
+        #[cfg(not(feature = "faucet"))]
+        err!(crate::error::ErrorCode::FaucetNotEnabled)
+    }
+
+    /// Instruction handler: claim starter tempAlign in a topic, once per user or claim interval
+    #[cfg_attr(not(feature = "faucet"), allow(unused_variables))]
+    pub fn faucet_claim(ctx: Context<FaucetClaim>) -> Result<()> {
+        #[cfg(feature = "faucet")]
+        return instructions::faucet::faucet_claim(ctx);
+        #[cfg(not(feature = "faucet"))]
+        err!(crate::error::ErrorCode::FaucetNotEnabled)
+    }
+
+    /// Instruction handler: set the lifetime admin mint cap for one mint (authority only)
+    pub fn update_admin_mint_cap(
+        ctx: Context<UpdateAdminMintCap>,
+        token_type: TokenType,
+        new_cap: u64,
+    ) -> Result<()> {
+        instructions::initialize::update_admin_mint_cap(ctx, token_type, new_cap)
+    }
+
+    /// Instruction handler: mint protocol tokens to a user with a recorded reason (authority only)
+    pub fn admin_mint(
+        ctx: Context<AdminMint>,
+        token_type: TokenType,
+        amount: u64,
+        reason: String,
     ) -> Result<()> {
-        instructions::ai::submit_ai_vote(ctx, ai_request_index, ai_decision)
+        instructions::tokens::admin_mint(ctx, token_type, amount, reason)
     }
 }
@@ -0,0 +1,395 @@
+use anchor_client::solana_sdk::{
+    ed25519_program,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    system_program, sysvar,
+};
+use anchor_client::Program;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::Result;
+use std::rc::Rc;
+
+use alignment_protocol::{
+    accounts as AccountsAll, instruction as InstructionAll, State as StateAccount,
+    Topic as TopicAccount, UserProfile as UserProfileAccount,
+};
+
+use crate::nonce::meta_nonce_pda;
+use crate::payload::{submission_topic_link, MetaAction, SignedPayload};
+
+/// Builds and sends `[ed25519 verify, *_meta instruction]` transactions, paid by the program
+/// client's payer
+pub struct MetaTxBuilder {
+    program: Program<Rc<Keypair>>,
+}
+
+impl MetaTxBuilder {
+    pub fn new(program: Program<Rc<Keypair>>) -> Self {
+        Self { program }
+    }
+
+    pub fn program(&self) -> &Program<Rc<Keypair>> {
+        &self.program
+    }
+
+    /// Build and send the transaction for a signed payload
+    pub fn send(&self, signed: &SignedPayload) -> Result<Signature> {
+        let mut request = self.program.request();
+        for ix in self.build(signed)? {
+            request = request.instruction(ix);
+        }
+        Ok(request.send()?)
+    }
+
+    /// The instructions relaying a signed payload: the user's nonce PDA and topic balance
+    /// creation if they do not exist yet, the ed25519 verify of their signature, then the
+    /// `*_meta` instruction
+    pub fn build(&self, signed: &SignedPayload) -> Result<Vec<Instruction>> {
+        let payload = &signed.payload;
+        let user = payload.user;
+        let relayer = self.program.payer();
+        let meta_nonce = meta_nonce_pda(&self.program.id(), &user);
+
+        let mut instructions = Vec::with_capacity(4);
+        if self.program.rpc().get_account(&meta_nonce).is_err() {
+            instructions.extend(
+                self.program
+                    .request()
+                    .accounts(AccountsAll::InitializeMetaNonce {
+                        meta_nonce,
+                        user,
+                        payer: relayer,
+                        system_program: system_program::ID,
+                    })
+                    .args(InstructionAll::InitializeMetaNonce {})
+                    .instructions()?,
+            );
+        }
+        if let MetaAction::SubmitDataToTopic { topic, .. }
+        | MetaAction::StakeTopicSpecificTokens { topic, .. }
+        | MetaAction::CommitVote { topic, .. } = &payload.action
+        {
+            instructions.extend(self.init_topic_balance_if_missing(&user, topic)?);
+        }
+        instructions.push(ed25519_verify_instruction(
+            &user,
+            &signed.signature,
+            &payload.digest(&self.program.id()),
+        ));
+        instructions.push(self.meta_instruction(signed, meta_nonce)?);
+        Ok(instructions)
+    }
+
+    fn meta_instruction(&self, signed: &SignedPayload, meta_nonce: Pubkey) -> Result<Instruction> {
+        let payload = &signed.payload;
+        let user = payload.user;
+        let relayer = self.program.payer();
+        let payload_nonce = payload.payload_nonce;
+        let expires_at = payload.expires_at;
+        let state_pda = self.pda(&[b"state"]);
+        let state: StateAccount = self.program.account(state_pda)?;
+        let request = self.program.request();
+
+        let request = match payload.action.clone() {
+            MetaAction::SubmitDataToTopic {
+                topic,
+                data_reference,
+                current_submission_index,
+            } => {
+                let submission = self.pda(&[
+                    b"submission",
+                    user.as_ref(),
+                    &current_submission_index.to_le_bytes(),
+                ]);
+                let (contributor_rep_ata, parent_topic_balance) =
+                    self.eligibility_accounts(&topic, &user)?;
+                request
+                    .accounts(AccountsAll::SubmitDataToTopicMeta {
+                        inner: AccountsAll::SubmitDataToTopic {
+                            state: state_pda,
+                            topic,
+                            temp_align_mint: state.temp_align_mint,
+                            payer: relayer,
+                            contributor_temp_align_account: self
+                                .pda(&[b"user_temp_align", user.as_ref()]),
+                            submission,
+                            submission_topic_link: submission_topic_link(
+                                &self.program.id(),
+                                &submission,
+                                &topic,
+                            ),
+                            contributor_profile: self.pda(&[b"user_profile", user.as_ref()]),
+                            user_topic_balance: self.user_topic_balance(&user, &topic),
+                            contributor: user,
+                            acting_signer: relayer,
+                            session_key: None,
+                            token_program: anchor_spl::token_2022::ID,
+                            system_program: system_program::ID,
+                            contributor_rep_ata,
+                            parent_topic_balance,
+                        },
+                        meta_nonce,
+                        instructions: sysvar::instructions::ID,
+                    })
+                    .args(InstructionAll::SubmitDataToTopicMeta {
+                        data_reference,
+                        current_submission_index,
+                        payload_nonce,
+                        expires_at,
+                    })
+            }
+            MetaAction::StakeTopicSpecificTokens { topic, amount } => request
+                .accounts(AccountsAll::StakeTopicSpecificTokensMeta {
+                    inner: AccountsAll::StakeTopicSpecificTokens {
+                        state: state_pda,
+                        topic,
+                        user_profile: self.pda(&[b"user_profile", user.as_ref()]),
+                        user_topic_balance: self.user_topic_balance(&user, &topic),
+                        temp_align_mint: state.temp_align_mint,
+                        user_temp_align_account: self.pda(&[b"user_temp_align", user.as_ref()]),
+                        temp_rep_mint: state.temp_rep_mint,
+                        user_temp_rep_account: self.pda(&[b"user_temp_rep", user.as_ref()]),
+                        user,
+                        acting_signer: relayer,
+                        payer: relayer,
+                        token_program: anchor_spl::token_2022::ID,
+                    },
+                    meta_nonce,
+                    instructions: sysvar::instructions::ID,
+                })
+                .args(InstructionAll::StakeTopicSpecificTokensMeta {
+                    amount,
+                    payload_nonce,
+                    expires_at,
+                }),
+            MetaAction::CommitVote {
+                submission,
+                topic,
+                vote_hash,
+                temp_rep_amount,
+                perm_rep_amount,
+                delegations,
+                delegation_amounts,
+            } => {
+                let link = submission_topic_link(&self.program.id(), &submission, &topic);
+                let (_, parent_topic_balance) = self.eligibility_accounts(&topic, &user)?;
+                // Only the delegations the validator signed for, in the order they signed
+                let delegation_metas: Vec<AccountMeta> = delegations
+                    .into_iter()
+                    .map(|pubkey| AccountMeta::new(pubkey, false))
+                    .collect();
+                request
+                    .accounts(AccountsAll::CommitVoteMeta {
+                        inner: AccountsAll::CommitVote {
+                            state: state_pda,
+                            submission_topic_link: link,
+                            topic,
+                            submission,
+                            vote_commit: self.pda(&[b"vote_commit", link.as_ref(), user.as_ref()]),
+                            user_profile: self.pda(&[b"user_profile", user.as_ref()]),
+                            user_topic_balance: self.user_topic_balance(&user, &topic),
+                            validator_rep_ata: token_ata(&user, &state.rep_mint),
+                            validator: user,
+                            acting_signer: relayer,
+                            session_key: None,
+                            payer: relayer,
+                            system_program: system_program::ID,
+                            rent: sysvar::rent::ID,
+                            parent_topic_balance,
+                        },
+                        meta_nonce,
+                        instructions: sysvar::instructions::ID,
+                    })
+                    .accounts(delegation_metas)
+                    .args(InstructionAll::CommitVoteMeta {
+                        vote_hash,
+                        temp_rep_amount,
+                        perm_rep_amount,
+                        delegation_amounts,
+                        payload_nonce,
+                        expires_at,
+                    })
+            }
+            MetaAction::RevealVote {
+                submission,
+                topic,
+                vote_choice,
+                nonce,
+            } => {
+                let link = submission_topic_link(&self.program.id(), &submission, &topic);
+                request
+                    .accounts(AccountsAll::RevealVoteMeta {
+                        inner: AccountsAll::RevealVote {
+                            state: state_pda,
+                            submission_topic_link: link,
+                            topic,
+                            submission,
+                            vote_commit: self.pda(&[b"vote_commit", link.as_ref(), user.as_ref()]),
+                            validator: user,
+                            acting_signer: relayer,
+                            session_key: None,
+                            payer: relayer,
+                            system_program: system_program::ID,
+                        },
+                        meta_nonce,
+                        instructions: sysvar::instructions::ID,
+                    })
+                    .args(InstructionAll::RevealVoteMeta {
+                        vote_choice,
+                        nonce,
+                        payload_nonce,
+                        expires_at,
+                    })
+            }
+            MetaAction::RequestAiValidation {
+                submission,
+                topic,
+                temp_rep_to_stake,
+                expected_ai_request_index,
+            } => {
+                let link = submission_topic_link(&self.program.id(), &submission, &topic);
+                request
+                    .accounts(AccountsAll::RequestAiValidationMeta {
+                        requester: user,
+                        payer: relayer,
+                        submission,
+                        topic,
+                        submission_topic_link: link,
+                        user_topic_balance: self.user_topic_balance(&user, &topic),
+                        ai_validation_request: self.pda(&[
+                            b"ai_request",
+                            link.as_ref(),
+                            user.as_ref(),
+                            &expected_ai_request_index.to_le_bytes(),
+                        ]),
+                        meta_nonce,
+                        instructions: sysvar::instructions::ID,
+                        system_program: system_program::ID,
+                    })
+                    .args(InstructionAll::RequestAiValidationMeta {
+                        temp_rep_to_stake,
+                        expected_ai_request_index,
+                        payload_nonce,
+                        expires_at,
+                    })
+            }
+        };
+
+        request
+            .instructions()?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Request produced no instruction"))
+    }
+
+    /// The optional `(rep_ata, parent_topic_balance)` accounts the topic's participation
+    /// rules need, `None` where there is no rule or the account does not exist
+    fn eligibility_accounts(
+        &self,
+        topic_pda: &Pubkey,
+        user: &Pubkey,
+    ) -> Result<(Option<Pubkey>, Option<Pubkey>)> {
+        let topic: TopicAccount = self.program.account(*topic_pda)?;
+        let rep_ata = if topic.min_rep_balance > 0 {
+            self.program
+                .account::<UserProfileAccount>(self.pda(&[b"user_profile", user.as_ref()]))
+                .ok()
+                .map(|profile| profile.user_rep_ata)
+                .filter(|ata| *ata != Pubkey::default())
+        } else {
+            None
+        };
+        let parent_topic_balance = topic.parent_topic.and_then(|parent_topic| {
+            let balance = self.user_topic_balance(user, &parent_topic);
+            self.program
+                .rpc()
+                .get_account(&balance)
+                .ok()
+                .map(|_| balance)
+        });
+        Ok((rep_ata, parent_topic_balance))
+    }
+
+    /// `initialize_user_topic_balance`, paid by the relayer, if the user has not joined the
+    /// topic yet, so their first submission, stake or vote there needs no separate step
+    fn init_topic_balance_if_missing(
+        &self,
+        user: &Pubkey,
+        topic: &Pubkey,
+    ) -> Result<Option<Instruction>> {
+        let user_topic_balance = self.user_topic_balance(user, topic);
+        if self.program.rpc().get_account(&user_topic_balance).is_ok() {
+            return Ok(None);
+        }
+        let (user_rep_ata, parent_topic_balance) = self.eligibility_accounts(topic, user)?;
+        Ok(self
+            .program
+            .request()
+            .accounts(AccountsAll::InitializeUserTopicBalance {
+                payer: self.program.payer(),
+                user: *user,
+                user_profile: self.pda(&[b"user_profile", user.as_ref()]),
+                topic: *topic,
+                user_topic_balance,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+                user_rep_ata,
+                parent_topic_balance,
+            })
+            .args(InstructionAll::InitializeUserTopicBalance {})
+            .instructions()?
+            .pop())
+    }
+
+    fn user_topic_balance(&self, user: &Pubkey, topic: &Pubkey) -> Pubkey {
+        self.pda(&[b"user_topic_balance", user.as_ref(), topic.as_ref()])
+    }
+
+    fn pda(&self, seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &self.program.id()).0
+    }
+}
+
+/// Protocol mints are Token-2022 mints, so their ATAs are derived under that program
+fn token_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &anchor_spl::token_2022::ID)
+}
+
+/// An ed25519 program instruction verifying `signature` by `pubkey` over `message`
+///
+/// The key, signature and message all live in the instruction's own data (instruction
+/// indices `u16::MAX`), which is the only layout the program's `verify_meta_tx` accepts.
+pub fn ed25519_verify_instruction(
+    pubkey: &Pubkey,
+    signature: &Signature,
+    message: &[u8],
+) -> Instruction {
+    // [num_signatures: u8, padding: u8] then 7 u16 offsets
+    const HEADER_LEN: usize = 2 + 14;
+    let pubkey_offset = HEADER_LEN;
+    let signature_offset = pubkey_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for offset in [
+        signature_offset as u16,
+        u16::MAX,
+        pubkey_offset as u16,
+        u16::MAX,
+        message_offset as u16,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
@@ -0,0 +1,14 @@
+//! Off-chain half of the protocol's meta-transactions
+//!
+//! A user signs a [`MetaPayload`] naming a Category B action, their next nonce and an expiry.
+//! A relayer checks it, wraps it into an `[ed25519 verify, *_meta instruction]`
+//! transaction with [`MetaTxBuilder`] and pays the fee, using [`NonceTracker`] to reject
+//! payloads whose nonce is not the user's next one.
+
+pub mod builder;
+pub mod nonce;
+pub mod payload;
+
+pub use builder::{ed25519_verify_instruction, MetaTxBuilder};
+pub use nonce::NonceTracker;
+pub use payload::{MetaAction, MetaPayload, SignedPayload};
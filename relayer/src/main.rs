@@ -0,0 +1,109 @@
+mod rate_limit;
+mod server;
+
+use alignment_relayer::{
+    nonce::fetch_nonce, payload::unix_now, MetaAction, MetaPayload, MetaTxBuilder,
+};
+use anchor_client::solana_sdk::signature::{read_keypair_file, Signer};
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand};
+use std::time::Duration;
+
+use rate_limit::RateLimiter;
+use server::RelayServer;
+
+/// Relays user-signed meta-transaction payloads, paying their fees
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Cli {
+    /// Path to the relayer's keypair file (pays transaction fees)
+    #[arg(long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Solana cluster (localnet, devnet, mainnet or custom URL)
+    #[arg(long, default_value = "localnet")]
+    cluster: String,
+
+    /// Program ID for the Alignment Protocol
+    #[arg(long, default_value = "FtHfXYCwuVEb8LVkNwNMmqMVooyg2fxkoT8i9bkEcvKW")]
+    program_id: String,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Serve the HTTP relay API
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+
+        /// Payloads relayed per user within the rate window
+        #[arg(long, default_value_t = 10)]
+        max_requests: usize,
+
+        /// Length of the rate window in seconds
+        #[arg(long, default_value_t = 60)]
+        rate_window: u64,
+    },
+
+    /// Sign a payload as a user and print it as JSON, for posting to `/relay` when testing
+    Sign {
+        /// Path to the user's keypair file
+        #[arg(long)]
+        user_keypair: String,
+
+        /// The action as JSON, e.g. '{"type":"stake_topic_specific_tokens","topic":"...","amount":10}'
+        #[arg(long)]
+        action: String,
+
+        /// Seconds the signed payload stays valid
+        #[arg(long, default_value_t = 120)]
+        valid_for: u64,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let program = alignment_client::setup_client(&cli.keypair, &cli.cluster, &cli.program_id)?;
+
+    match cli.command {
+        Commands::Serve {
+            bind,
+            max_requests,
+            rate_window,
+        } => {
+            let server = tiny_http::Server::http(&bind).map_err(|e| anyhow!("{}", e))?;
+            println!(
+                "Relayer {} for {} listening on {}",
+                program.payer(),
+                program.id(),
+                bind
+            );
+            let limiter = RateLimiter::new(max_requests, Duration::from_secs(rate_window));
+            RelayServer::new(MetaTxBuilder::new(program), limiter).run(&server);
+            Ok(())
+        }
+        Commands::Sign {
+            user_keypair,
+            action,
+            valid_for,
+        } => {
+            let user_keypair = shellexpand::tilde(&user_keypair).to_string();
+            let user = read_keypair_file(&user_keypair)
+                .map_err(|e| anyhow!("Failed to read user keypair file: {}", e))?;
+            let action: MetaAction = serde_json::from_str(&action)?;
+            let payload = MetaPayload {
+                user: user.pubkey(),
+                action,
+                payload_nonce: fetch_nonce(&program, &user.pubkey())?,
+                expires_at: unix_now() + valid_for,
+            };
+            let signed = payload.sign(&user, &program.id());
+            println!("{}", serde_json::to_string_pretty(&signed)?);
+            Ok(())
+        }
+    }
+}
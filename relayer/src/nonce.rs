@@ -0,0 +1,64 @@
+use alignment_protocol::MetaNonce as MetaNonceAccount;
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair,
+};
+use anchor_client::Program;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Per-user cache of the next meta-transaction nonce the program will accept
+///
+/// Nonces are read from the user's `MetaNonce` PDA the first time a user is seen and then
+/// advanced locally as the relayer's transactions land. A user without a nonce PDA is at 0;
+/// the builder creates the PDA in the same transaction.
+#[derive(Default)]
+pub struct NonceTracker {
+    next: HashMap<Pubkey, u64>,
+}
+
+impl NonceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The nonce `user`'s next payload must carry
+    pub fn next_nonce(&mut self, program: &Program<Rc<Keypair>>, user: &Pubkey) -> Result<u64> {
+        if let Some(nonce) = self.next.get(user) {
+            return Ok(*nonce);
+        }
+        let nonce = fetch_nonce(program, user)?;
+        self.next.insert(*user, nonce);
+        Ok(nonce)
+    }
+
+    /// Record that the payload carrying `nonce` landed on-chain
+    pub fn confirm(&mut self, user: &Pubkey, nonce: u64) {
+        self.next.insert(*user, nonce + 1);
+    }
+
+    /// Forget `user`'s cached nonce so the next lookup re-reads the chain, e.g. after a
+    /// failed send or a payload relayed by someone else
+    pub fn invalidate(&mut self, user: &Pubkey) {
+        self.next.remove(user);
+    }
+}
+
+/// The nonce stored on-chain for `user`, or 0 if their nonce PDA does not exist yet
+pub fn fetch_nonce(program: &Program<Rc<Keypair>>, user: &Pubkey) -> Result<u64> {
+    let meta_nonce = meta_nonce_pda(&program.id(), user);
+    let exists = program
+        .rpc()
+        .get_account_with_commitment(&meta_nonce, CommitmentConfig::confirmed())?
+        .value
+        .is_some();
+    if !exists {
+        return Ok(0);
+    }
+    let account: MetaNonceAccount = program.account(meta_nonce)?;
+    Ok(account.nonce)
+}
+
+pub fn meta_nonce_pda(program_id: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"meta_nonce", user.as_ref()], program_id).0
+}
@@ -0,0 +1,298 @@
+use alignment_protocol::{helpers::meta_tx_digest, instruction as InstructionAll, VoteChoice};
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+};
+use anchor_lang::InstructionData;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A Category B instruction a user asks a relayer to send for them
+///
+/// Votes and AI requests name the submission and topic; the submission-topic link they act on
+/// is derived from those.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetaAction {
+    SubmitDataToTopic {
+        #[serde(with = "display_from_str")]
+        topic: Pubkey,
+        data_reference: String,
+        current_submission_index: u64,
+    },
+    StakeTopicSpecificTokens {
+        #[serde(with = "display_from_str")]
+        topic: Pubkey,
+        amount: u64,
+    },
+    CommitVote {
+        #[serde(with = "display_from_str")]
+        submission: Pubkey,
+        #[serde(with = "display_from_str")]
+        topic: Pubkey,
+        #[serde(with = "hex_bytes")]
+        vote_hash: [u8; 32],
+        temp_rep_amount: u64,
+        perm_rep_amount: u64,
+        /// Delegation PDAs to draw on, in order; passed as the instruction's remaining accounts
+        #[serde(default, with = "display_from_str_vec")]
+        delegations: Vec<Pubkey>,
+        /// tempRep to draw from each of `delegations`, or empty to draw each in full
+        #[serde(default)]
+        delegation_amounts: Vec<u64>,
+    },
+    RevealVote {
+        #[serde(with = "display_from_str")]
+        submission: Pubkey,
+        #[serde(with = "display_from_str")]
+        topic: Pubkey,
+        #[serde(with = "vote_choice")]
+        vote_choice: VoteChoice,
+        nonce: String,
+    },
+    RequestAiValidation {
+        #[serde(with = "display_from_str")]
+        submission: Pubkey,
+        #[serde(with = "display_from_str")]
+        topic: Pubkey,
+        temp_rep_to_stake: u64,
+        expected_ai_request_index: u64,
+    },
+}
+
+impl MetaAction {
+    /// Data of the base (non-meta) instruction, which is what the user's signature covers
+    pub fn base_instruction_data(&self) -> Vec<u8> {
+        match self.clone() {
+            MetaAction::SubmitDataToTopic {
+                data_reference,
+                current_submission_index,
+                ..
+            } => InstructionAll::SubmitDataToTopic {
+                data_reference,
+                current_submission_index,
+            }
+            .data(),
+            MetaAction::StakeTopicSpecificTokens { amount, .. } => {
+                InstructionAll::StakeTopicSpecificTokens { amount }.data()
+            }
+            MetaAction::CommitVote {
+                vote_hash,
+                temp_rep_amount,
+                perm_rep_amount,
+                delegation_amounts,
+                ..
+            } => InstructionAll::CommitVote {
+                vote_hash,
+                temp_rep_amount,
+                perm_rep_amount,
+                delegation_amounts,
+            }
+            .data(),
+            MetaAction::RevealVote {
+                vote_choice, nonce, ..
+            } => InstructionAll::RevealVote { vote_choice, nonce }.data(),
+            MetaAction::RequestAiValidation {
+                temp_rep_to_stake,
+                expected_ai_request_index,
+                ..
+            } => InstructionAll::RequestAiValidation {
+                temp_rep_to_stake,
+                expected_ai_request_index,
+            }
+            .data(),
+        }
+    }
+
+    /// Account the signature is bound to: the topic for submissions and stakes, the
+    /// submission-topic link for votes and AI requests
+    pub fn scope(&self, program_id: &Pubkey) -> Pubkey {
+        match self {
+            MetaAction::SubmitDataToTopic { topic, .. }
+            | MetaAction::StakeTopicSpecificTokens { topic, .. } => *topic,
+            MetaAction::CommitVote {
+                submission, topic, ..
+            }
+            | MetaAction::RevealVote {
+                submission, topic, ..
+            }
+            | MetaAction::RequestAiValidation {
+                submission, topic, ..
+            } => submission_topic_link(program_id, submission, topic),
+        }
+    }
+
+    /// Keys of the `*_meta` instruction's remaining accounts, in order, which the signature
+    /// also covers
+    pub fn remaining_accounts(&self) -> Vec<Pubkey> {
+        match self {
+            MetaAction::CommitVote { delegations, .. } => delegations.clone(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+pub(crate) fn submission_topic_link(
+    program_id: &Pubkey,
+    submission: &Pubkey,
+    topic: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"submission_topic_link",
+            submission.as_ref(),
+            topic.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// What a user signs: an action bound to their next nonce and an expiry
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MetaPayload {
+    #[serde(with = "display_from_str")]
+    pub user: Pubkey,
+    pub action: MetaAction,
+    pub payload_nonce: u64,
+    /// Unix seconds after which the program rejects the payload
+    pub expires_at: u64,
+}
+
+impl MetaPayload {
+    /// The message the user signs, as recomputed on-chain by the `*_meta` instructions
+    pub fn digest(&self, program_id: &Pubkey) -> [u8; 32] {
+        meta_tx_digest(
+            &self.action.base_instruction_data(),
+            &self.action.scope(program_id),
+            &self.action.remaining_accounts(),
+            self.payload_nonce,
+            self.expires_at,
+        )
+    }
+
+    /// Whether the payload has expired by the local clock. The program checks the cluster
+    /// clock too; dropping expired payloads early saves the fee of a failing transaction.
+    pub fn is_expired(&self) -> bool {
+        unix_now() > self.expires_at
+    }
+
+    /// Sign the payload as `user`, which must be the payload's user
+    pub fn sign(self, user: &Keypair, program_id: &Pubkey) -> SignedPayload {
+        let signature = user.sign_message(&self.digest(program_id));
+        SignedPayload {
+            payload: self,
+            signature,
+        }
+    }
+}
+
+/// A payload together with the user's ed25519 signature over its digest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedPayload {
+    #[serde(flatten)]
+    pub payload: MetaPayload,
+    #[serde(with = "display_from_str")]
+    pub signature: Signature,
+}
+
+impl SignedPayload {
+    /// Check the signature off-chain, so a bad payload never costs the relayer a fee
+    pub fn verify(&self, program_id: &Pubkey) -> bool {
+        self.signature
+            .verify(self.payload.user.as_ref(), &self.payload.digest(program_id))
+    }
+}
+
+/// Seconds since the unix epoch by the local clock, for setting and checking `expires_at`
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Pubkeys and signatures as their base58 strings
+mod display_from_str {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Lists of pubkeys as lists of base58 strings
+mod display_from_str_vec {
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use serde::{de, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(keys: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(keys.len()))?;
+        for key in keys {
+            seq.serialize_element(&key.to_string())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Pubkey>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|key| key.parse().map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+/// 32-byte hashes as hex strings
+mod hex_bytes {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(String::deserialize(deserializer)?, &mut bytes)
+            .map_err(de::Error::custom)?;
+        Ok(bytes)
+    }
+}
+
+/// Vote choices as "yes" / "no"
+mod vote_choice {
+    use alignment_protocol::VoteChoice;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(choice: &VoteChoice, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match choice {
+            VoteChoice::Yes => "yes",
+            VoteChoice::No => "no",
+        })
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<VoteChoice, D::Error> {
+        match String::deserialize(deserializer)?.to_lowercase().as_str() {
+            "yes" => Ok(VoteChoice::Yes),
+            "no" => Ok(VoteChoice::No),
+            other => Err(de::Error::custom(format!("invalid vote choice: {}", other))),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Sliding-window limit on how many payloads each user can have relayed
+pub struct RateLimiter {
+    max_requests: usize,
+    window: Duration,
+    hits: HashMap<Pubkey, VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: usize, window: Duration) -> Self {
+        Self {
+            max_requests,
+            window,
+            hits: HashMap::new(),
+        }
+    }
+
+    /// Count a request from `user`, returning false if they are over the limit
+    pub fn allow(&mut self, user: &Pubkey) -> bool {
+        let now = Instant::now();
+        let hits = self.hits.entry(*user).or_default();
+        while hits
+            .front()
+            .is_some_and(|hit| now.duration_since(*hit) >= self.window)
+        {
+            hits.pop_front();
+        }
+        if hits.len() >= self.max_requests {
+            return false;
+        }
+        hits.push_back(now);
+        true
+    }
+}
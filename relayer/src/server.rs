@@ -0,0 +1,119 @@
+use alignment_relayer::{MetaTxBuilder, NonceTracker, SignedPayload};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde_json::{json, Value};
+use std::str::FromStr;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::rate_limit::RateLimiter;
+
+/// HTTP front end of the relayer
+///
+/// - `GET /nonce/<user>` returns the nonce the user's next payload must carry
+/// - `POST /relay` takes a JSON `SignedPayload` and returns the transaction signature
+pub struct RelayServer {
+    builder: MetaTxBuilder,
+    nonces: NonceTracker,
+    limiter: RateLimiter,
+}
+
+impl RelayServer {
+    pub fn new(builder: MetaTxBuilder, limiter: RateLimiter) -> Self {
+        Self {
+            builder,
+            nonces: NonceTracker::new(),
+            limiter,
+        }
+    }
+
+    /// Serve requests one at a time, so nonces for a user are handed out in order
+    pub fn run(&mut self, server: &Server) {
+        for mut request in server.incoming_requests() {
+            let (status, body) = self.handle(&mut request);
+            let response = Response::from_string(body.to_string())
+                .with_status_code(status)
+                .with_header(
+                    Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                        .expect("Static header is valid"),
+                );
+            if let Err(e) = request.respond(response) {
+                println!("Failed to respond: {}", e);
+            }
+        }
+    }
+
+    fn handle(&mut self, request: &mut Request) -> (u16, Value) {
+        let url = request.url().to_string();
+        match (request.method(), url.as_str()) {
+            (Method::Get, path) if path.starts_with("/nonce/") => {
+                self.nonce(&path["/nonce/".len()..])
+            }
+            (Method::Post, "/relay") => {
+                let mut body = String::new();
+                if let Err(e) = request.as_reader().read_to_string(&mut body) {
+                    return error(400, format!("Could not read body: {}", e));
+                }
+                match serde_json::from_str::<SignedPayload>(&body) {
+                    Ok(signed) => self.relay(&signed),
+                    Err(e) => error(400, format!("Invalid payload: {}", e)),
+                }
+            }
+            _ => error(404, "Not found"),
+        }
+    }
+
+    fn nonce(&mut self, user: &str) -> (u16, Value) {
+        let user = match Pubkey::from_str(user) {
+            Ok(user) => user,
+            Err(e) => return error(400, format!("Invalid user: {}", e)),
+        };
+        match self.nonces.next_nonce(self.builder.program(), &user) {
+            Ok(nonce) => (200, json!({ "user": user.to_string(), "nonce": nonce })),
+            Err(e) => error(502, format!("Could not read nonce: {}", e)),
+        }
+    }
+
+    fn relay(&mut self, signed: &SignedPayload) -> (u16, Value) {
+        let user = signed.payload.user;
+        let program_id = self.builder.program().id();
+
+        // Verify before counting, so nobody can use up another user's quota
+        if !signed.verify(&program_id) {
+            return error(401, "Signature does not match the payload");
+        }
+        if !self.limiter.allow(&user) {
+            return error(429, "Rate limit exceeded");
+        }
+        if signed.payload.is_expired() {
+            return error(400, "Payload has expired");
+        }
+        let expected = match self.nonces.next_nonce(self.builder.program(), &user) {
+            Ok(nonce) => nonce,
+            Err(e) => return error(502, format!("Could not read nonce: {}", e)),
+        };
+        if signed.payload.payload_nonce != expected {
+            return (
+                409,
+                json!({ "error": "Unexpected payload nonce", "expected_nonce": expected }),
+            );
+        }
+
+        match self.builder.send(signed) {
+            Ok(tx_sig) => {
+                self.nonces.confirm(&user, expected);
+                println!(
+                    "Relayed nonce {} for {} (txSig: {})",
+                    expected, user, tx_sig
+                );
+                (200, json!({ "signature": tx_sig.to_string() }))
+            }
+            Err(e) => {
+                self.nonces.invalidate(&user);
+                error(502, format!("Transaction failed: {}", e))
+            }
+        }
+    }
+}
+
+fn error(status: u16, message: impl Into<String>) -> (u16, Value) {
+    (status, json!({ "error": message.into() }))
+}
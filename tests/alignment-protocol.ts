@@ -13,6 +13,12 @@ import { runVotingTests } from "./sections/07-voting";
 import { runFinalizationTests } from "./sections/08-finalization";
 import { runTokenLockingTests } from "./sections/09-token-locking-tests";
 import { runValidationTests } from "./sections/10-validation-tests";
+import { runDelegationTests } from "./sections/11-delegation";
+import { runMetaTransactionTests } from "./sections/12-meta-transactions";
+import { runSessionKeyTests } from "./sections/13-session-keys";
+import { runFaucetTests } from "./sections/14-faucet";
+import { runAdminMintTests } from "./sections/15-admin-mint";
+import { runAiOracleTests } from "./sections/16-ai-oracle";
 
 // Import test context type
 import { TestContext } from "./utils/test-setup";
@@ -191,4 +197,10 @@ describe("Alignment Protocol Tests", () => {
   runFinalizationTests(ctx);
   runTokenLockingTests(ctx);
   runValidationTests(ctx);
+  runDelegationTests(ctx);
+  runMetaTransactionTests(ctx);
+  runSessionKeyTests(ctx);
+  runFaucetTests(ctx);
+  runAdminMintTests(ctx);
+  runAiOracleTests(ctx);
 });
@@ -1,7 +1,15 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { web3 } from "@coral-xyz/anchor";
-import { TOKEN_PROGRAM_ID, getMint } from "@solana/spl-token";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  getExtensionTypes,
+  getMetadataPointerState,
+  getMint,
+  getPermanentDelegate,
+  getTokenMetadata,
+} from "@solana/spl-token";
 import { TestContext } from "../utils/test-setup";
 
 export function runInitializationTests(ctx: TestContext): void {
@@ -33,6 +41,11 @@ export function runInitializationTests(ctx: TestContext): void {
       );
       expect(stateAcc.topicCount.toNumber()).to.equal(0);
       expect(stateAcc.tokensToMint.toNumber()).to.equal(0);
+      expect(stateAcc.validatorRewardEmission.toNumber()).to.equal(0);
+      expect(stateAcc.delegationCommissionBps).to.equal(0);
+      expect(stateAcc.finalizationReward.toNumber()).to.equal(0);
+      expect(stateAcc.finalizationRewardMinVotingPower.toNumber()).to.equal(0);
+      expect(stateAcc.oracleCommitteeActive).to.equal(false);
 
       // Check default voting phase durations (24 hours in seconds)
       expect(stateAcc.defaultCommitPhaseDuration.toNumber()).to.equal(
@@ -49,7 +62,7 @@ export function runInitializationTests(ctx: TestContext): void {
           state: ctx.statePda,
           tempAlignMint: ctx.tempAlignMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
           rent: web3.SYSVAR_RENT_PUBKEY,
         })
@@ -68,7 +81,7 @@ export function runInitializationTests(ctx: TestContext): void {
           state: ctx.statePda,
           alignMint: ctx.alignMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
           rent: web3.SYSVAR_RENT_PUBKEY,
         })
@@ -84,7 +97,7 @@ export function runInitializationTests(ctx: TestContext): void {
           state: ctx.statePda,
           tempRepMint: ctx.tempRepMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
           rent: web3.SYSVAR_RENT_PUBKEY,
         })
@@ -100,7 +113,7 @@ export function runInitializationTests(ctx: TestContext): void {
           state: ctx.statePda,
           repMint: ctx.repMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
           rent: web3.SYSVAR_RENT_PUBKEY,
         })
@@ -128,18 +141,26 @@ export function runInitializationTests(ctx: TestContext): void {
       const tempAlignMintInfo = await getMint(
         ctx.provider.connection,
         ctx.tempAlignMintPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const alignMintInfo = await getMint(
         ctx.provider.connection,
         ctx.alignMintPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const tempRepMintInfo = await getMint(
         ctx.provider.connection,
         ctx.tempRepMintPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const repMintInfo = await getMint(
         ctx.provider.connection,
         ctx.repMintPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       // Check all mints have 0 decimals
@@ -173,6 +194,109 @@ export function runInitializationTests(ctx: TestContext): void {
       expect(repMintInfo.freezeAuthority.toString()).to.equal(
         ctx.statePda.toString(),
       );
+
+      // tempAlign, tempRep and Rep are soulbound, with the state PDA as permanent delegate
+      for (const mintInfo of [
+        tempAlignMintInfo,
+        tempRepMintInfo,
+        repMintInfo,
+      ]) {
+        expect(getExtensionTypes(mintInfo.tlvData)).to.include.members([
+          ExtensionType.NonTransferable,
+          ExtensionType.PermanentDelegate,
+        ]);
+        expect(getPermanentDelegate(mintInfo).delegate.toString()).to.equal(
+          ctx.statePda.toString(),
+        );
+      }
+      // Align stays transferable
+      expect(getExtensionTypes(alignMintInfo.tlvData)).to.not.include(
+        ExtensionType.NonTransferable,
+      );
+
+      // Every mint points at itself for metadata, updatable by the state PDA
+      for (const mintInfo of [
+        tempAlignMintInfo,
+        alignMintInfo,
+        tempRepMintInfo,
+        repMintInfo,
+      ]) {
+        const pointer = getMetadataPointerState(mintInfo);
+        expect(pointer.authority.toString()).to.equal(ctx.statePda.toString());
+        expect(pointer.metadataAddress.toString()).to.equal(
+          mintInfo.address.toString(),
+        );
+      }
+    });
+
+    it("Sets and updates mint metadata", async () => {
+      const setMetadata = (name: string, symbol: string, uri: string) =>
+        ctx.program.methods
+          .setMintMetadata(name, symbol, uri)
+          .accounts({
+            state: ctx.statePda,
+            mint: ctx.alignMintPda,
+            authority: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+      await setMetadata("Alignment", "ALIGN", "https://example.com/a.json");
+      let metadata = await getTokenMetadata(
+        ctx.provider.connection,
+        ctx.alignMintPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(metadata.name).to.equal("Alignment");
+      expect(metadata.symbol).to.equal("ALIGN");
+      expect(metadata.uri).to.equal("https://example.com/a.json");
+      expect(metadata.updateAuthority.toString()).to.equal(
+        ctx.statePda.toString(),
+      );
+
+      // A longer value makes the mint grow; the authority covers the rent
+      await setMetadata(
+        "Alignment Token",
+        "ALIGN",
+        "https://example.com/metadata/align-token.json",
+      );
+      metadata = await getTokenMetadata(
+        ctx.provider.connection,
+        ctx.alignMintPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(metadata.name).to.equal("Alignment Token");
+      expect(metadata.uri).to.equal(
+        "https://example.com/metadata/align-token.json",
+      );
+
+      try {
+        await ctx.program.methods
+          .setMintMetadata("Temp Alignment", "TEMPALIGN", "")
+          .accounts({
+            state: ctx.statePda,
+            mint: ctx.tempAlignMintPda,
+            authority: ctx.user3Keypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([ctx.user3Keypair])
+          .rpc();
+        expect.fail("Non-authority should not set mint metadata");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ConstraintHasOne");
+      }
+
+      try {
+        await setMetadata("Alignment", "ALIGNMENTTOKEN", "");
+        expect.fail("Symbol over the length limit should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TokenMetadataTooLong");
+      }
     });
 
     it("Sets tokens_to_mint to a non-zero value", async () => {
@@ -195,5 +319,24 @@ export function runInitializationTests(ctx: TestContext): void {
       const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
       expect(stateAcc.tokensToMint.toNumber()).to.equal(tokensToMint);
     });
+
+    it("Rejects validator reward emission updates from non-authority", async () => {
+      try {
+        await ctx.program.methods
+          .updateValidatorRewardEmission(new anchor.BN(10))
+          .accounts({
+            state: ctx.statePda,
+            authority: ctx.user3Keypair.publicKey,
+          })
+          .signers([ctx.user3Keypair])
+          .rpc();
+        expect.fail("Non-authority should not update the reward emission");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ConstraintHasOne");
+      }
+
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      expect(stateAcc.validatorRewardEmission.toNumber()).to.equal(0);
+    });
   });
 }
@@ -38,6 +38,11 @@ export function runTopicManagementTests(ctx: TestContext): void {
       );
       expect(topicAcc.submissionCount.toNumber()).to.equal(0);
       expect(topicAcc.isActive).to.be.true;
+      expect(topicAcc.tokensToMint).to.be.null;
+      expect(topicAcc.repMintRatioBps).to.be.null;
+      expect(topicAcc.emissionBudgetPerEpoch).to.be.null;
+      expect(topicAcc.emissionEpochDuration.toNumber()).to.equal(24 * 60 * 60);
+      expect(topicAcc.emissionEpochMinted.toNumber()).to.equal(0);
 
       // Verify that the topic count in state was incremented
       const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
@@ -102,5 +107,386 @@ export function runTopicManagementTests(ctx: TestContext): void {
         customRevealDuration,
       );
     });
+
+    it("Sets per-topic reward overrides", async () => {
+      await ctx.program.methods
+        .updateTopic(null, null, null, new anchor.BN(50), 5_000)
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      const topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.tokensToMint.toNumber()).to.equal(50);
+      expect(topicAcc.repMintRatioBps).to.equal(5_000);
+      // Untouched fields keep their values
+      expect(topicAcc.isActive).to.be.true;
+      expect(topicAcc.commitPhaseDuration.toNumber()).to.equal(12 * 60 * 60);
+    });
+
+    it("Rejects reward override updates from non-creator", async () => {
+      try {
+        await ctx.program.methods
+          .updateTopic(null, null, null, new anchor.BN(1_000_000), null)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.validatorKeypair.publicKey,
+          })
+          .signers([ctx.validatorKeypair])
+          .rpc();
+        expect.fail("Non-creator should not update the topic");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidAuthority");
+      }
+    });
+
+    it("Rejects reward override updates from the topic creator", async () => {
+      try {
+        await ctx.program.methods
+          .updateTopic(null, null, null, new anchor.BN(10), null)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.contributorKeypair.publicKey,
+          })
+          .signers([ctx.contributorKeypair])
+          .rpc();
+        expect.fail("Only the protocol authority may set reward overrides");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidAuthority");
+      }
+    });
+
+    it("Rejects a tokens_to_mint override above the global value", async () => {
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      try {
+        await ctx.program.methods
+          .updateTopic(null, null, null, stateAcc.tokensToMint.addn(1), null)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Override above the global tokens_to_mint should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TopicTokensToMintTooHigh");
+      }
+    });
+
+    it("Rejects a Rep mint ratio above 1:1", async () => {
+      try {
+        await ctx.program.methods
+          .updateTopic(null, null, null, null, 10_001)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Ratio above 10000 bps should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidRepMintRatio");
+      }
+    });
+
+    it("Sets a per-epoch emission budget on a topic", async () => {
+      await ctx.program.methods
+        .updateTopicEmissionBudget(new anchor.BN(1_000), new anchor.BN(3600))
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      const topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.emissionBudgetPerEpoch.toNumber()).to.equal(1_000);
+      expect(topicAcc.emissionEpochDuration.toNumber()).to.equal(3600);
+    });
+
+    it("Rejects a zero-length emission epoch", async () => {
+      try {
+        await ctx.program.methods
+          .updateTopicEmissionBudget(new anchor.BN(1_000), new anchor.BN(0))
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Zero epoch duration should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "InvalidEmissionEpochDuration",
+        );
+      }
+    });
+
+    it("Lets the topic creator lower but not raise or remove the emission budget", async () => {
+      await ctx.program.methods
+        .updateTopicEmissionBudget(new anchor.BN(500), null)
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.contributorKeypair.publicKey,
+        })
+        .signers([ctx.contributorKeypair])
+        .rpc();
+      let topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.emissionBudgetPerEpoch.toNumber()).to.equal(500);
+
+      for (const budget of [new anchor.BN(501), null]) {
+        try {
+          await ctx.program.methods
+            .updateTopicEmissionBudget(budget, null)
+            .accounts({
+              state: ctx.statePda,
+              topic: ctx.topic2Pda,
+              authority: ctx.contributorKeypair.publicKey,
+            })
+            .signers([ctx.contributorKeypair])
+            .rpc();
+          expect.fail("Creator should not raise or remove the budget");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("InvalidAuthority");
+        }
+      }
+
+      // Restore the authority's budget for later sections
+      await ctx.program.methods
+        .updateTopicEmissionBudget(new anchor.BN(1_000), null)
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+      topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.emissionBudgetPerEpoch.toNumber()).to.equal(1_000);
+    });
+
+    it("Sets the AI voting weight and rejects weights above 100%", async () => {
+      let topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.aiWeightBps).to.equal(10_000);
+      expect(topicAcc.aiConfidenceScaling).to.equal(false);
+
+      await ctx.program.methods
+        .updateTopicAiWeight(5_000, true)
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.aiWeightBps).to.equal(5_000);
+      expect(topicAcc.aiConfidenceScaling).to.equal(true);
+
+      try {
+        await ctx.program.methods
+          .updateTopicAiWeight(10_001, false)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("AI weight above 10000 bps should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidAiWeight");
+      }
+    });
+
+    it("Assigns a topic its own oracle and falls back to the global one", async () => {
+      let topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.oraclePubkey).to.be.null;
+      expect(topicAcc.oracleCommitteeActive).to.equal(false);
+
+      const topicOracle = web3.Keypair.generate().publicKey;
+      await ctx.program.methods
+        .updateTopicOracle(topicOracle)
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.oraclePubkey.toString()).to.equal(topicOracle.toString());
+
+      await ctx.program.methods
+        .updateTopicOracle(null)
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.oraclePubkey).to.be.null;
+    });
+
+    it("Rejects topic oracle assignment by the topic creator", async () => {
+      try {
+        await ctx.program.methods
+          .updateTopicOracle(ctx.contributorKeypair.publicKey)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.contributorKeypair.publicKey,
+          })
+          .signers([ctx.contributorKeypair])
+          .rpc();
+        expect.fail("Only the protocol authority may assign topic oracles");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidAuthority");
+      }
+    });
+
+    describe("Topic roles", () => {
+      let validatorTopic2RolePda: web3.PublicKey;
+
+      before("Derive topic role PDA", () => {
+        [validatorTopic2RolePda] = web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("topic_role"),
+            ctx.topic2Pda.toBuffer(),
+            ctx.validatorKeypair.publicKey.toBuffer(),
+          ],
+          ctx.program.programId,
+        );
+      });
+
+      it("Rejects an empty or unknown role mask", async () => {
+        for (const roles of [0, 1 << 7]) {
+          try {
+            await ctx.program.methods
+              .grantTopicRole(roles)
+              .accounts({
+                state: ctx.statePda,
+                topic: ctx.topic2Pda,
+                topicRole: validatorTopic2RolePda,
+                holder: ctx.validatorKeypair.publicKey,
+                authority: ctx.contributorKeypair.publicKey,
+                systemProgram: web3.SystemProgram.programId,
+              })
+              .signers([ctx.contributorKeypair])
+              .rpc();
+            expect.fail("Invalid role mask should have failed");
+          } catch (error) {
+            expect(error.error.errorCode.code).to.equal("InvalidTopicRole");
+          }
+        }
+      });
+
+      it("Rejects oracle role grants by the topic creator", async () => {
+        try {
+          await ctx.program.methods
+            .grantTopicRole(1 << 2)
+            .accounts({
+              state: ctx.statePda,
+              topic: ctx.topic2Pda,
+              topicRole: validatorTopic2RolePda,
+              holder: ctx.validatorKeypair.publicKey,
+              authority: ctx.contributorKeypair.publicKey,
+              systemProgram: web3.SystemProgram.programId,
+            })
+            .signers([ctx.contributorKeypair])
+            .rpc();
+          expect.fail("Only the protocol authority may grant the oracle role");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("InvalidAuthority");
+        }
+      });
+
+      it("Lets a moderator granted by the topic creator update the topic", async () => {
+        // Moderator role only
+        await ctx.program.methods
+          .grantTopicRole(1)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            topicRole: validatorTopic2RolePda,
+            holder: ctx.validatorKeypair.publicKey,
+            authority: ctx.contributorKeypair.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([ctx.contributorKeypair])
+          .rpc();
+
+        const roleAcc = await ctx.program.account.topicRole.fetch(
+          validatorTopic2RolePda,
+        );
+        expect(roleAcc.topic.toString()).to.equal(ctx.topic2Pda.toString());
+        expect(roleAcc.holder.toString()).to.equal(
+          ctx.validatorKeypair.publicKey.toString(),
+        );
+        expect(roleAcc.roles).to.equal(1);
+        expect(roleAcc.grantedBy.toString()).to.equal(
+          ctx.contributorKeypair.publicKey.toString(),
+        );
+
+        await ctx.program.methods
+          .updateTopic(null, null, null, null, null)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            topicRole: validatorTopic2RolePda,
+            authority: ctx.validatorKeypair.publicKey,
+          })
+          .signers([ctx.validatorKeypair])
+          .rpc();
+      });
+
+      it("Revokes the role and blocks further updates", async () => {
+        await ctx.program.methods
+          .revokeTopicRole()
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            topicRole: validatorTopic2RolePda,
+            authority: ctx.contributorKeypair.publicKey,
+          })
+          .signers([ctx.contributorKeypair])
+          .rpc();
+
+        const closed = await ctx.provider.connection.getAccountInfo(
+          validatorTopic2RolePda,
+        );
+        expect(closed).to.be.null;
+
+        try {
+          await ctx.program.methods
+            .updateTopic(null, null, null, null, null)
+            .accounts({
+              state: ctx.statePda,
+              topic: ctx.topic2Pda,
+              authority: ctx.validatorKeypair.publicKey,
+            })
+            .signers([ctx.validatorKeypair])
+            .rpc();
+          expect.fail("Revoked moderator should not update the topic");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("InvalidAuthority");
+        }
+      });
+    });
   });
 }
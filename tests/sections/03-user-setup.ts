@@ -1,7 +1,8 @@
+import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { web3 } from "@coral-xyz/anchor";
 import {
-  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   getAccount,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
@@ -67,6 +68,7 @@ export function runUserSetupTests(ctx: TestContext): void {
       expect(contributorProfile.userRepAta.toString()).to.equal(
         web3.PublicKey.default.toString(),
       );
+      expect(contributorProfile.acceptedSubmissionCount.toNumber()).to.equal(0);
 
       // Verify the validator profile was created correctly
       const validatorProfile = await ctx.program.account.userProfile.fetch(
@@ -159,31 +161,43 @@ export function runUserSetupTests(ctx: TestContext): void {
       ctx.contributorAlignAta = await getAssociatedTokenAddress(
         ctx.alignMintPda,
         ctx.contributorKeypair.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       ctx.contributorRepAta = await getAssociatedTokenAddress(
         ctx.repMintPda,
         ctx.contributorKeypair.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       ctx.validatorAlignAta = await getAssociatedTokenAddress(
         ctx.alignMintPda,
         ctx.validatorKeypair.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       ctx.validatorRepAta = await getAssociatedTokenAddress(
         ctx.repMintPda,
         ctx.validatorKeypair.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       ctx.user3AlignAta = await getAssociatedTokenAddress(
         ctx.alignMintPda,
         ctx.user3Keypair.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       ctx.user3RepAta = await getAssociatedTokenAddress(
         ctx.repMintPda,
         ctx.user3Keypair.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       // Create protocol-owned tempAlign account for contributor
@@ -193,6 +207,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           payer: ctx.authorityKeypair.publicKey,
           user: ctx.contributorKeypair.publicKey,
           mint: ctx.tempAlignMintPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -217,6 +232,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           payer: ctx.authorityKeypair.publicKey,
           user: ctx.contributorKeypair.publicKey,
           mint: ctx.tempRepMintPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -241,6 +257,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           payer: ctx.authorityKeypair.publicKey,
           user: ctx.validatorKeypair.publicKey,
           mint: ctx.tempAlignMintPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -265,6 +282,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           payer: ctx.authorityKeypair.publicKey,
           user: ctx.validatorKeypair.publicKey,
           mint: ctx.tempRepMintPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -289,6 +307,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           payer: ctx.authorityKeypair.publicKey,
           user: ctx.user3Keypair.publicKey,
           mint: ctx.tempAlignMintPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -313,6 +332,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           payer: ctx.authorityKeypair.publicKey,
           user: ctx.user3Keypair.publicKey,
           mint: ctx.tempRepMintPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -338,6 +358,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           user: ctx.contributorKeypair.publicKey,
           mint: ctx.alignMintPda,
           userAta: ctx.contributorAlignAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -360,6 +381,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           user: ctx.contributorKeypair.publicKey,
           mint: ctx.repMintPda,
           userAta: ctx.contributorRepAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -382,6 +404,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           user: ctx.validatorKeypair.publicKey,
           mint: ctx.alignMintPda,
           userAta: ctx.validatorAlignAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -404,6 +427,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           user: ctx.validatorKeypair.publicKey,
           mint: ctx.repMintPda,
           userAta: ctx.validatorRepAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -426,6 +450,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           user: ctx.user3Keypair.publicKey,
           mint: ctx.alignMintPda,
           userAta: ctx.user3AlignAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -448,6 +473,7 @@ export function runUserSetupTests(ctx: TestContext): void {
           user: ctx.user3Keypair.publicKey,
           mint: ctx.repMintPda,
           userAta: ctx.user3RepAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .signers([ctx.authorityKeypair])
         .rpc();
@@ -467,6 +493,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const contributorTempAlignData = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(contributorTempAlignData.mint.toString()).to.equal(
         ctx.tempAlignMintPda.toString(),
@@ -479,6 +507,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const contributorTempRepData = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(contributorTempRepData.mint.toString()).to.equal(
         ctx.tempRepMintPda.toString(),
@@ -492,6 +522,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const validatorTempAlignData = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(validatorTempAlignData.mint.toString()).to.equal(
         ctx.tempAlignMintPda.toString(),
@@ -504,6 +536,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const validatorTempRepData = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(validatorTempRepData.mint.toString()).to.equal(
         ctx.tempRepMintPda.toString(),
@@ -517,6 +551,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const user3TempAlignData = await getAccount(
         ctx.provider.connection,
         ctx.user3TempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(user3TempAlignData.mint.toString()).to.equal(
         ctx.tempAlignMintPda.toString(),
@@ -529,6 +565,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const user3TempRepData = await getAccount(
         ctx.provider.connection,
         ctx.user3TempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(user3TempRepData.mint.toString()).to.equal(
         ctx.tempRepMintPda.toString(),
@@ -543,6 +581,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const contributorAlignData = await getAccount(
         ctx.provider.connection,
         ctx.contributorAlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(contributorAlignData.mint.toString()).to.equal(
         ctx.alignMintPda.toString(),
@@ -555,6 +595,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const contributorRepData = await getAccount(
         ctx.provider.connection,
         ctx.contributorRepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(contributorRepData.mint.toString()).to.equal(
         ctx.repMintPda.toString(),
@@ -568,6 +610,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const validatorAlignData = await getAccount(
         ctx.provider.connection,
         ctx.validatorAlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(validatorAlignData.mint.toString()).to.equal(
         ctx.alignMintPda.toString(),
@@ -580,6 +624,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const validatorRepData = await getAccount(
         ctx.provider.connection,
         ctx.validatorRepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(validatorRepData.mint.toString()).to.equal(
         ctx.repMintPda.toString(),
@@ -593,6 +639,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const user3AlignData = await getAccount(
         ctx.provider.connection,
         ctx.user3AlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(user3AlignData.mint.toString()).to.equal(
         ctx.alignMintPda.toString(),
@@ -605,6 +653,8 @@ export function runUserSetupTests(ctx: TestContext): void {
       const user3RepData = await getAccount(
         ctx.provider.connection,
         ctx.user3RepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(user3RepData.mint.toString()).to.equal(ctx.repMintPda.toString());
       expect(user3RepData.owner.toString()).to.equal(
@@ -612,5 +662,181 @@ export function runUserSetupTests(ctx: TestContext): void {
       );
       expect(Number(user3RepData.amount)).to.equal(0);
     });
+
+    it("Enforces topic eligibility rules on new topic balances", async () => {
+      // Require one accepted submission and a parent-topic history in topic 2
+      await ctx.program.methods
+        .updateTopicEligibility(
+          new anchor.BN(1),
+          new anchor.BN(1),
+          ctx.topic1Pda,
+          new anchor.BN(1),
+        )
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      let topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.minRepBalance.toNumber()).to.equal(1);
+      expect(topicAcc.minAcceptedSubmissions.toNumber()).to.equal(1);
+      expect(topicAcc.parentTopic.toString()).to.equal(
+        ctx.topic1Pda.toString(),
+      );
+      expect(topicAcc.minParentTopicRep.toNumber()).to.equal(1);
+
+      // User3 has no Rep, so the first rule fails
+      try {
+        await ctx.program.methods
+          .initializeUserTopicBalance()
+          .accounts({
+            user: ctx.user3Keypair.publicKey,
+            topic: ctx.topic2Pda,
+            payer: ctx.authorityKeypair.publicKey,
+            userRepAta: ctx.user3RepAta,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Ineligible user should not join the topic");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientRepForTopic");
+      }
+
+      // A topic cannot gate on itself
+      try {
+        await ctx.program.methods
+          .updateTopicEligibility(null, null, ctx.topic2Pda, null)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic2Pda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Self-parenting should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidParentTopic");
+      }
+
+      // Reset the rules so later sections can use topic 2 freely
+      await ctx.program.methods
+        .updateTopicEligibility(
+          new anchor.BN(0),
+          new anchor.BN(0),
+          null,
+          new anchor.BN(0),
+        )
+        .accounts({
+          state: ctx.statePda,
+          topic: ctx.topic2Pda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      topicAcc = await ctx.program.account.topic.fetch(ctx.topic2Pda);
+      expect(topicAcc.minRepBalance.toNumber()).to.equal(0);
+      expect(topicAcc.minAcceptedSubmissions.toNumber()).to.equal(0);
+      expect(topicAcc.minParentTopicRep.toNumber()).to.equal(0);
+    });
+
+    it("Onboards a new user in one call and skips existing accounts", async () => {
+      const newUser = web3.Keypair.generate();
+      const [profilePda] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), newUser.publicKey.toBuffer()],
+        ctx.program.programId,
+      );
+      const [tempAlignPda] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user_temp_align"), newUser.publicKey.toBuffer()],
+        ctx.program.programId,
+      );
+      const [tempRepPda] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user_temp_rep"), newUser.publicKey.toBuffer()],
+        ctx.program.programId,
+      );
+      const alignAta = await getAssociatedTokenAddress(
+        ctx.alignMintPda,
+        newUser.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      const repAta = await getAssociatedTokenAddress(
+        ctx.repMintPda,
+        newUser.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+      );
+
+      // Start half set up: the profile exists but none of the token accounts do
+      await ctx.program.methods
+        .createUserProfile()
+        .accounts({
+          user: newUser.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      // Running it twice shows it is idempotent
+      for (let i = 0; i < 2; i++) {
+        await ctx.program.methods
+          .onboardUser()
+          .accounts({
+            state: ctx.statePda,
+            payer: ctx.authorityKeypair.publicKey,
+            user: newUser.publicKey,
+            tempAlignMint: ctx.tempAlignMintPda,
+            alignMint: ctx.alignMintPda,
+            tempRepMint: ctx.tempRepMintPda,
+            repMint: ctx.repMintPda,
+            userAlignAta: alignAta,
+            userRepAta: repAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+      }
+
+      const profile = await ctx.program.account.userProfile.fetch(profilePda);
+      expect(profile.user.toString()).to.equal(newUser.publicKey.toString());
+      expect(profile.userTempAlignAccount.toString()).to.equal(
+        tempAlignPda.toString(),
+      );
+      expect(profile.userTempRepAccount.toString()).to.equal(
+        tempRepPda.toString(),
+      );
+      expect(profile.userAlignAta.toString()).to.equal(alignAta.toString());
+      expect(profile.userRepAta.toString()).to.equal(repAta.toString());
+
+      // Temp accounts are controlled by the state PDA, ATAs by the user
+      const tempAlignData = await getAccount(
+        ctx.provider.connection,
+        tempAlignPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(tempAlignData.mint.toString()).to.equal(
+        ctx.tempAlignMintPda.toString(),
+      );
+      expect(tempAlignData.owner.toString()).to.equal(ctx.statePda.toString());
+      const tempRepData = await getAccount(
+        ctx.provider.connection,
+        tempRepPda,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(tempRepData.owner.toString()).to.equal(ctx.statePda.toString());
+      const repData = await getAccount(
+        ctx.provider.connection,
+        repAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(repData.owner.toString()).to.equal(newUser.publicKey.toString());
+    });
   });
 }
@@ -1,7 +1,12 @@
 import { expect } from "chai";
 import * as anchor from "@coral-xyz/anchor";
 import { web3, BN } from "@coral-xyz/anchor";
-import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddress,
+} from "@solana/spl-token";
+import { ASSOCIATED_TOKEN_PROGRAM_ID } from "../utils/constants";
 import { TestContext } from "../utils/test-setup";
 
 export function runSubmissionTests(ctx: TestContext): void {
@@ -40,6 +45,7 @@ export function runSubmissionTests(ctx: TestContext): void {
         expect(balanceAcc.tempAlignAmount.toNumber()).to.equal(0);
         expect(balanceAcc.tempRepAmount.toNumber()).to.equal(0);
         expect(balanceAcc.lockedTempRepAmount.toNumber()).to.equal(0);
+        expect(balanceAcc.repEarned.toNumber()).to.equal(0);
       },
     );
 
@@ -85,9 +91,11 @@ export function runSubmissionTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           tempAlignMint: ctx.tempAlignMintPda,
           contributor: ctx.contributorKeypair.publicKey,
+          actingSigner: ctx.contributorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.contributorKeypair])
         .rpc();
 
       console.log("Submit data transaction signature:", tx);
@@ -114,6 +122,7 @@ export function runSubmissionTests(ctx: TestContext): void {
       expect(linkAcc.noVotingPower.toNumber()).to.equal(0);
       expect(linkAcc.totalCommittedVotes.toNumber()).to.equal(0);
       expect(linkAcc.totalRevealedVotes.toNumber()).to.equal(0);
+      expect(linkAcc.completedAiRequests.toNumber()).to.equal(0);
 
       const expectedCommitEnd =
         submissionAcc.timestamp.toNumber() +
@@ -132,6 +141,8 @@ export function runSubmissionTests(ctx: TestContext): void {
       const contributorTempAlignData = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
       expect(Number(contributorTempAlignData.amount)).to.equal(
@@ -163,6 +174,205 @@ export function runSubmissionTests(ctx: TestContext): void {
       );
       expect(balanceAccAfter.tempRepAmount.toNumber()).to.equal(0);
       expect(balanceAccAfter.lockedTempRepAmount.toNumber()).to.equal(0);
+      expect(balanceAccAfter.pendingSubmissionCount.toNumber()).to.equal(1);
+      expect(balanceAccAfter.windowSubmissionCount.toNumber()).to.equal(1);
+    });
+
+    it("Rejects a submission the contributor did not sign", async () => {
+      const profile = await ctx.program.account.userProfile.fetch(
+        ctx.contributorProfilePda,
+      );
+      try {
+        // The payer signs, but neither the contributor nor a session key of theirs does
+        await ctx.program.methods
+          .submitDataToTopic(ctx.SUBMISSION_DATA, profile.userSubmissionCount)
+          .accounts({
+            topic: ctx.topic1Pda,
+            tempAlignMint: ctx.tempAlignMintPda,
+            contributor: ctx.contributorKeypair.publicKey,
+            actingSigner: ctx.authorityKeypair.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail(
+          "Submitting without the contributor's signature should fail",
+        );
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "UnauthorizedActingSigner",
+        );
+      }
+    });
+
+    it("Enforces per-user submission rate limits", async () => {
+      const setRateLimits = (
+        maxPerWindow: anchor.BN | null,
+        maxPending: anchor.BN | null,
+      ) =>
+        ctx.program.methods
+          .updateTopicRateLimits(maxPerWindow, null, maxPending)
+          .accounts({
+            state: ctx.statePda,
+            topic: ctx.topic1Pda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+      const trySubmit = async () => {
+        const profile = await ctx.program.account.userProfile.fetch(
+          ctx.contributorProfilePda,
+        );
+        await ctx.program.methods
+          .submitDataToTopic(ctx.SUBMISSION_DATA, profile.userSubmissionCount)
+          .accounts({
+            topic: ctx.topic1Pda,
+            tempAlignMint: ctx.tempAlignMintPda,
+            contributor: ctx.contributorKeypair.publicKey,
+            actingSigner: ctx.contributorKeypair.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair, ctx.contributorKeypair])
+          .rpc();
+      };
+
+      // One submission per window: the contributor already used it
+      await setRateLimits(new anchor.BN(1), null);
+      let topicAcc = await ctx.program.account.topic.fetch(ctx.topic1Pda);
+      expect(topicAcc.maxSubmissionsPerWindow.toNumber()).to.equal(1);
+      try {
+        await trySubmit();
+        expect.fail("Submission over the window limit should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "SubmissionRateLimitExceeded",
+        );
+      }
+
+      // One pending submission: the first one is still unfinalized
+      await setRateLimits(null, new anchor.BN(1));
+      try {
+        await trySubmit();
+        expect.fail("Submission over the pending limit should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "TooManyPendingSubmissions",
+        );
+      }
+
+      // Remove the limits for the remaining sections
+      await setRateLimits(null, null);
+      topicAcc = await ctx.program.account.topic.fetch(ctx.topic1Pda);
+      expect(topicAcc.maxSubmissionsPerWindow).to.be.null;
+      expect(topicAcc.maxPendingSubmissions).to.be.null;
+    });
+
+    it("Creates a missing topic balance in the same transaction as the first submission", async () => {
+      // A fresh user, so the shared users' token balances checked by later sections stay put
+      const newUser = web3.Keypair.generate();
+      await ctx.program.methods
+        .onboardUser()
+        .accounts({
+          state: ctx.statePda,
+          payer: ctx.authorityKeypair.publicKey,
+          user: newUser.publicKey,
+          tempAlignMint: ctx.tempAlignMintPda,
+          alignMint: ctx.alignMintPda,
+          tempRepMint: ctx.tempRepMintPda,
+          repMint: ctx.repMintPda,
+          userAlignAta: await getAssociatedTokenAddress(
+            ctx.alignMintPda,
+            newUser.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+          ),
+          userRepAta: await getAssociatedTokenAddress(
+            ctx.repMintPda,
+            newUser.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+          ),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      const [topicPda] = web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("topic"),
+          stateAcc.topicCount.toArrayLike(Buffer, "le", 8),
+        ],
+        ctx.program.programId,
+      );
+      await ctx.program.methods
+        .createTopic(
+          "First Touch Topic",
+          "Topic joined on first submission",
+          null,
+          null,
+        )
+        .accounts({
+          creator: ctx.authorityKeypair.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+      const [balancePda] = web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("user_topic_balance"),
+          newUser.publicKey.toBuffer(),
+          topicPda.toBuffer(),
+        ],
+        ctx.program.programId,
+      );
+
+      const submit = (preInstructions: web3.TransactionInstruction[]) =>
+        ctx.program.methods
+          .submitDataToTopic(ctx.SUBMISSION_DATA, new BN(0))
+          .accounts({
+            topic: topicPda,
+            tempAlignMint: ctx.tempAlignMintPda,
+            contributor: newUser.publicKey,
+            actingSigner: newUser.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .preInstructions(preInstructions)
+          .signers([ctx.authorityKeypair, newUser])
+          .rpc();
+
+      // The program itself never creates the balance
+      try {
+        await submit([]);
+        expect.fail("Submitting without a topic balance should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AccountNotInitialized");
+      }
+
+      // What the CLI and relayer prepend when the balance is missing
+      const initBalanceIx = await ctx.program.methods
+        .initializeUserTopicBalance()
+        .accounts({
+          user: newUser.publicKey,
+          topic: topicPda,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .instruction();
+      await submit([initBalanceIx]);
+
+      const balance =
+        await ctx.program.account.userTopicBalance.fetch(balancePda);
+      expect(balance.user.toString()).to.equal(newUser.publicKey.toString());
+      expect(balance.topic.toString()).to.equal(topicPda.toString());
+      expect(balance.tempAlignAmount.toNumber()).to.equal(
+        stateAcc.tokensToMint.toNumber(),
+      );
+      expect(balance.pendingSubmissionCount.toNumber()).to.equal(1);
     });
   });
 }
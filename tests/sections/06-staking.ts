@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { web3, BN } from "@coral-xyz/anchor";
-import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
 import { TestContext } from "../utils/test-setup";
 
 export function runStakingTests(ctx: TestContext): void {
@@ -43,6 +43,28 @@ export function runStakingTests(ctx: TestContext): void {
       },
     );
 
+    it("Rejects a stake the user did not sign", async () => {
+      try {
+        // The payer signs, but the contributor does not
+        await ctx.program.methods
+          .stakeTopicSpecificTokens(new BN(1))
+          .accounts({
+            topic: ctx.topic1Pda,
+            user: ctx.contributorKeypair.publicKey,
+            actingSigner: ctx.authorityKeypair.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Staking without the user's signature should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "UnauthorizedActingSigner",
+        );
+      }
+    });
+
     it("Stakes tempAlign tokens for tempRep tokens for contributor", async () => {
       // Fetch initial balances from the UserTopicBalance account
       const balanceBefore = await ctx.program.account.userTopicBalance.fetch(
@@ -55,6 +77,8 @@ export function runStakingTests(ctx: TestContext): void {
       const globalTempAlignBefore = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const initialGlobalAmount = Number(globalTempAlignBefore.amount);
 
@@ -82,9 +106,11 @@ export function runStakingTests(ctx: TestContext): void {
         .accounts({
           topic: ctx.topic1Pda,
           user: ctx.contributorKeypair.publicKey,
+          actingSigner: ctx.contributorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.contributorKeypair])
         .rpc();
 
       console.log("Contributor stake tokens transaction signature:", tx);
@@ -93,6 +119,8 @@ export function runStakingTests(ctx: TestContext): void {
       const globalTempAlignAfter = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const finalGlobalAmount = Number(globalTempAlignAfter.amount);
       console.log(` -> Global TempAlign ATA after: ${finalGlobalAmount}`);
@@ -104,6 +132,8 @@ export function runStakingTests(ctx: TestContext): void {
       const globalTempRepAfter = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       console.log(
         ` -> Global TempRep ATA after: ${Number(globalTempRepAfter.amount)}`,
@@ -180,9 +210,11 @@ export function runStakingTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           tempAlignMint: ctx.tempAlignMintPda,
           contributor: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
 
       console.log(
@@ -194,6 +226,8 @@ export function runStakingTests(ctx: TestContext): void {
       const validatorGlobalTempAlignData = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const tokensToMint = (
         await ctx.program.account.state.fetch(ctx.statePda)
@@ -242,9 +276,11 @@ export function runStakingTests(ctx: TestContext): void {
         .accounts({
           topic: ctx.topic1Pda,
           user: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
 
       console.log("Validator stake transaction signature:", validatorStakeTx);
@@ -253,6 +289,8 @@ export function runStakingTests(ctx: TestContext): void {
       const validatorGlobalTempAlignAfterStake = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const validatorFinalGlobalAmount = Number(
         validatorGlobalTempAlignAfterStake.amount,
@@ -268,6 +306,8 @@ export function runStakingTests(ctx: TestContext): void {
       const validatorGlobalTempRepAfterStake = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       console.log(
         ` -> Global TempRep ATA after: ${Number(validatorGlobalTempRepAfterStake.amount)}`,
@@ -115,15 +115,16 @@ export function runVotingTests(ctx: TestContext): void {
 
       // Commit the vote - *** ADDED userTopicBalance and validatorRepAta ***
       const tx = await ctx.program.methods
-        .commitVote(voteHash, tempRepAmount, permRepAmount)
+        .commitVote(voteHash, tempRepAmount, permRepAmount, [])
         .accounts({
           topic: ctx.topic1Pda,
           submission: ctx.submissionPda,
           validatorRepAta: ctx.validatorRepAta, // ADDED
           validator: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
 
       console.log("Vote commit transaction signature:", tx);
@@ -258,9 +259,10 @@ export function runVotingTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           submission: ctx.submissionPda,
           validator: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
 
       console.log("Vote reveal transaction signature:", tx);
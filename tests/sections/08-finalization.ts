@@ -1,11 +1,78 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { web3, BN } from "@coral-xyz/anchor";
-import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddress,
+} from "@solana/spl-token";
+import { ASSOCIATED_TOKEN_PROGRAM_ID } from "../utils/constants";
 import { TestContext } from "../utils/test-setup";
+import * as crypto from "crypto";
 
 export function runFinalizationTests(ctx: TestContext): void {
   describe("Finalization", () => {
+    const FINALIZATION_REWARD = 5_000; // lamports
+
+    it("Creates and funds the fee vault for finalization rewards", async () => {
+      const [feeVaultPda] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault")],
+        ctx.program.programId,
+      );
+      await ctx.program.methods
+        .initializeFeeVault()
+        .accounts({
+          state: ctx.statePda,
+          feeVault: feeVaultPda,
+          authority: ctx.authorityKeypair.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      try {
+        await ctx.program.methods
+          .fundFeeVault(new BN(0))
+          .accounts({
+            feeVault: feeVaultPda,
+            funder: ctx.authorityKeypair.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Zero deposit should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("ZeroFeeVaultDeposit");
+      }
+
+      const vaultBefore = await ctx.provider.connection.getBalance(feeVaultPda);
+      await ctx.program.methods
+        .fundFeeVault(new BN(web3.LAMPORTS_PER_SOL / 10))
+        .accounts({
+          feeVault: feeVaultPda,
+          funder: ctx.authorityKeypair.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+      const vaultAfter = await ctx.provider.connection.getBalance(feeVaultPda);
+      expect(vaultAfter - vaultBefore).to.equal(web3.LAMPORTS_PER_SOL / 10);
+
+      await ctx.program.methods
+        .updateFinalizationReward(new BN(FINALIZATION_REWARD), new BN(0))
+        .accounts({
+          state: ctx.statePda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      expect(stateAcc.finalizationReward.toNumber()).to.equal(
+        FINALIZATION_REWARD,
+      );
+      expect(stateAcc.finalizationRewardMinVotingPower.toNumber()).to.equal(0);
+    });
+
     it("Finalizes the submission", async () => {
       // Fetch state before setting phases
       let linkAccBefore = await ctx.program.account.submissionTopicLink.fetch(
@@ -67,10 +134,14 @@ export function runFinalizationTests(ctx: TestContext): void {
       const globalTempAlignBefore = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const globalAlignBefore = await getAccount(
         ctx.provider.connection,
         ctx.contributorAlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       console.log("--- Before Finalize Submission ---");
@@ -84,6 +155,13 @@ export function runFinalizationTests(ctx: TestContext): void {
         `Contributor Global Align ATA: ${Number(globalAlignBefore.amount)}`,
       );
 
+      // Pass the fee vault so the caller collects the finalization reward
+      const [feeVaultPda] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault")],
+        ctx.program.programId,
+      );
+      const vaultBefore = await ctx.provider.connection.getBalance(feeVaultPda);
+
       // Finalize the submission - *** ADDED userTopicBalance ***
       const tx = await ctx.program.methods
         .finalizeSubmission()
@@ -99,7 +177,8 @@ export function runFinalizationTests(ctx: TestContext): void {
           tempAlignMint: ctx.tempAlignMintPda,
           alignMint: ctx.alignMintPda,
           authority: ctx.authorityKeypair.publicKey, // Payer/caller
-          tokenProgram: TOKEN_PROGRAM_ID,
+          feeVault: feeVaultPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([ctx.authorityKeypair])
@@ -107,6 +186,10 @@ export function runFinalizationTests(ctx: TestContext): void {
 
       console.log("Finalize submission transaction signature:", tx);
 
+      // The reward left the vault exactly once
+      const vaultAfter = await ctx.provider.connection.getBalance(feeVaultPda);
+      expect(vaultBefore - vaultAfter).to.equal(FINALIZATION_REWARD);
+
       // --- Verification ---
       // Verify the submission-topic link status changed
       const linkAccAfter = await ctx.program.account.submissionTopicLink.fetch(
@@ -123,10 +206,14 @@ export function runFinalizationTests(ctx: TestContext): void {
       const globalTempAlignAfter = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const globalAlignAfter = await getAccount(
         ctx.provider.connection,
         ctx.contributorAlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       console.log("--- After Finalize Submission ---");
@@ -171,10 +258,14 @@ export function runFinalizationTests(ctx: TestContext): void {
       const globalTempRepBefore = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const globalRepBefore = await getAccount(
         ctx.provider.connection,
         ctx.validatorRepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       console.log("--- Before Finalize Vote ---");
@@ -212,7 +303,7 @@ export function runFinalizationTests(ctx: TestContext): void {
           tempRepMint: ctx.tempRepMintPda,
           repMint: ctx.repMintPda,
           authority: ctx.authorityKeypair.publicKey, // Payer/caller
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([ctx.authorityKeypair])
@@ -233,10 +324,14 @@ export function runFinalizationTests(ctx: TestContext): void {
       const globalTempRepAfter = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const globalRepAfter = await getAccount(
         ctx.provider.connection,
         ctx.validatorRepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       console.log(
@@ -274,5 +369,472 @@ export function runFinalizationTests(ctx: TestContext): void {
       // REMOVED Check for validatorProfile.permanentRepAmount
       // REMOVED Check for validatorProfile.topicTokens
     });
+
+    it("Rejects batch-finalizing an already finalized vote", async () => {
+      try {
+        await ctx.program.methods
+          .finalizeVotesBatch()
+          .accounts({
+            state: ctx.statePda,
+            submissionTopicLink: ctx.submissionTopicLinkPda,
+            topic: ctx.topic1Pda,
+            tempRepMint: ctx.tempRepMintPda,
+            repMint: ctx.repMintPda,
+            authority: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts([
+            { pubkey: ctx.voteCommitPda, isSigner: false, isWritable: true },
+            {
+              pubkey: ctx.validatorTopic1BalancePda,
+              isSigner: false,
+              isWritable: true,
+            },
+            {
+              pubkey: ctx.validatorTempRepAccount,
+              isSigner: false,
+              isWritable: true,
+            },
+            { pubkey: ctx.validatorRepAta, isSigner: false, isWritable: true },
+          ])
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Batch finalization of a finalized vote should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("VoteAlreadyFinalized");
+      }
+    });
+
+    describe("Finalization reward guards", () => {
+      // Fresh users on a fresh topic, so the shared users' balances checked by later
+      // sections stay put
+      const contributor = web3.Keypair.generate();
+      const voter = web3.Keypair.generate();
+      let topicPda: web3.PublicKey;
+      let feeVaultPda: web3.PublicKey;
+
+      const findPda = (seeds: Buffer[]) =>
+        web3.PublicKey.findProgramAddressSync(seeds, ctx.program.programId)[0];
+      const ata = (mint: web3.PublicKey, user: web3.Keypair) =>
+        getAssociatedTokenAddress(
+          mint,
+          user.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID,
+        );
+      const balancePdaFor = (user: web3.Keypair) =>
+        findPda([
+          Buffer.from("user_topic_balance"),
+          user.publicKey.toBuffer(),
+          topicPda.toBuffer(),
+        ]);
+      const profilePdaFor = (user: web3.Keypair) =>
+        findPda([Buffer.from("user_profile"), user.publicKey.toBuffer()]);
+
+      const submit = async (user: web3.Keypair) => {
+        const profile = await ctx.program.account.userProfile.fetch(
+          profilePdaFor(user),
+        );
+        await ctx.program.methods
+          .submitDataToTopic(ctx.SUBMISSION_DATA, profile.userSubmissionCount)
+          .accounts({
+            topic: topicPda,
+            tempAlignMint: ctx.tempAlignMintPda,
+            contributor: user.publicKey,
+            actingSigner: user.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair, user])
+          .rpc();
+        const submissionPda = findPda([
+          Buffer.from("submission"),
+          user.publicKey.toBuffer(),
+          profile.userSubmissionCount.toArrayLike(Buffer, "le", 8),
+        ]);
+        const linkPda = findPda([
+          Buffer.from("submission_topic_link"),
+          submissionPda.toBuffer(),
+          topicPda.toBuffer(),
+        ]);
+        return { submissionPda, linkPda };
+      };
+
+      const endVoting = (
+        submissionPda: web3.PublicKey,
+        linkPda: web3.PublicKey,
+        revealOpen: boolean,
+      ) => {
+        const now = Math.floor(Date.now() / 1000);
+        return ctx.program.methods
+          .setVotingPhases(
+            new BN(now - 2400),
+            new BN(now - 1800),
+            new BN(now - 1800),
+            new BN(revealOpen ? now + 600 : now - 600),
+          )
+          .accounts({
+            state: ctx.statePda,
+            submissionTopicLink: linkPda,
+            topic: topicPda,
+            submission: submissionPda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+      };
+
+      const finalizeSubmission = async (
+        submissionPda: web3.PublicKey,
+        linkPda: web3.PublicKey,
+      ) =>
+        ctx.program.methods
+          .finalizeSubmission()
+          .accounts({
+            state: ctx.statePda,
+            submissionTopicLink: linkPda,
+            topic: topicPda,
+            submission: submissionPda,
+            contributorProfile: profilePdaFor(contributor),
+            userTopicBalance: balancePdaFor(contributor),
+            contributorTempAlignAccount: findPda([
+              Buffer.from("user_temp_align"),
+              contributor.publicKey.toBuffer(),
+            ]),
+            contributorAlignAta: await ata(ctx.alignMintPda, contributor),
+            tempAlignMint: ctx.tempAlignMintPda,
+            alignMint: ctx.alignMintPda,
+            authority: ctx.authorityKeypair.publicKey,
+            feeVault: feeVaultPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+      const setReward = (reward: number, minVotingPower = 0) =>
+        ctx.program.methods
+          .updateFinalizationReward(new BN(reward), new BN(minVotingPower))
+          .accounts({
+            state: ctx.statePda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+      // The voter commits and reveals a YES vote with 1 tempRep, then voting ends
+      const voteYes = async (
+        submissionPda: web3.PublicKey,
+        linkPda: web3.PublicKey,
+      ) => {
+        const voteHash = Array.from(
+          crypto
+            .createHash("sha256")
+            .update(
+              Buffer.concat([
+                voter.publicKey.toBuffer(),
+                linkPda.toBuffer(),
+                Buffer.from([1]),
+                Buffer.from(ctx.VOTE_NONCE),
+              ]),
+            )
+            .digest(),
+        );
+        await ctx.program.methods
+          .commitVote(voteHash, new BN(1), new BN(0), [])
+          .accounts({
+            topic: topicPda,
+            submission: submissionPda,
+            validatorRepAta: await ata(ctx.repMintPda, voter),
+            validator: voter.publicKey,
+            actingSigner: voter.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair, voter])
+          .rpc();
+        await endVoting(submissionPda, linkPda, true);
+        await ctx.program.methods
+          .revealVote(ctx.VOTE_CHOICE_YES, ctx.VOTE_NONCE)
+          .accounts({
+            topic: topicPda,
+            submission: submissionPda,
+            validator: voter.publicKey,
+            actingSigner: voter.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair, voter])
+          .rpc();
+        await endVoting(submissionPda, linkPda, false);
+        return findPda([
+          Buffer.from("vote_commit"),
+          linkPda.toBuffer(),
+          voter.publicKey.toBuffer(),
+        ]);
+      };
+
+      const finalizeVote = async (
+        submissionPda: web3.PublicKey,
+        linkPda: web3.PublicKey,
+        voteCommitPda: web3.PublicKey,
+      ) =>
+        ctx.program.methods
+          .finalizeVote()
+          .accounts({
+            state: ctx.statePda,
+            submissionTopicLink: linkPda,
+            topic: topicPda,
+            submission: submissionPda,
+            voteCommit: voteCommitPda,
+            validatorProfile: profilePdaFor(voter),
+            userTopicBalance: balancePdaFor(voter),
+            validatorTempRepAccount: findPda([
+              Buffer.from("user_temp_rep"),
+              voter.publicKey.toBuffer(),
+            ]),
+            validatorRepAta: await ata(ctx.repMintPda, voter),
+            tempRepMint: ctx.tempRepMintPda,
+            repMint: ctx.repMintPda,
+            authority: ctx.authorityKeypair.publicKey,
+            feeVault: feeVaultPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+      const setEmission = (emission: number) =>
+        ctx.program.methods
+          .updateValidatorRewardEmission(new BN(emission))
+          .accounts({
+            state: ctx.statePda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+      before("Onboard a contributor and a staked voter on a fresh topic", async () => {
+        feeVaultPda = findPda([Buffer.from("fee_vault")]);
+        const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+        topicPda = findPda([
+          Buffer.from("topic"),
+          stateAcc.topicCount.toArrayLike(Buffer, "le", 8),
+        ]);
+        await ctx.program.methods
+          .createTopic(
+            "Finalization Reward Topic",
+            "Topic for finalization reward checks",
+            null,
+            null,
+          )
+          .accounts({
+            creator: ctx.authorityKeypair.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+        for (const user of [contributor, voter]) {
+          await ctx.program.methods
+            .onboardUser()
+            .accounts({
+              state: ctx.statePda,
+              payer: ctx.authorityKeypair.publicKey,
+              user: user.publicKey,
+              tempAlignMint: ctx.tempAlignMintPda,
+              alignMint: ctx.alignMintPda,
+              tempRepMint: ctx.tempRepMintPda,
+              repMint: ctx.repMintPda,
+              userAlignAta: await ata(ctx.alignMintPda, user),
+              userRepAta: await ata(ctx.repMintPda, user),
+              tokenProgram: TOKEN_2022_PROGRAM_ID,
+              associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            })
+            .signers([ctx.authorityKeypair])
+            .rpc();
+          await ctx.program.methods
+            .initializeUserTopicBalance()
+            .accounts({
+              user: user.publicKey,
+              topic: topicPda,
+              payer: ctx.authorityKeypair.publicKey,
+            })
+            .signers([ctx.authorityKeypair])
+            .rpc();
+        }
+
+        // The voter earns tempAlign in the topic and stakes it for tempRep
+        await submit(voter);
+        await ctx.program.methods
+          .stakeTopicSpecificTokens(stateAcc.tokensToMint)
+          .accounts({
+            topic: topicPda,
+            user: voter.publicKey,
+            actingSigner: voter.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair, voter])
+          .rpc();
+      });
+
+      it("Pays no finalization reward for a link nobody revealed a vote on", async () => {
+        const { submissionPda, linkPda } = await submit(contributor);
+        await endVoting(submissionPda, linkPda, false);
+
+        const vaultBefore =
+          await ctx.provider.connection.getBalance(feeVaultPda);
+        await finalizeSubmission(submissionPda, linkPda);
+        const vaultAfter =
+          await ctx.provider.connection.getBalance(feeVaultPda);
+
+        const link =
+          await ctx.program.account.submissionTopicLink.fetch(linkPda);
+        expect(link.status.pending).to.be.undefined;
+        expect(link.totalRevealedVotes.toNumber()).to.equal(0);
+        expect(vaultAfter).to.equal(vaultBefore);
+      });
+
+      it("Adds no validator emission to a link nobody won a vote on", async () => {
+        const { submissionPda, linkPda } = await submit(contributor);
+        await endVoting(submissionPda, linkPda, false);
+
+        await setEmission(10);
+        try {
+          await finalizeSubmission(submissionPda, linkPda);
+        } finally {
+          await setEmission(0);
+        }
+
+        const link =
+          await ctx.program.account.submissionTopicLink.fetch(linkPda);
+        expect(link.noVotingPower.toNumber()).to.equal(0);
+        expect(link.rewardPool.toNumber()).to.equal(0);
+      });
+
+      it("Skips the reward rather than failing when the vault cannot cover it", async () => {
+        const { submissionPda, linkPda } = await submit(contributor);
+        const voteCommitPda = await voteYes(submissionPda, linkPda);
+
+        // A reward larger than everything in the vault
+        const vaultBefore =
+          await ctx.provider.connection.getBalance(feeVaultPda);
+        await setReward(vaultBefore + 1);
+        try {
+          await finalizeSubmission(submissionPda, linkPda);
+          await finalizeVote(submissionPda, linkPda, voteCommitPda);
+        } finally {
+          await setReward(FINALIZATION_REWARD);
+        }
+
+        const link =
+          await ctx.program.account.submissionTopicLink.fetch(linkPda);
+        expect(link.status.accepted).to.not.be.undefined;
+        const voteCommit =
+          await ctx.program.account.voteCommit.fetch(voteCommitPda);
+        expect(voteCommit.finalized).to.be.true;
+        expect(await ctx.provider.connection.getBalance(feeVaultPda)).to.equal(
+          vaultBefore,
+        );
+      });
+
+      it("Pays no finalization reward below the minimum revealed voting power", async () => {
+        const { submissionPda, linkPda } = await submit(contributor);
+        const voteCommitPda = await voteYes(submissionPda, linkPda);
+        const revealed =
+          await ctx.program.account.submissionTopicLink.fetch(linkPda);
+        const revealedPower =
+          revealed.yesVotingPower.toNumber() +
+          revealed.noVotingPower.toNumber();
+        expect(revealed.totalRevealedVotes.toNumber()).to.equal(1);
+        expect(revealedPower).to.be.greaterThan(0);
+
+        // One token reveal falls short of the minimum, so finalizing pays nothing
+        const vaultBefore =
+          await ctx.provider.connection.getBalance(feeVaultPda);
+        await setReward(FINALIZATION_REWARD, revealedPower + 1);
+        try {
+          await finalizeSubmission(submissionPda, linkPda);
+        } finally {
+          await setReward(FINALIZATION_REWARD);
+        }
+        const vaultAfterSubmission =
+          await ctx.provider.connection.getBalance(feeVaultPda);
+        expect(vaultAfterSubmission).to.equal(vaultBefore);
+
+        // Meeting the minimum exactly pays again
+        await setReward(FINALIZATION_REWARD, revealedPower);
+        try {
+          await finalizeVote(submissionPda, linkPda, voteCommitPda);
+        } finally {
+          await setReward(FINALIZATION_REWARD);
+        }
+        expect(
+          vaultAfterSubmission -
+            (await ctx.provider.connection.getBalance(feeVaultPda)),
+        ).to.equal(FINALIZATION_REWARD);
+
+        const link =
+          await ctx.program.account.submissionTopicLink.fetch(linkPda);
+        expect(link.status.accepted).to.not.be.undefined;
+      });
+
+      it("Converts only the tempAlign a budget-limited submission minted", async () => {
+        const setBudget = (budget: BN | null) =>
+          ctx.program.methods
+            .updateTopicEmissionBudget(budget, null)
+            .accounts({
+              state: ctx.statePda,
+              topic: topicPda,
+              authority: ctx.authorityKeypair.publicKey,
+            })
+            .signers([ctx.authorityKeypair])
+            .rpc();
+
+        // Leave 3 tempAlign of budget, below the topic's tokens_to_mint
+        const topicAcc = await ctx.program.account.topic.fetch(topicPda);
+        const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+        const budgetLeft = 3;
+        expect(stateAcc.tokensToMint.toNumber()).to.be.greaterThan(budgetLeft);
+        await setBudget(topicAcc.emissionEpochMinted.add(new BN(budgetLeft)));
+        const { submissionPda, linkPda } = await submit(contributor);
+        await setBudget(null);
+        const submission =
+          await ctx.program.account.submission.fetch(submissionPda);
+        expect(submission.tempAlignMinted.toNumber()).to.equal(budgetLeft);
+
+        // Earlier submissions left more tempAlign in the topic than this one minted
+        const balance = await ctx.program.account.userTopicBalance.fetch(
+          balancePdaFor(contributor),
+        );
+        expect(balance.tempAlignAmount.toNumber()).to.be.greaterThan(
+          budgetLeft,
+        );
+
+        await voteYes(submissionPda, linkPda);
+        const alignAta = await ata(ctx.alignMintPda, contributor);
+        const alignBefore = Number(
+          (
+            await getAccount(
+              ctx.provider.connection,
+              alignAta,
+              undefined,
+              TOKEN_2022_PROGRAM_ID,
+            )
+          ).amount,
+        );
+        await finalizeSubmission(submissionPda, linkPda);
+        const alignAfter = Number(
+          (
+            await getAccount(
+              ctx.provider.connection,
+              alignAta,
+              undefined,
+              TOKEN_2022_PROGRAM_ID,
+            )
+          ).amount,
+        );
+        expect(alignAfter - alignBefore).to.equal(budgetLeft);
+      });
+    });
   });
 }
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { web3, BN } from "@coral-xyz/anchor";
-import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
 import { TestContext } from "../utils/test-setup";
 import * as crypto from "crypto";
 
@@ -116,10 +116,14 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const contribGlobalTempAlign08 = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const contribGlobalAlign08 = await getAccount(
         ctx.provider.connection,
         ctx.contributorAlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       console.log(`Contributor Balances:`);
       console.log(
@@ -144,10 +148,14 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const validatorGlobalTempRep08 = await getAccount(
         ctx.provider.connection,
         ctx.validatorTempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const validatorGlobalRep08 = await getAccount(
         ctx.provider.connection,
         ctx.validatorRepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       console.log(`Validator Balances:`);
       console.log(
@@ -171,18 +179,26 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const user3GlobalTempAlign08 = await getAccount(
         ctx.provider.connection,
         ctx.user3TempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const user3GlobalTempRep08 = await getAccount(
         ctx.provider.connection,
         ctx.user3TempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const user3GlobalAlign08 = await getAccount(
         ctx.provider.connection,
         ctx.user3AlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const user3GlobalRep08 = await getAccount(
         ctx.provider.connection,
         ctx.user3RepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       console.log(`User3 Balances:`);
       console.log(
@@ -241,9 +257,11 @@ export function runTokenLockingTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           tempAlignMint: ctx.tempAlignMintPda,
           contributor: ctx.contributorKeypair.publicKey,
+          actingSigner: ctx.contributorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.contributorKeypair])
         .rpc();
       console.log(" -> Contributor test submission TX:", createSubmissionTx);
 
@@ -255,6 +273,8 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const contribGlobalTempAlignAfterTestSub = await getAccount(
         ctx.provider.connection,
         ctx.contributorTempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(Number(contribGlobalTempAlignAfterTestSub.amount)).to.equal(
         tokensToMint.toNumber(),
@@ -297,9 +317,11 @@ export function runTokenLockingTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           tempAlignMint: ctx.tempAlignMintPda,
           contributor: ctx.user3Keypair.publicKey,
+          actingSigner: ctx.user3Keypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.user3Keypair])
         .rpc();
       console.log(" -> User3 submission TX:", user3SubmitTx);
 
@@ -311,6 +333,8 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const user3GlobalTempAlignAfterTestSub = await getAccount(
         ctx.provider.connection,
         ctx.user3TempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(Number(user3GlobalTempAlignAfterTestSub.amount)).to.equal(
         tokensToMint.toNumber(),
@@ -327,9 +351,11 @@ export function runTokenLockingTests(ctx: TestContext): void {
         .accounts({
           topic: ctx.topic1Pda,
           user: ctx.user3Keypair.publicKey,
+          actingSigner: ctx.user3Keypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.user3Keypair])
         .rpc();
       console.log(" -> User3 staking TX:", user3StakeTx);
 
@@ -341,10 +367,14 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const user3GlobalTempAlignAfterStake = await getAccount(
         ctx.provider.connection,
         ctx.user3TempAlignAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const user3GlobalTempRepAfterStake = await getAccount(
         ctx.provider.connection,
         ctx.user3TempRepAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       expect(Number(user3GlobalTempAlignAfterStake.amount)).to.equal(
         tokensToMint.toNumber() - user3StakeAmount.toNumber(),
@@ -405,15 +435,16 @@ export function runTokenLockingTests(ctx: TestContext): void {
 
       // Commit the vote
       const tx = await ctx.program.methods
-        .commitVote(ctx.testVoteHash, tempRepAmount, permRepAmount)
+        .commitVote(ctx.testVoteHash, tempRepAmount, permRepAmount, [])
         .accounts({
           topic: ctx.topic1Pda,
           submission: ctx.testSubmissionPda,
           validatorRepAta: ctx.validatorRepAta, // Needed even if false
           validator: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
       console.log(" -> Validator commit TX:", tx);
 
@@ -468,15 +499,16 @@ export function runTokenLockingTests(ctx: TestContext): void {
 
       // Commit user3's vote
       const tx = await ctx.program.methods
-        .commitVote(ctx.user3VoteHash, tempRepAmount, permRepAmount)
+        .commitVote(ctx.user3VoteHash, tempRepAmount, permRepAmount, [])
         .accounts({
           topic: ctx.topic1Pda,
           submission: ctx.testSubmissionPda,
           validatorRepAta: ctx.user3RepAta, // Needed even if false
           validator: ctx.user3Keypair.publicKey,
+          actingSigner: ctx.user3Keypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.user3Keypair])
         .rpc();
       console.log(" -> User3 commit TX:", tx);
 
@@ -509,9 +541,10 @@ export function runTokenLockingTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           submission: ctx.testSubmissionPda,
           validator: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
       console.log(" -> Validator reveal TX:", revealTx1);
 
@@ -523,9 +556,10 @@ export function runTokenLockingTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           submission: ctx.testSubmissionPda,
           validator: ctx.user3Keypair.publicKey,
+          actingSigner: ctx.user3Keypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.user3Keypair])
         .rpc();
       console.log(" -> User3 reveal TX:", revealTx2);
 
@@ -543,6 +577,9 @@ export function runTokenLockingTests(ctx: TestContext): void {
         expectedPowerValidator + expectedPowerUser3,
       ); // 3 + 6 = 9
       expect(linkAcc.noVotingPower.toNumber()).to.equal(0);
+      // Revealed tempRep per side feeds the validator reward pool at finalization
+      expect(linkAcc.yesTempRep.toNumber()).to.equal(10 + 36);
+      expect(linkAcc.noTempRep.toNumber()).to.equal(0);
       expect(linkAcc.totalCommittedVotes.toNumber()).to.equal(2); // Both committed
       expect(linkAcc.totalRevealedVotes.toNumber()).to.equal(2); // Both revealed
 
@@ -598,14 +635,20 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const contribGlobalAlignBefore = await getAccount(
         ctx.provider.connection,
         ctx.contributorAlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const validatorGlobalRepBefore = await getAccount(
         ctx.provider.connection,
         ctx.validatorRepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const user3GlobalRepBefore = await getAccount(
         ctx.provider.connection,
         ctx.user3RepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
 
       console.log("--- Before Finalization ---");
@@ -631,7 +674,7 @@ export function runTokenLockingTests(ctx: TestContext): void {
           tempAlignMint: ctx.tempAlignMintPda,
           alignMint: ctx.alignMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([ctx.authorityKeypair])
@@ -646,6 +689,8 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const contribGlobalAlignAfter = await getAccount(
         ctx.provider.connection,
         ctx.contributorAlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       const tokensMinted = (
         await ctx.program.account.state.fetch(ctx.statePda)
@@ -655,6 +700,14 @@ export function runTokenLockingTests(ctx: TestContext): void {
         Number(contribGlobalAlignBefore.amount) + tokensMinted,
       ); // Received 100 Align
 
+      // Nobody voted No and no emission is configured, so the reward pool is empty
+      const linkAfterFinalize =
+        await ctx.program.account.submissionTopicLink.fetch(
+          ctx.testSubmissionTopicLinkPda,
+        );
+      expect(linkAfterFinalize.rewardPool.toNumber()).to.equal(0);
+      expect(linkAfterFinalize.rewardPoolClaimed.toNumber()).to.equal(0);
+
       // Finalize validator's vote
       console.log(
         `Finalizing validator's vote on test submission (VoteCommit: ${ctx.testVoteCommitPda.toBase58()})`,
@@ -674,7 +727,7 @@ export function runTokenLockingTests(ctx: TestContext): void {
           tempRepMint: ctx.tempRepMintPda,
           repMint: ctx.repMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([ctx.authorityKeypair])
@@ -700,7 +753,7 @@ export function runTokenLockingTests(ctx: TestContext): void {
           tempRepMint: ctx.tempRepMintPda,
           repMint: ctx.repMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([ctx.authorityKeypair])
@@ -716,6 +769,8 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const validatorGlobalRepAfter = await getAccount(
         ctx.provider.connection,
         ctx.validatorRepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       console.log(
         "Validator UserTopicBalance after finalization:",
@@ -742,6 +797,8 @@ export function runTokenLockingTests(ctx: TestContext): void {
       const user3GlobalRepAfter = await getAccount(
         ctx.provider.connection,
         ctx.user3RepAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
       );
       console.log(
         "User3 UserTopicBalance after finalization:",
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { web3, BN } from "@coral-xyz/anchor";
-import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
 import { TestContext } from "../utils/test-setup";
 import * as crypto from "crypto";
 
@@ -139,9 +139,11 @@ export function runValidationTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           tempAlignMint: ctx.tempAlignMintPda,
           contributor: user.publicKey,
+          actingSigner: user.publicKey,
           payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, user])
         .rpc();
       console.log(" -> Created validation submission TX:", tx);
 
@@ -192,15 +194,16 @@ export function runValidationTests(ctx: TestContext): void {
       console.log("Attempting self-vote (should fail)...");
       try {
         await ctx.program.methods
-          .commitVote(selfVoteHash, new BN(1), new BN(0))
+          .commitVote(selfVoteHash, new BN(1), new BN(0), [])
           .accounts({
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda, // Submission created by contributor
             validatorRepAta: userRepAta, // Contributor's rep ATA
             validator: user.publicKey, // Contributor is the signer
+            actingSigner: user.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, user])
           .rpc();
         expect.fail("Self-voting should have been rejected");
       } catch (error) {
@@ -233,15 +236,16 @@ export function runValidationTests(ctx: TestContext): void {
       console.log("Attempting vote with 0 tokens (should fail)...");
       try {
         await ctx.program.methods
-          .commitVote(zeroVoteHash, new BN(0), new BN(0))
+          .commitVote(zeroVoteHash, new BN(0), new BN(0), [])
           .accounts({
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda,
             validatorRepAta: ctx.validatorRepAta,
             validator: ctx.validatorKeypair.publicKey,
+            actingSigner: ctx.validatorKeypair.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, ctx.validatorKeypair])
           .rpc();
         expect.fail("Voting with 0 tokens should have been rejected");
       } catch (error) {
@@ -281,15 +285,16 @@ export function runValidationTests(ctx: TestContext): void {
 
       try {
         await ctx.program.methods
-          .commitVote(insufficientVoteHash, new BN(excessAmount), new BN(0))
+          .commitVote(insufficientVoteHash, new BN(excessAmount), new BN(0), [])
           .accounts({
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda,
             validatorRepAta: ctx.validatorRepAta,
             validator: ctx.validatorKeypair.publicKey,
+            actingSigner: ctx.validatorKeypair.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, ctx.validatorKeypair])
           .rpc();
         expect.fail("Voting with insufficient tokens should be rejected");
       } catch (error) {
@@ -331,15 +336,16 @@ export function runValidationTests(ctx: TestContext): void {
       );
       try {
         await ctx.program.methods
-          .commitVote(wrongPhaseVoteHash, new BN(1), new BN(0))
+          .commitVote(wrongPhaseVoteHash, new BN(1), new BN(0), [])
           .accounts({
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda,
             validatorRepAta: ctx.validatorRepAta,
             validator: ctx.validatorKeypair.publicKey,
+            actingSigner: ctx.validatorKeypair.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, ctx.validatorKeypair])
           .rpc();
         expect.fail("Committing vote during reveal phase should be rejected");
       } catch (error) {
@@ -395,15 +401,16 @@ export function runValidationTests(ctx: TestContext): void {
 
       console.log("Committing a valid vote first...");
       await ctx.program.methods
-        .commitVote(ctx.validationVoteHash, new BN(5), new BN(0))
+        .commitVote(ctx.validationVoteHash, new BN(5), new BN(0), [])
         .accounts({
           topic: ctx.topic1Pda,
           submission: ctx.validationSubmissionPda,
           validatorRepAta: ctx.validatorRepAta,
           validator: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
       console.log(" -> Committed vote successfully.");
 
@@ -418,9 +425,10 @@ export function runValidationTests(ctx: TestContext): void {
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda,
             validator: ctx.validatorKeypair.publicKey,
+            actingSigner: ctx.validatorKeypair.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, ctx.validatorKeypair])
           .rpc();
         expect.fail("Revealing vote during commit phase should be rejected");
       } catch (error) {
@@ -450,9 +458,10 @@ export function runValidationTests(ctx: TestContext): void {
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda,
             validator: ctx.validatorKeypair.publicKey,
+            actingSigner: ctx.validatorKeypair.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, ctx.validatorKeypair])
           .rpc();
         expect.fail("Revealing with incorrect nonce should be rejected");
       } catch (error) {
@@ -469,9 +478,10 @@ export function runValidationTests(ctx: TestContext): void {
           topic: ctx.topic1Pda,
           submission: ctx.validationSubmissionPda,
           validator: ctx.validatorKeypair.publicKey,
+          actingSigner: ctx.validatorKeypair.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, ctx.validatorKeypair])
         .rpc();
       console.log(" -> Revealed vote successfully.");
       const voteCommit = await ctx.program.account.voteCommit.fetch(
@@ -502,9 +512,10 @@ export function runValidationTests(ctx: TestContext): void {
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda,
             validator: ctx.validatorKeypair.publicKey,
+            actingSigner: ctx.validatorKeypair.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, ctx.validatorKeypair])
           .rpc();
         expect.fail("Revealing an already revealed vote should be rejected");
       } catch (error) {
@@ -567,15 +578,16 @@ export function runValidationTests(ctx: TestContext): void {
         `Committing vote as User3 (${voter.publicKey.toBase58()}) for 'reveal too late' test...`,
       );
       await ctx.program.methods
-        .commitVote(voteHash, voteAmount, new BN(0))
+        .commitVote(voteHash, voteAmount, new BN(0), [])
         .accounts({
           topic: ctx.topic1Pda,
           submission: ctx.validationSubmissionPda,
           validatorRepAta: voterRepAta, // Pass User3's Rep ATA
           validator: voter.publicKey, // User3 is the signer/validator here
+          actingSigner: voter.publicKey,
           payer: ctx.authorityKeypair.publicKey,
         })
-        .signers([ctx.authorityKeypair])
+        .signers([ctx.authorityKeypair, voter])
         .rpc();
       console.log(
         " -> Committed User3 vote successfully:",
@@ -604,10 +616,11 @@ export function runValidationTests(ctx: TestContext): void {
           .accounts({
             topic: ctx.topic1Pda,
             submission: ctx.validationSubmissionPda,
-            validator: voter.publicKey, // User3 is voter (not signer)
+            validator: voter.publicKey,
+            actingSigner: voter.publicKey,
             payer: ctx.authorityKeypair.publicKey,
           })
-          .signers([ctx.authorityKeypair])
+          .signers([ctx.authorityKeypair, voter])
           .rpc();
         expect.fail(
           "Revealing vote after reveal phase ended should be rejected",
@@ -651,7 +664,7 @@ export function runValidationTests(ctx: TestContext): void {
             tempRepMint: ctx.tempRepMintPda,
             repMint: ctx.repMintPda,
             authority: ctx.authorityKeypair.publicKey,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
           })
           .signers([ctx.authorityKeypair])
@@ -695,7 +708,7 @@ export function runValidationTests(ctx: TestContext): void {
             tempAlignMint: ctx.tempAlignMintPda,
             alignMint: ctx.alignMintPda,
             authority: ctx.authorityKeypair.publicKey,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
           })
           .signers([ctx.authorityKeypair])
@@ -734,7 +747,7 @@ export function runValidationTests(ctx: TestContext): void {
           tempAlignMint: ctx.tempAlignMintPda,
           alignMint: ctx.alignMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([ctx.authorityKeypair])
@@ -761,7 +774,7 @@ export function runValidationTests(ctx: TestContext): void {
             tempAlignMint: ctx.tempAlignMintPda,
             alignMint: ctx.alignMintPda,
             authority: ctx.authorityKeypair.publicKey,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
           })
           .signers([ctx.authorityKeypair])
@@ -793,7 +806,7 @@ export function runValidationTests(ctx: TestContext): void {
           tempRepMint: ctx.tempRepMintPda,
           repMint: ctx.repMintPda,
           authority: ctx.authorityKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([ctx.authorityKeypair])
@@ -821,7 +834,7 @@ export function runValidationTests(ctx: TestContext): void {
             tempRepMint: ctx.tempRepMintPda,
             repMint: ctx.repMintPda,
             authority: ctx.authorityKeypair.publicKey,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
           })
           .signers([ctx.authorityKeypair])
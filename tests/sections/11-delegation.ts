@@ -0,0 +1,802 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { web3, BN } from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { TestContext } from "../utils/test-setup";
+import * as crypto from "crypto";
+
+export function runDelegationTests(ctx: TestContext): void {
+  describe("Delegation", () => {
+    let user3DelegationPda: web3.PublicKey;
+
+    before("Derive delegation PDA", () => {
+      [user3DelegationPda] = web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("delegation"),
+          ctx.user3Keypair.publicKey.toBuffer(),
+          ctx.topic1Pda.toBuffer(),
+        ],
+        ctx.program.programId,
+      );
+    });
+
+    it("Rejects delegating tempRep to yourself", async () => {
+      try {
+        await ctx.program.methods
+          .createDelegation(new anchor.BN(1))
+          .accounts({
+            topic: ctx.topic1Pda,
+            userTopicBalance: ctx.user3Topic1BalancePda,
+            delegation: user3DelegationPda,
+            validatorProfile: ctx.user3ProfilePda,
+            validator: ctx.user3Keypair.publicKey,
+            delegator: ctx.user3Keypair.publicKey,
+            payer: ctx.user3Keypair.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+          })
+          .signers([ctx.user3Keypair])
+          .rpc();
+        expect.fail("Self-delegation should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "SelfDelegationNotAllowed",
+        );
+      }
+    });
+
+    it("Rejects delegating more tempRep than is available", async () => {
+      const balance = await ctx.program.account.userTopicBalance.fetch(
+        ctx.user3Topic1BalancePda,
+      );
+
+      try {
+        await ctx.program.methods
+          .createDelegation(balance.tempRepAmount.addn(1))
+          .accounts({
+            topic: ctx.topic1Pda,
+            userTopicBalance: ctx.user3Topic1BalancePda,
+            delegation: user3DelegationPda,
+            validatorProfile: ctx.validatorProfilePda,
+            validator: ctx.validatorKeypair.publicKey,
+            delegator: ctx.user3Keypair.publicKey,
+            payer: ctx.user3Keypair.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+          })
+          .signers([ctx.user3Keypair])
+          .rpc();
+        expect.fail("Over-delegation should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "InsufficientTempRepBalance",
+        );
+      }
+    });
+
+    it("Delegates tempRep to a validator and withdraws it again", async () => {
+      const delegateAmount = 5;
+      const balanceBefore = await ctx.program.account.userTopicBalance.fetch(
+        ctx.user3Topic1BalancePda,
+      );
+      expect(balanceBefore.tempRepAmount.toNumber()).to.be.at.least(
+        delegateAmount,
+      );
+
+      await ctx.program.methods
+        .createDelegation(new anchor.BN(delegateAmount))
+        .accounts({
+          topic: ctx.topic1Pda,
+          userTopicBalance: ctx.user3Topic1BalancePda,
+          delegation: user3DelegationPda,
+          validatorProfile: ctx.validatorProfilePda,
+          validator: ctx.validatorKeypair.publicKey,
+          delegator: ctx.user3Keypair.publicKey,
+          payer: ctx.user3Keypair.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+          rent: web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([ctx.user3Keypair])
+        .rpc();
+
+      let delegation =
+        await ctx.program.account.delegation.fetch(user3DelegationPda);
+      expect(delegation.delegator.toString()).to.equal(
+        ctx.user3Keypair.publicKey.toString(),
+      );
+      expect(delegation.validator.toString()).to.equal(
+        ctx.validatorKeypair.publicKey.toString(),
+      );
+      expect(delegation.topic.toString()).to.equal(ctx.topic1Pda.toString());
+      expect(delegation.amount.toNumber()).to.equal(delegateAmount);
+      expect(delegation.lockedAmount.toNumber()).to.equal(0);
+
+      let balance = await ctx.program.account.userTopicBalance.fetch(
+        ctx.user3Topic1BalancePda,
+      );
+      expect(balance.tempRepAmount.toNumber()).to.equal(
+        balanceBefore.tempRepAmount.toNumber() - delegateAmount,
+      );
+
+      // Cannot withdraw more than is delegated
+      try {
+        await ctx.program.methods
+          .undelegateTempRep(new anchor.BN(delegateAmount + 1))
+          .accounts({
+            topic: ctx.topic1Pda,
+            userTopicBalance: ctx.user3Topic1BalancePda,
+            delegation: user3DelegationPda,
+            delegator: ctx.user3Keypair.publicKey,
+          })
+          .signers([ctx.user3Keypair])
+          .rpc();
+        expect.fail("Over-withdrawal should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal(
+          "InsufficientDelegatedBalance",
+        );
+      }
+
+      await ctx.program.methods
+        .undelegateTempRep(new anchor.BN(delegateAmount))
+        .accounts({
+          topic: ctx.topic1Pda,
+          userTopicBalance: ctx.user3Topic1BalancePda,
+          delegation: user3DelegationPda,
+          delegator: ctx.user3Keypair.publicKey,
+        })
+        .signers([ctx.user3Keypair])
+        .rpc();
+
+      delegation =
+        await ctx.program.account.delegation.fetch(user3DelegationPda);
+      expect(delegation.amount.toNumber()).to.equal(0);
+
+      balance = await ctx.program.account.userTopicBalance.fetch(
+        ctx.user3Topic1BalancePda,
+      );
+      expect(balance.tempRepAmount.toNumber()).to.equal(
+        balanceBefore.tempRepAmount.toNumber(),
+      );
+    });
+
+    it("Rejects delegation commission updates above 100%", async () => {
+      try {
+        await ctx.program.methods
+          .updateDelegationCommission(10_001)
+          .accounts({
+            state: ctx.statePda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Commission above 10000 bps should have failed");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidCommission");
+      }
+
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      expect(stateAcc.delegationCommissionBps).to.equal(0);
+    });
+  });
+
+  describe("Delegated Vote Settlement", () => {
+    const delegator = () => ctx.user3Keypair;
+    const validator = () => ctx.validatorKeypair;
+    const contributor = () => ctx.contributorKeypair;
+    let topicPda: web3.PublicKey;
+    let delegationPda: web3.PublicKey;
+    let delegatorBalancePda: web3.PublicKey;
+    let validatorBalancePda: web3.PublicKey;
+    let contributorBalancePda: web3.PublicKey;
+
+    const findPda = (seeds: Buffer[]) =>
+      web3.PublicKey.findProgramAddressSync(seeds, ctx.program.programId)[0];
+
+    const balancePdaFor = (user: web3.PublicKey) =>
+      findPda([
+        Buffer.from("user_topic_balance"),
+        user.toBuffer(),
+        topicPda.toBuffer(),
+      ]);
+
+    const tokenAmount = async (account: web3.PublicKey) =>
+      Number(
+        (
+          await getAccount(
+            ctx.provider.connection,
+            account,
+            undefined,
+            TOKEN_2022_PROGRAM_ID,
+          )
+        ).amount,
+      );
+
+    const voteHashFor = (
+      voter: web3.PublicKey,
+      linkPda: web3.PublicKey,
+      yes: boolean,
+    ) =>
+      Array.from(
+        crypto
+          .createHash("sha256")
+          .update(
+            Buffer.concat([
+              voter.toBuffer(),
+              linkPda.toBuffer(),
+              Buffer.from([yes ? 1 : 0]),
+              Buffer.from(ctx.VOTE_NONCE),
+            ]),
+          )
+          .digest(),
+      );
+
+    const submitAsContributor = async (data: string) => {
+      const profile = await ctx.program.account.userProfile.fetch(
+        ctx.contributorProfilePda,
+      );
+      const submissionPda = findPda([
+        Buffer.from("submission"),
+        contributor().publicKey.toBuffer(),
+        profile.userSubmissionCount.toArrayLike(Buffer, "le", 8),
+      ]);
+      await ctx.program.methods
+        .submitDataToTopic(data, profile.userSubmissionCount)
+        .accounts({
+          topic: topicPda,
+          tempAlignMint: ctx.tempAlignMintPda,
+          contributor: contributor().publicKey,
+          actingSigner: contributor().publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair, contributor()])
+        .rpc();
+      const linkPda = findPda([
+        Buffer.from("submission_topic_link"),
+        submissionPda.toBuffer(),
+        topicPda.toBuffer(),
+      ]);
+      return { submissionPda, linkPda };
+    };
+
+    const commitVote = (
+      voter: web3.Keypair,
+      voterRepAta: web3.PublicKey,
+      submissionPda: web3.PublicKey,
+      linkPda: web3.PublicKey,
+      yes: boolean,
+      tempRepAmount: number,
+      delegations: web3.PublicKey[],
+      delegationAmounts: number[] = [],
+    ) =>
+      ctx.program.methods
+        .commitVote(
+          voteHashFor(voter.publicKey, linkPda, yes),
+          new BN(tempRepAmount),
+          new BN(0),
+          delegationAmounts.map((amount) => new BN(amount)),
+        )
+        .accounts({
+          topic: topicPda,
+          submission: submissionPda,
+          validatorRepAta: voterRepAta,
+          validator: voter.publicKey,
+          actingSigner: voter.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .remainingAccounts(
+          delegations.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          })),
+        )
+        .signers([ctx.authorityKeypair, voter])
+        .rpc();
+
+    const revealVote = (
+      voter: web3.Keypair,
+      submissionPda: web3.PublicKey,
+      yes: boolean,
+    ) =>
+      ctx.program.methods
+        .revealVote(
+          yes ? ctx.VOTE_CHOICE_YES : ctx.VOTE_CHOICE_NO,
+          ctx.VOTE_NONCE,
+        )
+        .accounts({
+          topic: topicPda,
+          submission: submissionPda,
+          validator: voter.publicKey,
+          actingSigner: voter.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair, voter])
+        .rpc();
+
+    // Move the link's voting window so that `phase` is the current one
+    const setPhase = (
+      submissionPda: web3.PublicKey,
+      linkPda: web3.PublicKey,
+      phase: "reveal" | "ended",
+    ) => {
+      const now = Math.floor(Date.now() / 1000);
+      const revealEnd = phase === "reveal" ? now + 600 : now - 600;
+      return ctx.program.methods
+        .setVotingPhases(
+          new BN(now - 2400),
+          new BN(now - 1800),
+          new BN(now - 1800),
+          new BN(revealEnd),
+        )
+        .accounts({
+          state: ctx.statePda,
+          submissionTopicLink: linkPda,
+          topic: topicPda,
+          submission: submissionPda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+    };
+
+    const finalizeSubmission = (
+      submissionPda: web3.PublicKey,
+      linkPda: web3.PublicKey,
+    ) =>
+      ctx.program.methods
+        .finalizeSubmission()
+        .accounts({
+          state: ctx.statePda,
+          submissionTopicLink: linkPda,
+          topic: topicPda,
+          submission: submissionPda,
+          contributorProfile: ctx.contributorProfilePda,
+          userTopicBalance: contributorBalancePda,
+          contributorTempAlignAccount: ctx.contributorTempAlignAccount,
+          contributorAlignAta: ctx.contributorAlignAta,
+          tempAlignMint: ctx.tempAlignMintPda,
+          alignMint: ctx.alignMintPda,
+          authority: ctx.authorityKeypair.publicKey,
+          feeVault: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+    // Finalize the validator's vote, passing the delegation settlement group
+    const finalizeValidatorVote = (
+      submissionPda: web3.PublicKey,
+      linkPda: web3.PublicKey,
+    ) =>
+      ctx.program.methods
+        .finalizeVote()
+        .accounts({
+          state: ctx.statePda,
+          submissionTopicLink: linkPda,
+          topic: topicPda,
+          submission: submissionPda,
+          voteCommit: findPda([
+            Buffer.from("vote_commit"),
+            linkPda.toBuffer(),
+            validator().publicKey.toBuffer(),
+          ]),
+          validatorProfile: ctx.validatorProfilePda,
+          userTopicBalance: validatorBalancePda,
+          validatorTempRepAccount: ctx.validatorTempRepAccount,
+          validatorRepAta: ctx.validatorRepAta,
+          tempRepMint: ctx.tempRepMintPda,
+          repMint: ctx.repMintPda,
+          authority: ctx.authorityKeypair.publicKey,
+          feeVault: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .remainingAccounts(
+          [
+            delegationPda,
+            ctx.user3TempRepAccount,
+            ctx.user3RepAta,
+            delegatorBalancePda,
+          ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+        )
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+    const setEmission = (emission: number) =>
+      ctx.program.methods
+        .updateValidatorRewardEmission(new BN(emission))
+        .accounts({
+          state: ctx.statePda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+    const modifyDelegation = (
+      method: "delegateTempRep" | "undelegateTempRep",
+      amount: number,
+    ) =>
+      ctx.program.methods[method](new BN(amount))
+        .accounts({
+          topic: topicPda,
+          userTopicBalance: delegatorBalancePda,
+          delegation: delegationPda,
+          delegator: delegator().publicKey,
+        })
+        .signers([delegator()])
+        .rpc();
+
+    const updateCommission = (bps: number) =>
+      ctx.program.methods
+        .updateDelegationCommission(bps)
+        .accounts({
+          state: ctx.statePda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+    before("Fund a delegator on a fresh topic", async () => {
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      topicPda = findPda([
+        Buffer.from("topic"),
+        stateAcc.topicCount.toArrayLike(Buffer, "le", 8),
+      ]);
+      await ctx.program.methods
+        .createTopic(
+          "Delegation Topic",
+          "Topic for delegated vote settlement",
+          null,
+          null,
+        )
+        .accounts({
+          creator: ctx.authorityKeypair.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      for (const user of [delegator(), validator(), contributor()]) {
+        await ctx.program.methods
+          .initializeUserTopicBalance()
+          .accounts({
+            user: user.publicKey,
+            topic: topicPda,
+            payer: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+      }
+      delegatorBalancePda = balancePdaFor(delegator().publicKey);
+      validatorBalancePda = balancePdaFor(validator().publicKey);
+      contributorBalancePda = balancePdaFor(contributor().publicKey);
+      delegationPda = findPda([
+        Buffer.from("delegation"),
+        delegator().publicKey.toBuffer(),
+        topicPda.toBuffer(),
+      ]);
+
+      // The delegator earns tempAlign in the topic and stakes it for tempRep
+      const profile = await ctx.program.account.userProfile.fetch(
+        ctx.user3ProfilePda,
+      );
+      await ctx.program.methods
+        .submitDataToTopic("Delegator submission", profile.userSubmissionCount)
+        .accounts({
+          topic: topicPda,
+          tempAlignMint: ctx.tempAlignMintPda,
+          contributor: delegator().publicKey,
+          actingSigner: delegator().publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair, delegator()])
+        .rpc();
+      await ctx.program.methods
+        .stakeTopicSpecificTokens(stateAcc.tokensToMint)
+        .accounts({
+          topic: topicPda,
+          user: delegator().publicKey,
+          actingSigner: delegator().publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair, delegator()])
+        .rpc();
+    });
+
+    it("Passes the Rep reward through to the delegator net of commission", async () => {
+      const delegateAmount = 40;
+      // The validator draws only part of the delegation into the vote
+      const drawnAmount = 30;
+      // Sole winning validator, so the whole emission is the vote's reward on top of principal
+      const emission = 20;
+      const commissionBps = 1_000;
+      const expectedCommission = (emission * commissionBps) / 10_000;
+      const expectedNet = drawnAmount + emission - expectedCommission;
+
+      await updateCommission(commissionBps);
+      try {
+        await ctx.program.methods
+          .createDelegation(new BN(delegateAmount))
+          .accounts({
+            topic: topicPda,
+            userTopicBalance: delegatorBalancePda,
+            delegation: delegationPda,
+            validatorProfile: ctx.validatorProfilePda,
+            validator: validator().publicKey,
+            delegator: delegator().publicKey,
+            payer: delegator().publicKey,
+            systemProgram: web3.SystemProgram.programId,
+            rent: web3.SYSVAR_RENT_PUBKEY,
+          })
+          .signers([delegator()])
+          .rpc();
+
+        const { submissionPda, linkPda } = await submitAsContributor(
+          "Submission backed by delegated tempRep",
+        );
+
+        // The validator votes with delegated tempRep only
+        await commitVote(
+          validator(),
+          ctx.validatorRepAta,
+          submissionPda,
+          linkPda,
+          true,
+          0,
+          [delegationPda],
+          [drawnAmount],
+        );
+
+        const voteCommit = await ctx.program.account.voteCommit.fetch(
+          findPda([
+            Buffer.from("vote_commit"),
+            linkPda.toBuffer(),
+            validator().publicKey.toBuffer(),
+          ]),
+        );
+        expect(voteCommit.tempRepAmount.toNumber()).to.equal(0);
+        expect(voteCommit.delegatedTempRepAmount.toNumber()).to.equal(
+          drawnAmount,
+        );
+        expect(voteCommit.delegations.length).to.equal(1);
+        expect(voteCommit.delegations[0].delegation.toString()).to.equal(
+          delegationPda.toString(),
+        );
+        let delegation =
+          await ctx.program.account.delegation.fetch(delegationPda);
+        expect(delegation.amount.toNumber()).to.equal(
+          delegateAmount - drawnAmount,
+        );
+        expect(delegation.lockedAmount.toNumber()).to.equal(drawnAmount);
+
+        await setPhase(submissionPda, linkPda, "reveal");
+        await revealVote(validator(), submissionPda, true);
+        await setPhase(submissionPda, linkPda, "ended");
+        await setEmission(emission);
+        try {
+          await finalizeSubmission(submissionPda, linkPda);
+        } finally {
+          await setEmission(0);
+        }
+
+        const delegatorBalanceBefore =
+          await ctx.program.account.userTopicBalance.fetch(
+            delegatorBalancePda,
+          );
+        const validatorBalanceBefore =
+          await ctx.program.account.userTopicBalance.fetch(
+            validatorBalancePda,
+          );
+        const delegatorTempRepBefore = await tokenAmount(
+          ctx.user3TempRepAccount,
+        );
+        const delegatorRepBefore = await tokenAmount(ctx.user3RepAta);
+        const validatorRepBefore = await tokenAmount(ctx.validatorRepAta);
+
+        await finalizeValidatorVote(submissionPda, linkPda);
+
+        expect(await tokenAmount(ctx.user3TempRepAccount)).to.equal(
+          delegatorTempRepBefore - drawnAmount,
+        );
+        expect(await tokenAmount(ctx.user3RepAta)).to.equal(
+          delegatorRepBefore + expectedNet,
+        );
+        expect(await tokenAmount(ctx.validatorRepAta)).to.equal(
+          validatorRepBefore + expectedCommission,
+        );
+
+        const delegatorBalanceAfter =
+          await ctx.program.account.userTopicBalance.fetch(
+            delegatorBalancePda,
+          );
+        expect(delegatorBalanceAfter.repEarned.toNumber()).to.equal(
+          delegatorBalanceBefore.repEarned.toNumber() + expectedNet,
+        );
+        const validatorBalanceAfter =
+          await ctx.program.account.userTopicBalance.fetch(
+            validatorBalancePda,
+          );
+        expect(validatorBalanceAfter.repEarned.toNumber()).to.equal(
+          validatorBalanceBefore.repEarned.toNumber() + expectedCommission,
+        );
+
+        delegation = await ctx.program.account.delegation.fetch(delegationPda);
+        expect(delegation.amount.toNumber()).to.equal(
+          delegateAmount - drawnAmount,
+        );
+        expect(delegation.lockedAmount.toNumber()).to.equal(0);
+
+        // The undrawn part was never locked and can be withdrawn
+        await modifyDelegation(
+          "undelegateTempRep",
+          delegateAmount - drawnAmount,
+        );
+      } finally {
+        await updateCommission(0);
+      }
+    });
+
+    it("Burns delegated tempRep without Rep when the validator votes against consensus", async () => {
+      // Delegated power sqrt(9) = 3 loses to the delegator's own sqrt(16) = 4
+      const delegateAmount = 9;
+      const opposingAmount = 16;
+
+      await modifyDelegation("delegateTempRep", delegateAmount);
+
+      const { submissionPda, linkPda } = await submitAsContributor(
+        "Submission the delegated vote loses on",
+      );
+
+      await commitVote(
+        validator(),
+        ctx.validatorRepAta,
+        submissionPda,
+        linkPda,
+        true,
+        0,
+        [delegationPda],
+      );
+      await commitVote(
+        delegator(),
+        ctx.user3RepAta,
+        submissionPda,
+        linkPda,
+        false,
+        opposingAmount,
+        [],
+      );
+
+      await setPhase(submissionPda, linkPda, "reveal");
+      await revealVote(validator(), submissionPda, true);
+      await revealVote(delegator(), submissionPda, false);
+      await setPhase(submissionPda, linkPda, "ended");
+      await finalizeSubmission(submissionPda, linkPda);
+
+      const link =
+        await ctx.program.account.submissionTopicLink.fetch(linkPda);
+      expect(link.status.rejected).to.not.be.undefined;
+
+      const delegatorBalanceBefore =
+        await ctx.program.account.userTopicBalance.fetch(delegatorBalancePda);
+      const validatorBalanceBefore =
+        await ctx.program.account.userTopicBalance.fetch(validatorBalancePda);
+      const delegatorTempRepBefore = await tokenAmount(
+        ctx.user3TempRepAccount,
+      );
+      const delegatorRepBefore = await tokenAmount(ctx.user3RepAta);
+      const validatorRepBefore = await tokenAmount(ctx.validatorRepAta);
+
+      await finalizeValidatorVote(submissionPda, linkPda);
+
+      expect(await tokenAmount(ctx.user3TempRepAccount)).to.equal(
+        delegatorTempRepBefore - delegateAmount,
+      );
+      expect(await tokenAmount(ctx.user3RepAta)).to.equal(delegatorRepBefore);
+      expect(await tokenAmount(ctx.validatorRepAta)).to.equal(
+        validatorRepBefore,
+      );
+
+      const delegatorBalanceAfter =
+        await ctx.program.account.userTopicBalance.fetch(delegatorBalancePda);
+      expect(delegatorBalanceAfter.repEarned.toNumber()).to.equal(
+        delegatorBalanceBefore.repEarned.toNumber(),
+      );
+      const validatorBalanceAfter =
+        await ctx.program.account.userTopicBalance.fetch(validatorBalancePda);
+      expect(validatorBalanceAfter.repEarned.toNumber()).to.equal(
+        validatorBalanceBefore.repEarned.toNumber(),
+      );
+
+      const delegation =
+        await ctx.program.account.delegation.fetch(delegationPda);
+      expect(delegation.lockedAmount.toNumber()).to.equal(0);
+    });
+
+    it("Releases delegated tempRep locked in a vote that was never revealed", async () => {
+      const delegateAmount = 5;
+      await modifyDelegation("delegateTempRep", delegateAmount);
+
+      const { submissionPda, linkPda } = await submitAsContributor(
+        "Submission the validator never reveals on",
+      );
+      const voteCommitPda = findPda([
+        Buffer.from("vote_commit"),
+        linkPda.toBuffer(),
+        validator().publicKey.toBuffer(),
+      ]);
+      await commitVote(
+        validator(),
+        ctx.validatorRepAta,
+        submissionPda,
+        linkPda,
+        true,
+        0,
+        [delegationPda],
+      );
+      let delegation =
+        await ctx.program.account.delegation.fetch(delegationPda);
+      expect(delegation.amount.toNumber()).to.equal(0);
+      expect(delegation.lockedAmount.toNumber()).to.equal(delegateAmount);
+
+      const settleUnrevealed = () =>
+        ctx.program.methods
+          .settleUnrevealedVote()
+          .accounts({
+            submissionTopicLink: linkPda,
+            voteCommit: voteCommitPda,
+            userTopicBalance: validatorBalancePda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: delegationPda, isSigner: false, isWritable: true },
+          ])
+          .signers([ctx.authorityKeypair])
+          .rpc();
+
+      // The validator may still reveal, so nothing is released yet
+      await setPhase(submissionPda, linkPda, "reveal");
+      try {
+        await settleUnrevealed();
+        expect.fail("Settling during the reveal phase should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("RevealPhaseNotEnded");
+      }
+
+      const delegatorTempRepBefore = await tokenAmount(
+        ctx.user3TempRepAccount,
+      );
+      await setPhase(submissionPda, linkPda, "ended");
+      await settleUnrevealed();
+
+      delegation = await ctx.program.account.delegation.fetch(delegationPda);
+      expect(delegation.amount.toNumber()).to.equal(delegateAmount);
+      expect(delegation.lockedAmount.toNumber()).to.equal(0);
+      expect(await tokenAmount(ctx.user3TempRepAccount)).to.equal(
+        delegatorTempRepBefore,
+      );
+      const voteCommit =
+        await ctx.program.account.voteCommit.fetch(voteCommitPda);
+      expect(voteCommit.revealed).to.be.false;
+      expect(voteCommit.finalized).to.be.true;
+
+      // A released vote cannot be revealed afterwards
+      await setPhase(submissionPda, linkPda, "reveal");
+      try {
+        await revealVote(validator(), submissionPda, true);
+        expect.fail("Revealing a released vote should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("VoteAlreadyFinalized");
+      }
+
+      // The released tempRep can be withdrawn again
+      await modifyDelegation("undelegateTempRep", delegateAmount);
+      delegation = await ctx.program.account.delegation.fetch(delegationPda);
+      expect(delegation.amount.toNumber()).to.equal(0);
+    });
+  });
+}
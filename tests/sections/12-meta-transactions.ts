@@ -0,0 +1,244 @@
+import { expect } from "chai";
+import { web3, BN } from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { createHash } from "crypto";
+import { TestContext } from "../utils/test-setup";
+
+// Borsh-encoded `submit_data_to_topic` instruction data (discriminator + args)
+function submitDataToTopicData(dataReference: string, index: BN): Buffer {
+  const discriminator = createHash("sha256")
+    .update("global:submit_data_to_topic")
+    .digest()
+    .subarray(0, 8);
+  const reference = Buffer.from(dataReference);
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(reference.length);
+  return Buffer.concat([
+    discriminator,
+    length,
+    reference,
+    index.toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+// sha256("ALIGN_META_TX" || base instruction data || scope ||
+//        u32 count || remaining account keys || nonce || expires_at)
+function metaTxDigest(
+  instructionData: Buffer,
+  scope: web3.PublicKey,
+  remainingAccounts: web3.PublicKey[],
+  nonce: BN,
+  expiresAt: BN,
+): Buffer {
+  const count = Buffer.alloc(4);
+  count.writeUInt32LE(remainingAccounts.length);
+  return createHash("sha256")
+    .update(Buffer.from("ALIGN_META_TX"))
+    .update(instructionData)
+    .update(scope.toBuffer())
+    .update(count)
+    .update(Buffer.concat(remainingAccounts.map((key) => key.toBuffer())))
+    .update(nonce.toArrayLike(Buffer, "le", 8))
+    .update(expiresAt.toArrayLike(Buffer, "le", 8))
+    .digest();
+}
+
+export function runMetaTransactionTests(ctx: TestContext): void {
+  describe("Meta-Transactions", () => {
+    const dataReference = "Relayed submission";
+    let metaNoncePda: web3.PublicKey;
+
+    // Relay `submit_data_to_topic` for the contributor, paid by the authority,
+    // passing `remainingAccounts` while the signer signed `signedAccounts`
+    const relaySubmission = async (
+      signer: web3.Keypair | null,
+      payloadNonce: BN,
+      expiresAt = new BN(Math.floor(Date.now() / 1000) + 120),
+      remainingAccounts: web3.PublicKey[] = [],
+      signedAccounts = remainingAccounts,
+    ) => {
+      const profile = await ctx.program.account.userProfile.fetch(
+        ctx.contributorProfilePda,
+      );
+      const index = profile.userSubmissionCount;
+      const digest = metaTxDigest(
+        submitDataToTopicData(dataReference, index),
+        ctx.topic1Pda,
+        signedAccounts,
+        payloadNonce,
+        expiresAt,
+      );
+      const preInstructions = signer
+        ? [
+            web3.Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: signer.secretKey,
+              message: digest,
+            }),
+          ]
+        : [];
+
+      await ctx.program.methods
+        .submitDataToTopicMeta(
+          dataReference,
+          index,
+          payloadNonce,
+          expiresAt,
+        )
+        .accounts({
+          inner: {
+            topic: ctx.topic1Pda,
+            tempAlignMint: ctx.tempAlignMintPda,
+            contributor: ctx.contributorKeypair.publicKey,
+            actingSigner: ctx.authorityKeypair.publicKey,
+            payer: ctx.authorityKeypair.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          },
+          metaNonce: metaNoncePda,
+          instructions: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .remainingAccounts(
+          remainingAccounts.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: false,
+          })),
+        )
+        .preInstructions(preInstructions)
+        .signers([ctx.authorityKeypair])
+        .rpc();
+      return index;
+    };
+
+    before("Create the contributor's meta-transaction nonce", async () => {
+      [metaNoncePda] = web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("meta_nonce"),
+          ctx.contributorKeypair.publicKey.toBuffer(),
+        ],
+        ctx.program.programId,
+      );
+      await ctx.program.methods
+        .initializeMetaNonce()
+        .accounts({
+          metaNonce: metaNoncePda,
+          user: ctx.contributorKeypair.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      const nonceAcc = await ctx.program.account.metaNonce.fetch(metaNoncePda);
+      expect(nonceAcc.user.toString()).to.equal(
+        ctx.contributorKeypair.publicKey.toString(),
+      );
+      expect(nonceAcc.nonce.toNumber()).to.equal(0);
+    });
+
+    it("Rejects a relayed submission without the user's signature", async () => {
+      try {
+        await relaySubmission(null, new BN(0));
+        expect.fail("Relayed submission without a signature should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("MetaTxSignatureMissing");
+      }
+    });
+
+    it("Rejects a relayed submission signed by someone else", async () => {
+      try {
+        await relaySubmission(ctx.validatorKeypair, new BN(0));
+        expect.fail("Relayed submission signed by another key should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("MetaTxSignatureMissing");
+      }
+    });
+
+    it("Relays a submission signed by the user", async () => {
+      const index = await relaySubmission(ctx.contributorKeypair, new BN(0));
+
+      const [submissionPda] = web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("submission"),
+          ctx.contributorKeypair.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        ctx.program.programId,
+      );
+      const submission =
+        await ctx.program.account.submission.fetch(submissionPda);
+      expect(submission.contributor.toString()).to.equal(
+        ctx.contributorKeypair.publicKey.toString(),
+      );
+      expect(submission.dataReference).to.equal(dataReference);
+
+      const nonceAcc = await ctx.program.account.metaNonce.fetch(metaNoncePda);
+      expect(nonceAcc.nonce.toNumber()).to.equal(1);
+    });
+
+    it("Rejects an expired payload", async () => {
+      const expiredAt = new BN(Math.floor(Date.now() / 1000) - 3600);
+      try {
+        await relaySubmission(ctx.contributorKeypair, new BN(1), expiredAt);
+        expect.fail("Expired payload should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("MetaTxExpired");
+      }
+
+      const nonceAcc = await ctx.program.account.metaNonce.fetch(metaNoncePda);
+      expect(nonceAcc.nonce.toNumber()).to.equal(1);
+    });
+
+    it("Rejects a payload carrying a used nonce", async () => {
+      try {
+        await relaySubmission(ctx.contributorKeypair, new BN(0));
+        expect.fail("Reused nonce should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("InvalidMetaNonce");
+      }
+    });
+
+    it("Rejects remaining accounts the user did not sign for", async () => {
+      const signed = web3.Keypair.generate().publicKey;
+      const swapped = web3.Keypair.generate().publicKey;
+      for (const [passed, signedFor] of [
+        [[signed], []],
+        [[swapped], [signed]],
+        [[signed, swapped], [swapped, signed]],
+      ]) {
+        try {
+          await relaySubmission(
+            ctx.contributorKeypair,
+            new BN(1),
+            undefined,
+            passed,
+            signedFor,
+          );
+          expect.fail("Unsigned remaining accounts should fail");
+        } catch (error: any) {
+          expect(error.error.errorCode.code).to.equal(
+            "MetaTxSignatureMissing",
+          );
+        }
+      }
+
+      const nonceAcc = await ctx.program.account.metaNonce.fetch(metaNoncePda);
+      expect(nonceAcc.nonce.toNumber()).to.equal(1);
+    });
+
+    it("Relays a payload with the remaining accounts the user signed", async () => {
+      const remaining = [
+        web3.Keypair.generate().publicKey,
+        web3.Keypair.generate().publicKey,
+      ];
+      await relaySubmission(
+        ctx.contributorKeypair,
+        new BN(1),
+        undefined,
+        remaining,
+      );
+
+      const nonceAcc = await ctx.program.account.metaNonce.fetch(metaNoncePda);
+      expect(nonceAcc.nonce.toNumber()).to.equal(2);
+    });
+  });
+}
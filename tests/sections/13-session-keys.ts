@@ -0,0 +1,184 @@
+import { expect } from "chai";
+import { web3, BN } from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { TestContext } from "../utils/test-setup";
+
+// Mirrors the SESSION_PERMISSION_* constants in data.rs
+const SESSION_PERMISSION_SUBMIT = 1 << 0;
+const SESSION_PERMISSION_COMMIT_VOTE = 1 << 1;
+
+export function runSessionKeyTests(ctx: TestContext): void {
+  describe("Session Keys", () => {
+    const submitSession = web3.Keypair.generate();
+    const voteSession = web3.Keypair.generate();
+
+    const sessionKeyPda = (sessionSigner: web3.PublicKey) =>
+      web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("session_key"),
+          ctx.contributorKeypair.publicKey.toBuffer(),
+          sessionSigner.toBuffer(),
+        ],
+        ctx.program.programId,
+      )[0];
+
+    const createSession = async (
+      sessionSigner: web3.PublicKey,
+      expiresAt: number,
+      permissions: number,
+    ) => {
+      await ctx.program.methods
+        .createSessionKey(sessionSigner, new BN(expiresAt), permissions)
+        .accounts({
+          sessionKey: sessionKeyPda(sessionSigner),
+          user: ctx.contributorKeypair.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ctx.contributorKeypair, ctx.authorityKeypair])
+        .rpc();
+    };
+
+    // Submit for the contributor, signed by `actingSigner` and paid by the authority
+    const submitAs = async (
+      actingSigner: web3.Keypair,
+      sessionKey: web3.PublicKey | null,
+      dataReference: string,
+    ) => {
+      const profile = await ctx.program.account.userProfile.fetch(
+        ctx.contributorProfilePda,
+      );
+      const index = profile.userSubmissionCount;
+      await ctx.program.methods
+        .submitDataToTopic(dataReference, index)
+        .accounts({
+          topic: ctx.topic1Pda,
+          tempAlignMint: ctx.tempAlignMintPda,
+          contributor: ctx.contributorKeypair.publicKey,
+          actingSigner: actingSigner.publicKey,
+          sessionKey,
+          payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair, actingSigner])
+        .rpc();
+      return index;
+    };
+
+    const now = () => Math.floor(Date.now() / 1000);
+
+    it("Creates a session key for the contributor", async () => {
+      const expiresAt = now() + 3600;
+      await createSession(
+        submitSession.publicKey,
+        expiresAt,
+        SESSION_PERMISSION_SUBMIT,
+      );
+
+      const session = await ctx.program.account.sessionKey.fetch(
+        sessionKeyPda(submitSession.publicKey),
+      );
+      expect(session.user.toString()).to.equal(
+        ctx.contributorKeypair.publicKey.toString(),
+      );
+      expect(session.sessionSigner.toString()).to.equal(
+        submitSession.publicKey.toString(),
+      );
+      expect(session.expiresAt.toNumber()).to.equal(expiresAt);
+      expect(session.permissions).to.equal(SESSION_PERMISSION_SUBMIT);
+    });
+
+    it("Rejects a session key valid for longer than the maximum duration", async () => {
+      try {
+        await createSession(
+          web3.Keypair.generate().publicKey,
+          now() + 30 * 24 * 60 * 60,
+          SESSION_PERMISSION_SUBMIT,
+        );
+        expect.fail("Session beyond the maximum duration should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("InvalidSessionExpiry");
+      }
+    });
+
+    it("Submits through the session key", async () => {
+      const dataReference = "Session submission";
+      const index = await submitAs(
+        submitSession,
+        sessionKeyPda(submitSession.publicKey),
+        dataReference,
+      );
+
+      const [submissionPda] = web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("submission"),
+          ctx.contributorKeypair.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        ctx.program.programId,
+      );
+      const submission =
+        await ctx.program.account.submission.fetch(submissionPda);
+      expect(submission.contributor.toString()).to.equal(
+        ctx.contributorKeypair.publicKey.toString(),
+      );
+      expect(submission.dataReference).to.equal(dataReference);
+    });
+
+    it("Accepts the contributor as the acting signer without a session key", async () => {
+      await submitAs(ctx.contributorKeypair, null, "Self-signed submission");
+    });
+
+    it("Rejects an acting signer without a session key", async () => {
+      try {
+        await submitAs(web3.Keypair.generate(), null, "Unauthorized");
+        expect.fail("Signer without a session key should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal(
+          "UnauthorizedActingSigner",
+        );
+      }
+    });
+
+    it("Rejects a session key without the submit permission", async () => {
+      await createSession(
+        voteSession.publicKey,
+        now() + 3600,
+        SESSION_PERMISSION_COMMIT_VOTE,
+      );
+      try {
+        await submitAs(
+          voteSession,
+          sessionKeyPda(voteSession.publicKey),
+          "Not permitted",
+        );
+        expect.fail("Session without the submit permission should fail");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("SessionPermissionDenied");
+      }
+    });
+
+    it("Revokes a session key", async () => {
+      const pda = sessionKeyPda(submitSession.publicKey);
+      await ctx.program.methods
+        .revokeSessionKey()
+        .accounts({
+          sessionKey: pda,
+          user: ctx.contributorKeypair.publicKey,
+          signer: submitSession.publicKey,
+        })
+        .signers([submitSession])
+        .rpc();
+
+      const account = await ctx.provider.connection.getAccountInfo(pda);
+      expect(account).to.be.null;
+
+      try {
+        await submitAs(submitSession, pda, "After revocation");
+        expect.fail("Revoked session key should no longer work");
+      } catch (error: any) {
+        expect(error.error.errorCode.code).to.equal("AccountNotInitialized");
+      }
+    });
+  });
+}
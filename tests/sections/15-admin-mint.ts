@@ -0,0 +1,206 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { BN } from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { TestContext } from "../utils/test-setup";
+
+export function runAdminMintTests(ctx: TestContext): void {
+  describe("Admin Mint", () => {
+    const ALIGN_INDEX = 1;
+
+    type TokenType = Parameters<
+      typeof ctx.program.methods.updateAdminMintCap
+    >[0];
+
+    const updateCap = (tokenType: TokenType, cap: number) =>
+      ctx.program.methods
+        .updateAdminMintCap(tokenType, new BN(cap))
+        .accounts({
+          state: ctx.statePda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+    const mintAlign = (amount: number, reason: string) =>
+      ctx.program.methods
+        .adminMint({ align: {} }, new BN(amount), reason)
+        .accounts({
+          state: ctx.statePda,
+          authority: ctx.authorityKeypair.publicKey,
+          recipient: ctx.user3Keypair.publicKey,
+          mint: ctx.alignMintPda,
+          recipientTokenAccount: ctx.user3AlignAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc({ commitment: "confirmed" });
+
+    it("Rejects admin mints until a cap is set", async () => {
+      try {
+        await mintAlign(1, "Before any cap");
+        expect.fail("Admin mint should fail while the cap is zero");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AdminMintCapExceeded");
+      }
+    });
+
+    it("Mints permanent Align up to the cap and emits an event", async () => {
+      await updateCap({ align: {} }, 1_000);
+
+      const before = await getAccount(
+        ctx.provider.connection,
+        ctx.user3AlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      const reason = "Genesis contributor grant";
+      const txSig = await mintAlign(400, reason);
+
+      const after = await getAccount(
+        ctx.provider.connection,
+        ctx.user3AlignAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(Number(after.amount - before.amount)).to.equal(400);
+
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      expect(stateAcc.adminMinted[ALIGN_INDEX].toNumber()).to.equal(400);
+      expect(stateAcc.adminMintCaps[ALIGN_INDEX].toNumber()).to.equal(1_000);
+
+      const tx = await ctx.provider.connection.getTransaction(txSig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(
+        ctx.program.programId,
+        ctx.program.coder,
+      );
+      const events = Array.from(parser.parseLogs(tx.meta.logMessages));
+      const minted = events.find(
+        (event) => event.name.toLowerCase() === "adminminted",
+      );
+      expect(minted).to.not.be.undefined;
+      expect(minted.data.reason).to.equal(reason);
+      expect(minted.data.amount.toNumber()).to.equal(400);
+      expect(minted.data.totalMinted.toNumber()).to.equal(400);
+      expect(minted.data.recipient.toBase58()).to.equal(
+        ctx.user3Keypair.publicKey.toBase58(),
+      );
+      expect(minted.data.topic).to.be.null;
+
+      try {
+        await mintAlign(601, "Over the cap");
+        expect.fail("Admin mint should not exceed the lifetime cap");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AdminMintCapExceeded");
+      }
+    });
+
+    it("Credits the topic balance when minting temporary tokens", async () => {
+      await updateCap({ tempAlign: {} }, 100);
+
+      const balanceBefore = await ctx.program.account.userTopicBalance.fetch(
+        ctx.user3Topic1BalancePda,
+      );
+
+      await ctx.program.methods
+        .adminMint({ tempAlign: {} }, new BN(50), "Topic bootstrap")
+        .accounts({
+          state: ctx.statePda,
+          authority: ctx.authorityKeypair.publicKey,
+          recipient: ctx.user3Keypair.publicKey,
+          mint: ctx.tempAlignMintPda,
+          recipientTokenAccount: ctx.user3TempAlignAccount,
+          userTopicBalance: ctx.user3Topic1BalancePda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      const balanceAfter = await ctx.program.account.userTopicBalance.fetch(
+        ctx.user3Topic1BalancePda,
+      );
+      expect(
+        balanceAfter.tempAlignAmount.sub(balanceBefore.tempAlignAmount).toNumber(),
+      ).to.equal(50);
+      // Like faucet tempAlign, it is tracked so finalization never converts it to Align
+      expect(
+        balanceAfter.unconvertibleTempAlignAmount
+          .sub(balanceBefore.unconvertibleTempAlignAmount)
+          .toNumber(),
+      ).to.equal(50);
+
+      // Temporary tokens must be credited to a topic
+      try {
+        await ctx.program.methods
+          .adminMint({ tempAlign: {} }, new BN(10), "No topic")
+          .accounts({
+            state: ctx.statePda,
+            authority: ctx.authorityKeypair.publicKey,
+            recipient: ctx.user3Keypair.publicKey,
+            mint: ctx.tempAlignMintPda,
+            recipientTokenAccount: ctx.user3TempAlignAccount,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("Temporary admin mints should require a topic balance");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("MissingUserTopicBalance");
+      }
+    });
+
+    it("Rejects mints from non-authorities and mismatched mints", async () => {
+      try {
+        await ctx.program.methods
+          .adminMint({ align: {} }, new BN(1), "Not the authority")
+          .accounts({
+            state: ctx.statePda,
+            authority: ctx.user3Keypair.publicKey,
+            recipient: ctx.user3Keypair.publicKey,
+            mint: ctx.alignMintPda,
+            recipientTokenAccount: ctx.user3AlignAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.user3Keypair])
+          .rpc();
+        expect.fail("Only the authority may admin mint");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ConstraintHasOne");
+      }
+
+      try {
+        await ctx.program.methods
+          .adminMint({ rep: {} }, new BN(1), "Wrong mint")
+          .accounts({
+            state: ctx.statePda,
+            authority: ctx.authorityKeypair.publicKey,
+            recipient: ctx.user3Keypair.publicKey,
+            mint: ctx.alignMintPda,
+            recipientTokenAccount: ctx.user3AlignAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+        expect.fail("The mint must match the requested token type");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TokenMintMismatch");
+      }
+    });
+
+    it("Does not lower a cap below the amount already minted", async () => {
+      try {
+        await updateCap({ align: {} }, 399);
+        expect.fail("Cap should not drop below the minted total");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AdminMintCapBelowMinted");
+      }
+
+      await updateCap({ align: {} }, 400);
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      expect(stateAcc.adminMintCaps[ALIGN_INDEX].toNumber()).to.equal(400);
+    });
+  });
+}
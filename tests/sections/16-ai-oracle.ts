@@ -0,0 +1,214 @@
+import { expect } from "chai";
+import { web3, BN } from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { TestContext } from "../utils/test-setup";
+
+// Mirrors TOPIC_ROLE_ORACLE in data.rs
+const TOPIC_ROLE_ORACLE = 1 << 2;
+
+export function runAiOracleTests(ctx: TestContext): void {
+  describe("AI Oracle Authorization", () => {
+    const requester = () => ctx.user3Keypair;
+    let topicPda: web3.PublicKey;
+    let balancePda: web3.PublicKey;
+    let submissionPda: web3.PublicKey;
+    let linkPda: web3.PublicKey;
+    let aiRequestPda: web3.PublicKey;
+    let aiRequestIndex: BN;
+
+    const findPda = (seeds: Buffer[]) =>
+      web3.PublicKey.findProgramAddressSync(seeds, ctx.program.programId)[0];
+
+    const submitAiVote = (
+      oracle: web3.Keypair,
+      topicRole: web3.PublicKey | null,
+    ) =>
+      ctx.program.methods
+        .submitAiVote(
+          aiRequestIndex,
+          { yes: {} },
+          Array(32).fill(1),
+          Array(32).fill(2),
+          9000,
+        )
+        .accounts({
+          oracle: oracle.publicKey,
+          state: ctx.statePda,
+          aiValidationRequest: aiRequestPda,
+          submissionTopicLink: linkPda,
+          topicRole,
+          topic: topicPda,
+        })
+        .signers([oracle])
+        .rpc();
+
+    before("Open an AI request on a fresh topic", async () => {
+      const stateAcc = await ctx.program.account.state.fetch(ctx.statePda);
+      topicPda = findPda([
+        Buffer.from("topic"),
+        stateAcc.topicCount.toArrayLike(Buffer, "le", 8),
+      ]);
+      await ctx.program.methods
+        .createTopic("AI Oracle Topic", "Topic for AI oracle checks", null, null)
+        .accounts({
+          creator: ctx.authorityKeypair.publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      balancePda = findPda([
+        Buffer.from("user_topic_balance"),
+        requester().publicKey.toBuffer(),
+        topicPda.toBuffer(),
+      ]);
+      await ctx.program.methods
+        .initializeUserTopicBalance()
+        .accounts({
+          user: requester().publicKey,
+          topic: topicPda,
+          payer: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      const profile = await ctx.program.account.userProfile.fetch(
+        ctx.user3ProfilePda,
+      );
+      submissionPda = findPda([
+        Buffer.from("submission"),
+        requester().publicKey.toBuffer(),
+        profile.userSubmissionCount.toArrayLike(Buffer, "le", 8),
+      ]);
+      linkPda = findPda([
+        Buffer.from("submission_topic_link"),
+        submissionPda.toBuffer(),
+        topicPda.toBuffer(),
+      ]);
+      await ctx.program.methods
+        .submitDataToTopic("AI oracle submission", profile.userSubmissionCount)
+        .accounts({
+          topic: topicPda,
+          tempAlignMint: ctx.tempAlignMintPda,
+          contributor: requester().publicKey,
+          actingSigner: requester().publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair, requester()])
+        .rpc();
+
+      await ctx.program.methods
+        .stakeTopicSpecificTokens(stateAcc.tokensToMint)
+        .accounts({
+          topic: topicPda,
+          user: requester().publicKey,
+          actingSigner: requester().publicKey,
+          payer: ctx.authorityKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([ctx.authorityKeypair, requester()])
+        .rpc();
+
+      const balance =
+        await ctx.program.account.userTopicBalance.fetch(balancePda);
+      aiRequestIndex = balance.userAiRequestCount;
+      aiRequestPda = findPda([
+        Buffer.from("ai_request"),
+        linkPda.toBuffer(),
+        requester().publicKey.toBuffer(),
+        aiRequestIndex.toArrayLike(Buffer, "le", 8),
+      ]);
+      await ctx.program.methods
+        .requestAiValidation(new BN(1), aiRequestIndex)
+        .accounts({
+          requester: requester().publicKey,
+          submission: submissionPda,
+          topic: topicPda,
+          submissionTopicLink: linkPda,
+          userTopicBalance: balancePda,
+          aiValidationRequest: aiRequestPda,
+        })
+        .signers([requester()])
+        .rpc();
+    });
+
+    it("Rejects the contributor deciding their own request as topic oracle", async () => {
+      await ctx.program.methods
+        .updateTopicOracle(requester().publicKey)
+        .accounts({
+          state: ctx.statePda,
+          topic: topicPda,
+          authority: ctx.authorityKeypair.publicKey,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      try {
+        await submitAiVote(requester(), null);
+        expect.fail("The contributor should not judge their own submission");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("SelfVotingNotAllowed");
+      } finally {
+        await ctx.program.methods
+          .updateTopicOracle(null)
+          .accounts({
+            state: ctx.statePda,
+            topic: topicPda,
+            authority: ctx.authorityKeypair.publicKey,
+          })
+          .signers([ctx.authorityKeypair])
+          .rpc();
+      }
+    });
+
+    it("Rejects oracle role holders deciding alone once a topic committee is active", async () => {
+      const oracle = ctx.validatorKeypair;
+      const topicRolePda = findPda([
+        Buffer.from("topic_role"),
+        topicPda.toBuffer(),
+        oracle.publicKey.toBuffer(),
+      ]);
+      await ctx.program.methods
+        .grantTopicRole(TOPIC_ROLE_ORACLE)
+        .accounts({
+          state: ctx.statePda,
+          topic: topicPda,
+          topicRole: topicRolePda,
+          holder: oracle.publicKey,
+          authority: ctx.authorityKeypair.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+      await ctx.program.methods
+        .initializeTopicOracleCommittee(
+          [oracle.publicKey, ctx.authorityKeypair.publicKey],
+          2,
+        )
+        .accounts({
+          state: ctx.statePda,
+          topic: topicPda,
+          oracleCommittee: findPda([
+            Buffer.from("oracle_committee"),
+            topicPda.toBuffer(),
+          ]),
+          authority: ctx.authorityKeypair.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ctx.authorityKeypair])
+        .rpc();
+
+      try {
+        await submitAiVote(oracle, topicRolePda);
+        expect.fail("Committee topics should only accept attestations");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedOracle");
+      }
+
+      const aiRequest =
+        await ctx.program.account.aiValidationRequest.fetch(aiRequestPda);
+      expect(aiRequest.status.pending).to.not.be.undefined;
+    });
+  });
+}